@@ -0,0 +1,308 @@
+use crate::commands::linker::{project_skills_dir, user_skills_dir};
+use crate::commands::{profiles, scanner};
+use crate::commands::settings::{self, data_subdir};
+use crate::error::AppError;
+use crate::models::{Profile, Skill};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::os::unix::fs as unix_fs;
+use std::path::{Component, Path, PathBuf};
+use walkdir::WalkDir;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum AdoptMode {
+    /// Move the directory into the repository
+    Move,
+    /// Copy it into the repository and archive the original in the config dir
+    Copy,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AdoptResult {
+    /// The adopted skill as found by the rescan
+    pub skill: Skill,
+    /// Where the original directory was archived (Copy mode only)
+    pub archived_to: Option<String>,
+    /// The profile the skill was added to, if requested
+    pub profile: Option<Profile>,
+}
+
+/// Completed steps, undone in reverse order on failure
+enum Step {
+    Renamed { from: PathBuf, to: PathBuf },
+    Copied { to: PathBuf },
+    Linked { link: PathBuf },
+}
+
+fn rollback(steps: Vec<Step>) {
+    for step in steps.into_iter().rev() {
+        let _ = match step {
+            Step::Renamed { from, to } => fs::rename(&to, &from),
+            Step::Copied { to } => fs::remove_dir_all(&to),
+            Step::Linked { link } => fs::remove_file(&link),
+        };
+    }
+}
+
+fn archive_dir() -> Result<PathBuf, AppError> {
+    data_subdir("adopted")
+}
+
+/// Recursively copy a directory, recreating symlinks instead of following them
+fn copy_dir(from: &Path, to: &Path) -> Result<(), AppError> {
+    for entry in WalkDir::new(from).follow_links(false) {
+        let entry = entry.map_err(|e| AppError::Custom(e.to_string()))?;
+        let rel = entry.path().strip_prefix(from).unwrap_or(entry.path());
+        let dest = to.join(rel);
+        let file_type = entry.file_type();
+        if file_type.is_dir() {
+            fs::create_dir_all(&dest)?;
+        } else if file_type.is_symlink() {
+            unix_fs::symlink(fs::read_link(entry.path())?, &dest)?;
+        } else {
+            fs::copy(entry.path(), &dest)?;
+        }
+    }
+    Ok(())
+}
+
+/// Move a directory, falling back to copy when rename crosses file systems
+fn move_dir(from: &Path, to: &Path, steps: &mut Vec<Step>) -> Result<(), AppError> {
+    if fs::rename(from, to).is_ok() {
+        steps.push(Step::Renamed {
+            from: from.to_path_buf(),
+            to: to.to_path_buf(),
+        });
+        return Ok(());
+    }
+    copy_dir(from, to).inspect_err(|_| {
+        let _ = fs::remove_dir_all(to);
+    })?;
+    steps.push(Step::Copied { to: to.to_path_buf() });
+    Ok(())
+}
+
+/// Validate a repo-relative destination directory (no absolute paths or `..`)
+fn checked_dest_dir(repo_root: &Path, dest_dir: &str) -> Result<PathBuf, AppError> {
+    let rel = Path::new(dest_dir);
+    if rel
+        .components()
+        .any(|c| !matches!(c, Component::Normal(_) | Component::CurDir))
+    {
+        return Err(AppError::Custom(format!(
+            "Destination must be a path inside the repository: {}",
+            dest_dir
+        )));
+    }
+    Ok(repo_root.join(rel))
+}
+
+fn run_adoption(
+    src: &Path,
+    dest: &Path,
+    mode: AdoptMode,
+    repo_path: &str,
+    profile_id: Option<String>,
+    steps: &mut Vec<Step>,
+) -> Result<(Skill, Option<Profile>, Option<PathBuf>), AppError> {
+    // 1. Put the skill into the repository
+    match mode {
+        AdoptMode::Move => move_dir(src, dest, steps)?,
+        AdoptMode::Copy => {
+            copy_dir(src, dest).inspect_err(|_| {
+                let _ = fs::remove_dir_all(dest);
+            })?;
+            steps.push(Step::Copied { to: dest.to_path_buf() });
+        }
+    }
+
+    // 2. Park the original beside the link location so the name is free
+    let parked = src.with_file_name(format!(
+        ".{}.sp-adopt",
+        src.file_name().unwrap_or_default().to_string_lossy()
+    ));
+    if src.exists() {
+        fs::rename(src, &parked)?;
+        steps.push(Step::Renamed {
+            from: src.to_path_buf(),
+            to: parked.clone(),
+        });
+    }
+
+    // 3. Replace it with a symlink
+    unix_fs::symlink(dest, src)?;
+    steps.push(Step::Linked { link: src.to_path_buf() });
+
+    // 4. Rescan and find the adopted skill
+    let canon_dest = dest.canonicalize()?;
+    let skill = scanner::scan_skills_repo(Some(repo_path.to_string()))?
+        .into_iter()
+        .find(|s| s.source_path.canonicalize().ok().as_ref() == Some(&canon_dest))
+        .ok_or_else(|| {
+            AppError::Custom(format!(
+                "Adopted directory was not found by the scanner (excluded directory, or shadowed by a skill with the same name): {}",
+                dest.display()
+            ))
+        })?;
+
+    // 5. Optionally register it in a profile
+    let profile = match profile_id {
+        Some(id) => {
+            let mut profile = profiles::load_profiles()?
+                .into_iter()
+                .find(|p| p.id == id)
+                .ok_or_else(|| AppError::Custom(format!("Profile not found: {}", id)))?;
+            if !profile.skill_ids.contains(&skill.id) {
+                profile.skill_ids.push(skill.id.clone());
+            }
+            Some(profiles::save_profile(profile)?)
+        }
+        None => None,
+    };
+
+    Ok((skill, profile, parked.exists().then_some(parked)))
+}
+
+/// Adopt a real skill directory from a skills dir into the repository:
+/// move or copy it to `<repo>/<dest_dir>/<name>`, replace it with a symlink,
+/// rescan and optionally add it to a profile. Every step is rolled back on failure.
+#[tauri::command]
+pub fn adopt_skill_directory(
+    skill_name: String,
+    repo_path: Option<String>,
+    dest_dir: String,
+    mode: AdoptMode,
+    project_path: Option<String>,
+    profile_id: Option<String>,
+) -> Result<AdoptResult, AppError> {
+    let link_dir = match &project_path {
+        Some(p) => project_skills_dir(p),
+        None => user_skills_dir()?,
+    };
+    let src = link_dir.join(&skill_name);
+    let meta = src.symlink_metadata().map_err(|_| {
+        AppError::Custom(format!("Skill directory not found: {}", src.display()))
+    })?;
+    if meta.file_type().is_symlink() || !meta.is_dir() {
+        return Err(AppError::Custom(format!(
+            "Not a real directory, nothing to adopt: {}",
+            src.display()
+        )));
+    }
+    if !src.join("SKILL.md").is_file() {
+        return Err(AppError::Custom(format!(
+            "No SKILL.md in {}",
+            src.display()
+        )));
+    }
+
+    let repo_path = settings::repo_path(repo_path)?;
+    let repo_root = PathBuf::from(&repo_path);
+    let dest_parent = checked_dest_dir(&repo_root, &dest_dir)?;
+    let dest = dest_parent.join(&skill_name);
+    if dest.symlink_metadata().is_ok() {
+        return Err(AppError::Custom(format!(
+            "Destination already exists: {}",
+            dest.display()
+        )));
+    }
+    fs::create_dir_all(&dest_parent)?;
+
+    let mut steps = Vec::new();
+    let (skill, profile, parked) =
+        match run_adoption(&src, &dest, mode, &repo_path, profile_id, &mut steps) {
+            Ok(r) => r,
+            Err(e) => {
+                rollback(steps);
+                return Err(e);
+            }
+        };
+
+    // Finalize: drop or archive the parked original. Failures here leave a
+    // recoverable copy behind rather than undoing a completed adoption.
+    let mut archived_to = None;
+    if let Some(parked) = parked {
+        match mode {
+            AdoptMode::Move => {
+                let _ = fs::remove_dir_all(&parked);
+            }
+            AdoptMode::Copy => {
+                let archive = archive_dir()?.join(format!(
+                    "{}-{}",
+                    skill_name,
+                    chrono::Utc::now().format("%Y%m%d%H%M%S")
+                ));
+                if fs::rename(&parked, &archive).is_err() {
+                    copy_dir(&parked, &archive)?;
+                    fs::remove_dir_all(&parked)?;
+                }
+                archived_to = Some(archive.to_string_lossy().to_string());
+            }
+        }
+    }
+
+    Ok(AdoptResult {
+        skill,
+        archived_to,
+        profile,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("skillpilot-adopt-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn skill_dir(root: &Path) -> PathBuf {
+        let dir = root.join("review");
+        fs::create_dir_all(dir.join("scripts")).unwrap();
+        fs::write(dir.join("SKILL.md"), "# Review").unwrap();
+        unix_fs::symlink("SKILL.md", dir.join("README.md")).unwrap();
+        dir
+    }
+
+    #[test]
+    fn destinations_must_stay_inside_the_repository() {
+        let repo = Path::new("/repo");
+        assert_eq!(checked_dest_dir(repo, "skills/./mine").unwrap(), repo.join("skills/./mine"));
+        for dest in ["/etc", "../outside", "skills/../../x"] {
+            assert!(checked_dest_dir(repo, dest).is_err(), "{}", dest);
+        }
+    }
+
+    #[test]
+    fn copies_keep_symlinks_as_links() {
+        let root = temp_dir();
+        let src = skill_dir(&root);
+        let dest = root.join("copy");
+
+        copy_dir(&src, &dest).unwrap();
+        assert_eq!(fs::read_to_string(dest.join("SKILL.md")).unwrap(), "# Review");
+        assert!(dest.join("scripts").is_dir());
+        assert_eq!(fs::read_link(dest.join("README.md")).unwrap(), Path::new("SKILL.md"));
+    }
+
+    #[test]
+    fn rollback_puts_the_original_back() {
+        let root = temp_dir();
+        let src = skill_dir(&root);
+        let dest = root.join("repo/review");
+        fs::create_dir_all(dest.parent().unwrap()).unwrap();
+
+        let mut steps = Vec::new();
+        move_dir(&src, &dest, &mut steps).unwrap();
+        unix_fs::symlink(&dest, &src).unwrap();
+        steps.push(Step::Linked { link: src.clone() });
+        assert!(src.is_symlink());
+
+        rollback(steps);
+        assert!(!src.is_symlink());
+        assert_eq!(fs::read_to_string(src.join("SKILL.md")).unwrap(), "# Review");
+        assert!(!dest.exists());
+    }
+}
@@ -0,0 +1,547 @@
+//! Backup and restore of the whole data root as one `.tar.gz` archive.
+//!
+//! The archive holds `manifest.json`, the data root under `data/` and, only when asked for,
+//! `secrets.bin`: the Keychain passwords of the remote servers, encrypted with
+//! ChaCha20-Poly1305 under a key derived from a passphrase (salt | nonce | ciphertext).
+//! Locks, temp files, backups, the `backups/` directory and the config sync repository
+//! (`.git/`) are left out.
+
+use crate::commands::discovery::{DiscoveryConfig, DISCOVERY_SCHEMA};
+use crate::commands::projects::{self, update_projects, PROJECTS_SCHEMA};
+use crate::commands::remote::{self, update_remotes, REMOTES_SCHEMA};
+use crate::commands::settings::{self, data_dir, data_subdir, SETTINGS_SCHEMA};
+use crate::error::AppError;
+use crate::models::remote::{RemoteServer, SshAuth};
+use crate::models::{
+    AppSettings, BackupExportResult, BackupImportResult, BackupManifest, ImportMode, PathMapping, ProjectConfig,
+};
+use crate::ssh::connection::{read_keychain_password, save_keychain_password};
+use crate::store;
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use sha2::Sha256;
+use std::collections::{BTreeMap, HashSet};
+use std::fs::{self, File};
+use std::os::unix::fs as unix_fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// Version of the archive layout
+const FORMAT_VERSION: u32 = 1;
+
+const MANIFEST_NAME: &str = "manifest.json";
+const DATA_DIR_NAME: &str = "data";
+const SECRETS_NAME: &str = "secrets.bin";
+
+/// Directory of the data root holding the archives written before a replacing import
+const BACKUPS_DIR_NAME: &str = "backups";
+
+/// Git repository of the data root when config sync is on; it stays with the machine
+const GIT_DIR_NAME: &str = ".git";
+
+/// Directories of the data root whose entries are merged as a whole, never file by file
+const MERGE_UNIT_DIRS: &[&str] = &["profile-history", "adopted"];
+
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+const KDF_ROUNDS: u32 = 600_000;
+
+/// Files of the data root that are not part of a backup
+fn is_excluded(rel: &Path) -> bool {
+    if rel
+        .components()
+        .next()
+        .is_some_and(|c| c.as_os_str() == BACKUPS_DIR_NAME || c.as_os_str() == GIT_DIR_NAME)
+    {
+        return true;
+    }
+    let name = rel.file_name().unwrap_or_default().to_string_lossy();
+    name == ".lock" || name.ends_with(".tmp") || name.ends_with(".bak")
+}
+
+/// Files and symlinks below `root` that belong in a backup, relative to `root`
+fn data_files(root: &Path) -> Result<Vec<PathBuf>, AppError> {
+    let mut files = Vec::new();
+    for entry in WalkDir::new(root).follow_links(false).sort_by_file_name() {
+        let entry = entry.map_err(|e| AppError::Custom(e.to_string()))?;
+        if entry.file_type().is_dir() {
+            continue;
+        }
+        let rel = entry.path().strip_prefix(root).unwrap_or(entry.path());
+        if !is_excluded(rel) {
+            files.push(rel.to_path_buf());
+        }
+    }
+    Ok(files)
+}
+
+fn derive_key(passphrase: &str, salt: &[u8]) -> Key {
+    let mut key = [0u8; 32];
+    pbkdf2::pbkdf2_hmac::<Sha256>(passphrase.as_bytes(), salt, KDF_ROUNDS, &mut key);
+    Key::from(key)
+}
+
+fn encrypt_secrets(secrets: &BTreeMap<String, String>, passphrase: &str) -> Result<Vec<u8>, AppError> {
+    let mut salt = [0u8; SALT_LEN];
+    OsRng.fill_bytes(&mut salt);
+    let cipher = ChaCha20Poly1305::new(&derive_key(passphrase, &salt));
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let ciphertext = cipher
+        .encrypt(&nonce, serde_json::to_vec(secrets)?.as_slice())
+        .map_err(|_| AppError::Custom("Failed to encrypt secrets".to_string()))?;
+    Ok([salt.as_slice(), nonce.as_slice(), &ciphertext].concat())
+}
+
+fn decrypt_secrets(data: &[u8], passphrase: &str) -> Result<BTreeMap<String, String>, AppError> {
+    if data.len() < SALT_LEN + NONCE_LEN {
+        return Err(AppError::Custom("The secrets in this backup are damaged".to_string()));
+    }
+    let (salt, rest) = data.split_at(SALT_LEN);
+    let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+    let cipher = ChaCha20Poly1305::new(&derive_key(passphrase, salt));
+    let plaintext = cipher
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| AppError::Custom("Wrong passphrase for the secrets in this backup".to_string()))?;
+    Ok(serde_json::from_slice(&plaintext)?)
+}
+
+/// Keychain passwords of the servers that authenticate by password
+fn collect_secrets() -> Result<BTreeMap<String, String>, AppError> {
+    let mut secrets = BTreeMap::new();
+    for server in remote::load_remotes()? {
+        if matches!(server.auth, SshAuth::Password) {
+            if let Ok(password) = read_keychain_password(&server.id) {
+                secrets.insert(server.id, password);
+            }
+        }
+    }
+    Ok(secrets)
+}
+
+fn append_bytes<W: std::io::Write>(builder: &mut tar::Builder<W>, name: &str, data: &[u8]) -> Result<(), AppError> {
+    let mut header = tar::Header::new_gnu();
+    header.set_size(data.len() as u64);
+    header.set_mode(0o600);
+    header.set_mtime(chrono::Utc::now().timestamp().max(0) as u64);
+    header.set_cksum();
+    builder.append_data(&mut header, name, data)?;
+    Ok(())
+}
+
+/// Write the archive next to `dest` and rename it into place once complete
+fn write_archive(
+    dest: &Path,
+    root: &Path,
+    manifest: &BackupManifest,
+    secrets: Option<&[u8]>,
+) -> Result<(), AppError> {
+    let tmp = dest.with_extension("partial");
+    let write = || -> Result<(), AppError> {
+        let mut builder = tar::Builder::new(GzEncoder::new(File::create(&tmp)?, Compression::default()));
+        builder.follow_symlinks(false);
+        append_bytes(&mut builder, MANIFEST_NAME, &serde_json::to_vec_pretty(manifest)?)?;
+        for rel in &manifest.files {
+            builder.append_path_with_name(root.join(rel), Path::new(DATA_DIR_NAME).join(rel))?;
+        }
+        if let Some(secrets) = secrets {
+            append_bytes(&mut builder, SECRETS_NAME, secrets)?;
+        }
+        builder.into_inner()?.finish()?.sync_all()?;
+        Ok(())
+    };
+    if let Err(e) = write() {
+        let _ = fs::remove_file(&tmp);
+        return Err(e);
+    }
+    fs::rename(&tmp, dest)?;
+    Ok(())
+}
+
+/// Archive the data root to `dest`, with the given encrypted secrets
+fn export_to(dest: &Path, secrets: Option<(&BTreeMap<String, String>, &str)>) -> Result<BackupManifest, AppError> {
+    let root = data_dir()?;
+    let files = data_files(&root)?;
+    let manifest = BackupManifest {
+        format_version: FORMAT_VERSION,
+        created_at: chrono::Utc::now().to_rfc3339(),
+        home_dir: dirs::home_dir().map(|h| h.to_string_lossy().to_string()),
+        repo_roots: settings::load_settings()?.repo_roots,
+        files: files.iter().map(|f| f.to_string_lossy().to_string()).collect(),
+        secret_server_ids: secrets.map(|(s, _)| s.keys().cloned().collect()).unwrap_or_default(),
+    };
+    let encrypted = match secrets {
+        Some((secrets, passphrase)) => Some(encrypt_secrets(secrets, passphrase)?),
+        None => None,
+    };
+    write_archive(dest, &root, &manifest, encrypted.as_deref())?;
+    Ok(manifest)
+}
+
+/// Export the whole configuration to a backup archive. Keychain passwords are only
+/// included when asked for, and then encrypted with the passphrase.
+#[tauri::command]
+pub fn export_backup(
+    path: String,
+    include_secrets: bool,
+    passphrase: Option<String>,
+) -> Result<BackupExportResult, AppError> {
+    let dest = PathBuf::from(shellexpand::tilde(&path).to_string());
+    let manifest = if include_secrets {
+        let passphrase = passphrase
+            .filter(|p| !p.is_empty())
+            .ok_or_else(|| AppError::Custom("A passphrase is required to include secrets".to_string()))?;
+        let secrets = collect_secrets()?;
+        export_to(&dest, Some((&secrets, &passphrase)))?
+    } else {
+        export_to(&dest, None)?
+    };
+    Ok(BackupExportResult {
+        path: dest.to_string_lossy().to_string(),
+        manifest,
+    })
+}
+
+/// Rewrites paths with the longest matching mapping and records what changed
+struct Remapper {
+    mappings: Vec<PathMapping>,
+    applied: Vec<PathMapping>,
+}
+
+impl Remapper {
+    fn remap(&mut self, path: &str) -> String {
+        let p = Path::new(path);
+        let mut best: Option<&PathMapping> = None;
+        for mapping in &self.mappings {
+            let longer = best.is_none_or(|b| mapping.from.len() > b.from.len());
+            if p.starts_with(&mapping.from) && longer {
+                best = Some(mapping);
+            }
+        }
+        let Some(mapping) = best else {
+            return path.to_string();
+        };
+        let rest = p.strip_prefix(&mapping.from).unwrap_or(p);
+        let new = if rest.as_os_str().is_empty() {
+            mapping.to.clone()
+        } else {
+            Path::new(&mapping.to).join(rest).to_string_lossy().to_string()
+        };
+        if new != path && !self.applied.iter().any(|m| m.from == path) {
+            self.applied.push(PathMapping {
+                from: path.to_string(),
+                to: new.clone(),
+            });
+        }
+        new
+    }
+}
+
+/// The caller's mappings, then the archive's repo root to `repo_path`, then the old home
+/// directory to this one; the longest matching prefix wins
+fn build_mappings(manifest: &BackupManifest, mappings: Vec<PathMapping>, repo_path: Option<String>) -> Vec<PathMapping> {
+    let mut all: Vec<PathMapping> = mappings
+        .into_iter()
+        .map(|m| PathMapping {
+            from: m.from.trim().trim_end_matches('/').to_string(),
+            to: m.to.trim().trim_end_matches('/').to_string(),
+        })
+        .filter(|m| !m.from.is_empty() && !m.to.is_empty())
+        .collect();
+    let mut add = |from: String, to: String| {
+        if from != to && !all.iter().any(|m| m.from == from) {
+            all.push(PathMapping { from, to });
+        }
+    };
+    if let (Some(old), Some(new)) = (manifest.repo_roots.first(), repo_path.filter(|p| !p.trim().is_empty())) {
+        add(old.clone(), new.trim().to_string());
+    }
+    if let (Some(old), Some(new)) = (&manifest.home_dir, dirs::home_dir()) {
+        add(old.clone(), new.to_string_lossy().to_string());
+    }
+    all
+}
+
+/// Rewrite the machine paths of the unpacked data root: project paths, discovery roots,
+/// repo roots and private key paths
+fn remap_staged(data: &Path, remapper: &mut Remapper) -> Result<(), AppError> {
+    let projects_file = data.join("projects.json");
+    if projects_file.exists() {
+        store::update_json(&projects_file, &PROJECTS_SCHEMA, |projects: &mut Vec<ProjectConfig>| {
+            for project in projects.iter_mut() {
+                project.path = PathBuf::from(remapper.remap(&project.path.to_string_lossy()));
+            }
+            Ok(())
+        })?;
+    }
+    let remotes_file = data.join("remotes.json");
+    if remotes_file.exists() {
+        store::update_json(&remotes_file, &REMOTES_SCHEMA, |servers: &mut Vec<RemoteServer>| {
+            for server in servers.iter_mut() {
+                if let SshAuth::Key { private_key_path } = &mut server.auth {
+                    *private_key_path = remapper.remap(private_key_path);
+                }
+            }
+            Ok(())
+        })?;
+    }
+    let settings_file = data.join("config.json");
+    if settings_file.exists() {
+        store::update_json(&settings_file, &SETTINGS_SCHEMA, |settings: &mut AppSettings| {
+            for root in settings.repo_roots.iter_mut() {
+                *root = remapper.remap(root);
+            }
+            Ok(())
+        })?;
+    }
+    let discovery_file = data.join("discovery.json");
+    if discovery_file.exists() {
+        store::update_json(&discovery_file, &DISCOVERY_SCHEMA, |discovery: &mut DiscoveryConfig| {
+            for root in discovery.roots.iter_mut() {
+                *root = remapper.remap(root);
+            }
+            Ok(())
+        })?;
+    }
+    Ok(())
+}
+
+fn copy_entry(from: &Path, to: &Path) -> Result<(), AppError> {
+    if let Some(parent) = to.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    if fs::symlink_metadata(from)?.file_type().is_symlink() {
+        if fs::symlink_metadata(to).is_ok() {
+            fs::remove_file(to)?;
+        }
+        unix_fs::symlink(fs::read_link(from)?, to)?;
+    } else {
+        fs::copy(from, to)?;
+    }
+    Ok(())
+}
+
+/// The path a file is merged with: its directory for entries of MERGE_UNIT_DIRS
+fn merge_unit(rel: &Path) -> PathBuf {
+    let mut components = rel.components();
+    match components.next() {
+        Some(first) if MERGE_UNIT_DIRS.iter().any(|d| first.as_os_str() == *d) => match components.next() {
+            Some(second) => Path::new(first.as_os_str()).join(second),
+            None => rel.to_path_buf(),
+        },
+        _ => rel.to_path_buf(),
+    }
+}
+
+/// Add the archive's projects, servers and files that are missing locally
+fn merge_into(staged: &Path, root: &Path, result: &mut BackupImportResult) -> Result<(), AppError> {
+    let imported: Vec<ProjectConfig> =
+        store::read_json(&staged.join("projects.json"), &PROJECTS_SCHEMA)?.unwrap_or_default();
+    update_projects(|projects| {
+        for project in imported {
+            if projects.iter().any(|p| p.id == project.id || p.path == project.path) {
+                result.skipped_project_ids.push(project.id);
+            } else {
+                result.imported_project_ids.push(project.id.clone());
+                projects.push(project);
+            }
+        }
+        Ok(())
+    })?;
+
+    let imported: Vec<RemoteServer> =
+        store::read_json(&staged.join("remotes.json"), &REMOTES_SCHEMA)?.unwrap_or_default();
+    update_remotes(|servers| {
+        for server in imported {
+            if servers.iter().any(|s| s.id == server.id) {
+                result.skipped_server_ids.push(server.id);
+            } else {
+                result.imported_server_ids.push(server.id.clone());
+                servers.push(server);
+            }
+        }
+        Ok(())
+    })?;
+
+    let files = data_files(staged)?;
+    // Decided before copying, so the entries of one unit are all copied or all skipped
+    let local_units: HashSet<PathBuf> = files
+        .iter()
+        .map(|rel| merge_unit(rel))
+        .filter(|unit| fs::symlink_metadata(root.join(unit)).is_ok())
+        .collect();
+    for rel in files {
+        if rel == Path::new("projects.json") || rel == Path::new("remotes.json") {
+            continue;
+        }
+        let name = rel.to_string_lossy().to_string();
+        if local_units.contains(&merge_unit(&rel)) {
+            result.skipped_files.push(name);
+            continue;
+        }
+        copy_entry(&staged.join(&rel), &root.join(&rel))?;
+        result.imported_files.push(name);
+    }
+    Ok(())
+}
+
+/// Archive the current configuration to backups/, then swap in the archive's data root
+fn replace_with(staged: &Path, root: &Path, result: &mut BackupImportResult) -> Result<(), AppError> {
+    let stamp = chrono::Utc::now().format("%Y%m%dT%H%M%SZ");
+    let previous = data_subdir(BACKUPS_DIR_NAME)?.join(format!("pre-import-{}.tar.gz", stamp));
+    export_to(&previous, None)?;
+    result.previous_backup = Some(previous.to_string_lossy().to_string());
+
+    for entry in fs::read_dir(root)? {
+        let path = entry?.path();
+        let name = path.file_name().unwrap_or_default();
+        if name == ".lock" || name == BACKUPS_DIR_NAME || name == GIT_DIR_NAME {
+            continue;
+        }
+        if fs::symlink_metadata(&path)?.is_dir() {
+            fs::remove_dir_all(&path)?;
+        } else {
+            fs::remove_file(&path)?;
+        }
+    }
+
+    for rel in data_files(staged)? {
+        copy_entry(&staged.join(&rel), &root.join(&rel))?;
+        result.imported_files.push(rel.to_string_lossy().to_string());
+    }
+    result.imported_project_ids = projects::load_projects()?.into_iter().map(|p| p.id).collect();
+    result.imported_server_ids = remote::load_remotes()?.into_iter().map(|s| s.id).collect();
+    Ok(())
+}
+
+fn import_staged(
+    archive_path: &Path,
+    staging: &Path,
+    mode: ImportMode,
+    passphrase: Option<String>,
+    mappings: Vec<PathMapping>,
+    repo_path: Option<String>,
+) -> Result<BackupImportResult, AppError> {
+    let not_a_backup = || AppError::Custom(format!("Not a SkillPilot backup: {}", archive_path.display()));
+    tar::Archive::new(GzDecoder::new(File::open(archive_path)?))
+        .unpack(staging)
+        .map_err(|_| not_a_backup())?;
+    let manifest_file = staging.join(MANIFEST_NAME);
+    if !manifest_file.exists() {
+        return Err(not_a_backup());
+    }
+    let manifest: BackupManifest = serde_json::from_str(&fs::read_to_string(&manifest_file)?)?;
+    if manifest.format_version > FORMAT_VERSION {
+        return Err(AppError::Custom(format!(
+            "{} was written by a newer version of SkillPilot (backup format {}, this version supports up to {}); update SkillPilot to import it",
+            archive_path.display(),
+            manifest.format_version,
+            FORMAT_VERSION
+        )));
+    }
+
+    let mut result = BackupImportResult::default();
+    // Decrypt before touching anything, so a wrong passphrase leaves the configuration alone
+    let secrets_file = staging.join(SECRETS_NAME);
+    let secrets = match (secrets_file.exists(), passphrase.filter(|p| !p.is_empty())) {
+        (true, Some(passphrase)) => decrypt_secrets(&fs::read(&secrets_file)?, &passphrase)?,
+        (true, None) => {
+            result.secrets_skipped = true;
+            BTreeMap::new()
+        }
+        (false, _) => BTreeMap::new(),
+    };
+
+    let staged = staging.join(DATA_DIR_NAME);
+    fs::create_dir_all(&staged)?;
+    let mut remapper = Remapper {
+        mappings: build_mappings(&manifest, mappings, repo_path),
+        applied: Vec::new(),
+    };
+    remap_staged(&staged, &mut remapper)?;
+    result.remapped_paths = remapper.applied;
+
+    let root = data_dir()?;
+    match mode {
+        ImportMode::Merge => merge_into(&staged, &root, &mut result)?,
+        ImportMode::Replace => replace_with(&staged, &root, &mut result)?,
+    }
+
+    let imported: HashSet<&String> = result.imported_server_ids.iter().collect();
+    for (server_id, password) in &secrets {
+        if !imported.contains(server_id) {
+            continue;
+        }
+        match save_keychain_password(server_id, password) {
+            Ok(()) => result.restored_secret_ids.push(server_id.clone()),
+            Err(e) => eprintln!("Restoring password of {}: {}", server_id, e),
+        }
+    }
+    Ok(result)
+}
+
+/// Import a backup archive. Merge adds the projects, servers and files missing locally;
+/// Replace archives the current configuration to <data root>/backups and swaps in the
+/// backup. Paths are rewritten by `path_mappings`, the archive's first repo root becomes
+/// `repo_path` and the old home directory becomes this one.
+#[tauri::command]
+pub fn import_backup(
+    path: String,
+    mode: ImportMode,
+    passphrase: Option<String>,
+    path_mappings: Option<Vec<PathMapping>>,
+    repo_path: Option<String>,
+) -> Result<BackupImportResult, AppError> {
+    let archive_path = PathBuf::from(shellexpand::tilde(&path).to_string());
+    let staging = std::env::temp_dir().join(format!("skillpilot-import-{}", uuid::Uuid::new_v4()));
+    fs::create_dir_all(&staging)?;
+    let result = import_staged(
+        &archive_path,
+        &staging,
+        mode,
+        passphrase,
+        path_mappings.unwrap_or_default(),
+        repo_path,
+    );
+    let _ = fs::remove_dir_all(&staging);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mapping(from: &str, to: &str) -> PathMapping {
+        PathMapping {
+            from: from.to_string(),
+            to: to.to_string(),
+        }
+    }
+
+    #[test]
+    fn remap_uses_longest_prefix_on_component_boundaries() {
+        let mut remapper = Remapper {
+            mappings: vec![mapping("/home/a", "/Users/b"), mapping("/home/a/skills", "/opt/skills")],
+            applied: Vec::new(),
+        };
+
+        assert_eq!(remapper.remap("/home/a/skills/x"), "/opt/skills/x");
+        assert_eq!(remapper.remap("/home/a/work/p"), "/Users/b/work/p");
+        assert_eq!(remapper.remap("/home/a"), "/Users/b");
+        assert_eq!(remapper.remap("/home/ab/x"), "/home/ab/x");
+        assert_eq!(remapper.remap("/home/a/work/p"), "/Users/b/work/p");
+
+        let applied: Vec<(&str, &str)> = remapper.applied.iter().map(|m| (m.from.as_str(), m.to.as_str())).collect();
+        assert_eq!(
+            applied,
+            vec![
+                ("/home/a/skills/x", "/opt/skills/x"),
+                ("/home/a/work/p", "/Users/b/work/p"),
+                ("/home/a", "/Users/b"),
+            ]
+        );
+    }
+}
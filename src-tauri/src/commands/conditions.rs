@@ -0,0 +1,294 @@
+use crate::commands::query::glob_to_regex;
+use crate::commands::resolver::{resolve_profile_links, resolve_project_links, DesiredLink, LinkConflict};
+use crate::commands::settings::load_settings;
+use crate::commands::{profiles, projects, scanner};
+use crate::error::AppError;
+use crate::models::{EntryCondition, Profile, ProjectConfig, Skill, TargetScope};
+use serde::Serialize;
+use std::cell::OnceCell;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// Depth of the project listing used for glob conditions (from the settings)
+pub(crate) fn max_glob_depth() -> usize {
+    load_settings().unwrap_or_default().thresholds.max_glob_depth
+}
+
+/// Directories not descended into when matching glob conditions
+pub(crate) const SKIPPED_DIRS: &[&str] = &[".git", "node_modules", "target"];
+
+/// A profile entry left out for a target, and why
+#[derive(Debug, Clone, Serialize)]
+pub struct SkippedEntry {
+    /// Skill ID as written in the profile
+    pub skill_id: String,
+    /// Profile that declares the entry
+    pub profile_id: String,
+    /// One line per condition that does not hold
+    pub reasons: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TargetResolution {
+    pub profile_id: String,
+    /// Project path, or None for the user-level skills directory
+    pub target_path: Option<String>,
+    /// (link name, source path) pairs to link
+    pub entries: Vec<(String, String)>,
+    pub skipped: Vec<SkippedEntry>,
+    /// Skill IDs that matched nothing in the catalog
+    pub unresolved_skill_ids: Vec<String>,
+    /// Link names claimed by several skills of the profile
+    pub conflicts: Vec<LinkConflict>,
+}
+
+/// Machine a target lives on
+pub(crate) trait TargetHost {
+    /// OS name as in `std::env::consts::OS`
+    fn os(&self) -> Result<String, AppError>;
+    /// Remote server ID, or None for this machine
+    fn server_id(&self) -> Option<&str>;
+    fn file_exists(&self, path: &str) -> Result<bool, AppError>;
+    /// Paths of the files and directories under `root`, relative to it
+    fn list_files(&self, root: &str) -> Result<Vec<String>, AppError>;
+}
+
+/// This machine
+pub(crate) struct LocalHost;
+
+impl TargetHost for LocalHost {
+    fn os(&self) -> Result<String, AppError> {
+        Ok(std::env::consts::OS.to_string())
+    }
+
+    fn server_id(&self) -> Option<&str> {
+        None
+    }
+
+    fn file_exists(&self, path: &str) -> Result<bool, AppError> {
+        Ok(Path::new(path).try_exists()?)
+    }
+
+    fn list_files(&self, root: &str) -> Result<Vec<String>, AppError> {
+        Ok(WalkDir::new(root)
+            .max_depth(max_glob_depth())
+            .into_iter()
+            .filter_entry(|e| !SKIPPED_DIRS.contains(&e.file_name().to_string_lossy().as_ref()))
+            .filter_map(|e| e.ok())
+            .filter_map(|e| {
+                e.path()
+                    .strip_prefix(root)
+                    .ok()
+                    .map(|p| p.to_string_lossy().to_string())
+            })
+            .filter(|p| !p.is_empty())
+            .collect())
+    }
+}
+
+/// The target a profile is applied to: user-level or a project, on some host
+pub(crate) struct TargetContext<'a> {
+    host: &'a dyn TargetHost,
+    project_path: Option<String>,
+    project_tags: Vec<String>,
+    os: OnceCell<String>,
+    files: OnceCell<Vec<String>>,
+}
+
+impl<'a> TargetContext<'a> {
+    pub(crate) fn project(host: &'a dyn TargetHost, project: &ProjectConfig) -> Self {
+        Self::new(
+            host,
+            Some(project.path.to_string_lossy().to_string()),
+            project.tags.clone(),
+        )
+    }
+
+    /// User-level target for None, otherwise the project at `path` (tags from a saved
+    /// project config with that path, if any)
+    pub(crate) fn for_path(host: &'a dyn TargetHost, path: Option<&str>, projects: &[ProjectConfig]) -> Self {
+        let tags = path
+            .and_then(|p| projects.iter().find(|c| c.path == Path::new(p)))
+            .map(|c| c.tags.clone())
+            .unwrap_or_default();
+        Self::new(host, path.map(|p| p.to_string()), tags)
+    }
+
+    fn new(host: &'a dyn TargetHost, project_path: Option<String>, project_tags: Vec<String>) -> Self {
+        TargetContext {
+            host,
+            project_path,
+            project_tags,
+            os: OnceCell::new(),
+            files: OnceCell::new(),
+        }
+    }
+
+    /// Project path, or None for the user-level skills directory
+    pub(crate) fn project_path(&self) -> Option<&str> {
+        self.project_path.as_deref()
+    }
+
+    fn host_os(&self) -> Result<&String, AppError> {
+        if let Some(os) = self.os.get() {
+            return Ok(os);
+        }
+        let os = self.host.os()?;
+        Ok(self.os.get_or_init(|| os))
+    }
+
+    fn project_files(&self, root: &str) -> Result<&Vec<String>, AppError> {
+        if let Some(files) = self.files.get() {
+            return Ok(files);
+        }
+        let files = self.host.list_files(root)?;
+        Ok(self.files.get_or_init(|| files))
+    }
+
+    fn scope(&self) -> TargetScope {
+        match self.project_path {
+            Some(_) => TargetScope::Project,
+            None => TargetScope::User,
+        }
+    }
+
+    /// Why a condition does not hold for this target, or None if it holds. Fails when the
+    /// host cannot be inspected, rather than treating that as the condition not holding.
+    fn failure(&self, condition: &EntryCondition) -> Result<Option<String>, AppError> {
+        Ok(match condition {
+            EntryCondition::FileExists { path } => {
+                let root = match &self.project_path {
+                    Some(root) => root,
+                    None => return Ok(Some(format!("needs {} in a project (target is user-level)", path))),
+                };
+                let full = PathBuf::from(root).join(path);
+                (!self.host.file_exists(&full.to_string_lossy())?).then(|| format!("{} not found in project", path))
+            }
+            EntryCondition::GlobMatch { pattern } => {
+                let root = match &self.project_path {
+                    Some(root) => root,
+                    None => {
+                        return Ok(Some(format!(
+                            "needs a file matching {} in a project (target is user-level)",
+                            pattern
+                        )))
+                    }
+                };
+                let regex = match glob_to_regex(pattern) {
+                    Some(r) => r,
+                    None => return Ok(Some(format!("invalid glob {}", pattern))),
+                };
+                let files = self.project_files(root)?;
+                (!files.iter().any(|f| regex.is_match(f))).then(|| format!("no project file matches {}", pattern))
+            }
+            EntryCondition::ProjectTag { tag } => match &self.project_path {
+                None => Some(format!("needs project tag {} (target is user-level)", tag)),
+                Some(_) if !self.project_tags.contains(tag) => Some(format!("project is not tagged {}", tag)),
+                Some(_) => None,
+            },
+            EntryCondition::Scope { scope } => (*scope != self.scope()).then(|| match scope {
+                TargetScope::User => "applies to the user-level skills directory only".to_string(),
+                TargetScope::Project => "applies to projects only".to_string(),
+            }),
+            EntryCondition::HostOs { os } => {
+                let host_os = self.host_os()?;
+                (!host_os.eq_ignore_ascii_case(os)).then(|| format!("host OS is {}, needs {}", host_os, os))
+            }
+            EntryCondition::Local => self
+                .host
+                .server_id()
+                .map(|id| format!("applies on this machine only (target is on server {})", id)),
+            EntryCondition::Remote { server_ids } => match self.host.server_id() {
+                None => Some("applies on remote servers only".to_string()),
+                Some(id) if !server_ids.is_empty() && !server_ids.iter().any(|s| s == id) => {
+                    Some(format!("applies on servers {} only", server_ids.join(", ")))
+                }
+                Some(_) => None,
+            },
+            EntryCondition::AnyOf { conditions } => {
+                let mut failures = Vec::new();
+                for condition in conditions {
+                    failures.extend(self.failure(condition)?);
+                }
+                (!conditions.is_empty() && failures.len() == conditions.len())
+                    .then(|| format!("none of: {}", failures.join("; ")))
+            }
+        })
+    }
+
+    /// Reasons an entry of `profile` is skipped for this target (empty when it applies).
+    /// Conditions may be keyed by the ID as written in the profile or by the catalog ID.
+    pub(crate) fn skip_reasons(
+        &self,
+        profile: &Profile,
+        sid: &str,
+        catalog_id: Option<&str>,
+    ) -> Result<Vec<String>, AppError> {
+        let conditions = profile
+            .conditions
+            .get(sid)
+            .or_else(|| catalog_id.and_then(|id| profile.conditions.get(id)));
+        let mut reasons = Vec::new();
+        for condition in conditions.into_iter().flatten() {
+            reasons.extend(self.failure(condition)?);
+        }
+        Ok(reasons)
+    }
+}
+
+/// Resolve a profile for one target: the links to create and the entries skipped
+pub(crate) fn resolve_for_target(
+    profile: &Profile,
+    profiles: &[Profile],
+    skills: &[Skill],
+    ctx: &TargetContext,
+) -> Result<TargetResolution, AppError> {
+    let links = resolve_profile_links(profile, profiles, skills, ctx)?;
+    Ok(TargetResolution {
+        profile_id: profile.id.clone(),
+        target_path: ctx.project_path.clone(),
+        entries: link_entries(links.desired),
+        skipped: links.skipped,
+        unresolved_skill_ids: links.unresolved,
+        conflicts: links.conflicts,
+    })
+}
+
+/// (link name, source path) pairs for resolved links, as passed to the linker
+pub(crate) fn link_entries(desired: Vec<DesiredLink>) -> Vec<(String, String)> {
+    desired
+        .into_iter()
+        .map(|d| (d.name, d.source_path.to_string_lossy().to_string()))
+        .collect()
+}
+
+/// Resolve a profile for the user-level skills directory (None) or a project, evaluating
+/// entry conditions and explaining the entries that were skipped
+#[tauri::command]
+pub fn resolve_profile_for_target(
+    profile_id: String,
+    target_path: Option<String>,
+    repo_path: Option<String>,
+) -> Result<TargetResolution, AppError> {
+    let all = profiles::load_profiles()?;
+    let profile = all
+        .iter()
+        .find(|p| p.id == profile_id)
+        .ok_or_else(|| AppError::Custom(format!("Profile not found: {}", profile_id)))?;
+    let skills = scanner::scan_skills_repo(repo_path)?;
+    let ctx = TargetContext::for_path(&LocalHost, target_path.as_deref(), &projects::load_projects()?);
+    resolve_for_target(profile, &all, &skills, &ctx)
+}
+
+/// Links a project should have, with aliases, entry conditions and its link winners applied.
+/// Saving a project or profile syncs the project's links from this.
+#[tauri::command]
+pub fn resolve_project_link_entries(
+    project: ProjectConfig,
+    repo_path: Option<String>,
+) -> Result<Vec<(String, String)>, AppError> {
+    let all = profiles::load_profiles()?;
+    let skills = scanner::scan_skills_repo(repo_path)?;
+    let links = resolve_project_links(&project, &all, &skills, &TargetContext::project(&LocalHost, &project))?;
+    Ok(link_entries(links.desired))
+}
@@ -0,0 +1,307 @@
+use crate::commands::presets::preset_locale;
+use crate::commands::resolver::find_skill;
+use crate::commands::{profiles, scanner};
+use crate::commands::settings::{data_dir, default_repo_path};
+use crate::error::AppError;
+use crate::models::{DetectionRule, ProjectMarker, Skill};
+use crate::store::{self, Schema};
+use regex::Regex;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// Detection rules shipped with the app
+const BUNDLED_RULES: &str = include_str!("../../detection/default.json");
+
+/// File extensions that count as documentation for `ProjectMarker::DocsOnly`
+const DOC_EXTENSIONS: &[&str] = &[
+    "md", "markdown", "mdx", "txt", "rst", "adoc", "pdf", "doc", "docx", "png", "jpg", "jpeg",
+    "gif", "svg",
+];
+
+/// Directories ignored when checking for docs-only projects
+const IGNORED_DIRS: &[&str] = &["node_modules", "target", "dist", "build"];
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ProfileSuggestion {
+    pub profile_id: String,
+    pub profile_name: String,
+    pub reasons: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SkillSuggestion {
+    pub skill_id: String,
+    pub name: String,
+    pub reasons: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ProjectDetection {
+    /// IDs of the rules that matched
+    pub matched_rules: Vec<String>,
+    pub profiles: Vec<ProfileSuggestion>,
+    /// Skills not already covered by a suggested profile
+    pub skills: Vec<SkillSuggestion>,
+}
+
+/// Schema of detection-rules.json
+const RULES_SCHEMA: Schema = Schema {
+    version: 1,
+    migrations: &[store::unversioned],
+};
+
+fn rules_path() -> Result<PathBuf, AppError> {
+    let dir = data_dir()?;
+    Ok(dir.join("detection-rules.json"))
+}
+
+fn bundled_rules() -> Vec<DetectionRule> {
+    serde_json::from_str(BUNDLED_RULES).expect("bundled detection rules are valid JSON")
+}
+
+fn load_user_rules() -> Result<Vec<DetectionRule>, AppError> {
+    Ok(store::read_json(&rules_path()?, &RULES_SCHEMA)?.unwrap_or_default())
+}
+
+/// True when the project has at least one doc file and nothing else
+fn is_docs_only(root: &Path) -> bool {
+    let mut found_doc = false;
+    let walker = WalkDir::new(root).max_depth(4).into_iter().filter_entry(|e| {
+        let name = e.file_name().to_string_lossy();
+        e.depth() == 0 || !(name.starts_with('.') || IGNORED_DIRS.iter().any(|d| name == *d))
+    });
+    for entry in walker.filter_map(|e| e.ok()) {
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let ext = match entry.path().extension() {
+            Some(e) => e.to_string_lossy().to_lowercase(),
+            None => continue,
+        };
+        if DOC_EXTENSIONS.contains(&ext.as_str()) {
+            found_doc = true;
+        } else {
+            return false;
+        }
+    }
+    found_doc
+}
+
+/// A marker ready to be checked, with its pattern compiled once per rule load
+enum Matcher<'a> {
+    FileExists(&'a str),
+    /// None when the pattern is not a valid regex; such a marker never matches
+    FileContains(&'a str, Option<Regex>),
+    DocsOnly,
+}
+
+impl<'a> Matcher<'a> {
+    fn new(marker: &'a ProjectMarker) -> Self {
+        match marker {
+            ProjectMarker::FileExists { path } => Matcher::FileExists(path),
+            ProjectMarker::FileContains { path, pattern } => Matcher::FileContains(path, Regex::new(pattern).ok()),
+            ProjectMarker::DocsOnly => Matcher::DocsOnly,
+        }
+    }
+
+    /// Check the marker against a project directory; returns the evidence when it matches
+    fn evidence(&self, root: &Path) -> Option<String> {
+        match self {
+            Matcher::FileExists(path) => root.join(path).exists().then(|| path.to_string()),
+            Matcher::FileContains(path, re) => {
+                let content = fs::read_to_string(root.join(path)).ok()?;
+                re.as_ref()?.is_match(&content).then(|| path.to_string())
+            }
+            Matcher::DocsOnly => is_docs_only(root).then(|| "documentation only".to_string()),
+        }
+    }
+}
+
+fn add_reason(reasons: &mut Vec<String>, reason: String) {
+    if !reasons.contains(&reason) {
+        reasons.push(reason);
+    }
+}
+
+/// List detection rules (defaults + user rules, user rules take priority)
+#[tauri::command]
+pub fn list_detection_rules() -> Result<Vec<DetectionRule>, AppError> {
+    let mut user_rules = load_user_rules()?;
+
+    let mut rules: Vec<DetectionRule> = Vec::new();
+    for default in bundled_rules() {
+        match user_rules.iter().position(|r| r.id == default.id) {
+            Some(idx) => rules.push(user_rules.remove(idx)),
+            None => rules.push(default),
+        }
+    }
+    rules.extend(user_rules);
+
+    Ok(rules)
+}
+
+/// Replace the user detection rules
+#[tauri::command]
+pub fn save_detection_rules(rules: Vec<DetectionRule>) -> Result<Vec<DetectionRule>, AppError> {
+    store::write_json(&rules_path()?, &RULES_SCHEMA, &rules)?;
+    Ok(rules)
+}
+
+/// Inspect a project directory and suggest profiles and skills.
+/// Without a repo path, skill suggestions are limited to the rules' literal skill IDs.
+#[tauri::command]
+pub fn detect_project_type(
+    project_path: String,
+    repo_path: Option<String>,
+) -> Result<ProjectDetection, AppError> {
+    let root = PathBuf::from(&project_path);
+    if !root.is_dir() {
+        return Err(AppError::Custom(format!(
+            "Project path does not exist: {}",
+            project_path
+        )));
+    }
+
+    let all_profiles = profiles::load_profiles()?;
+    let skills: Vec<Skill> = match repo_path.or_else(default_repo_path) {
+        Some(p) => scanner::scan_skills_repo(Some(p))?,
+        None => Vec::new(),
+    };
+
+    let mut matched_rules = Vec::new();
+    let mut profile_suggestions: Vec<ProfileSuggestion> = Vec::new();
+    let mut skill_suggestions: Vec<SkillSuggestion> = Vec::new();
+
+    let locale = preset_locale()?;
+    let rules = list_detection_rules()?;
+    let compiled: Vec<(&DetectionRule, Vec<Matcher>)> = rules
+        .iter()
+        .map(|rule| (rule, rule.markers.iter().map(Matcher::new).collect()))
+        .collect();
+    for (rule, matchers) in compiled {
+        let evidence = match matchers.iter().find_map(|m| m.evidence(&root)) {
+            Some(e) => e,
+            None => continue,
+        };
+        matched_rules.push(rule.id.clone());
+        let reason = format!("{} ({})", rule.reason.get(&locale), evidence);
+
+        for pid in &rule.profile_ids {
+            let profile = match all_profiles.iter().find(|p| &p.id == pid) {
+                Some(p) => p,
+                None => continue,
+            };
+            match profile_suggestions.iter_mut().find(|s| &s.profile_id == pid) {
+                Some(s) => add_reason(&mut s.reasons, reason.clone()),
+                None => profile_suggestions.push(ProfileSuggestion {
+                    profile_id: profile.id.clone(),
+                    profile_name: profile.name.clone(),
+                    reasons: vec![reason.clone()],
+                }),
+            }
+        }
+
+        // Literal skill IDs: resolve through the catalog when available
+        let mut candidates: Vec<(String, String)> = Vec::new();
+        for sid in &rule.skill_ids {
+            if skills.is_empty() {
+                candidates.push((sid.clone(), sid.clone()));
+            } else if let Some(skill) = find_skill(&skills, sid) {
+                candidates.push((skill.id.clone(), skill.name.clone()));
+            }
+        }
+        for skill in &skills {
+            let tagged = skill
+                .tags
+                .iter()
+                .any(|t| rule.skill_tags.iter().any(|rt| rt.eq_ignore_ascii_case(t)));
+            if tagged {
+                candidates.push((skill.id.clone(), skill.name.clone()));
+            }
+        }
+
+        for (skill_id, name) in candidates {
+            match skill_suggestions.iter_mut().find(|s| s.skill_id == skill_id) {
+                Some(s) => add_reason(&mut s.reasons, reason.clone()),
+                None => skill_suggestions.push(SkillSuggestion {
+                    skill_id,
+                    name,
+                    reasons: vec![reason.clone()],
+                }),
+            }
+        }
+    }
+
+    // Drop skills that a suggested profile already brings in
+    let covered: HashSet<String> = profile_suggestions
+        .iter()
+        .filter_map(|s| all_profiles.iter().find(|p| p.id == s.profile_id))
+        .flat_map(|p| p.effective_skill_ids())
+        .collect();
+    skill_suggestions.retain(|s| !covered.contains(&s.skill_id) && !covered.contains(&s.name));
+
+    Ok(ProjectDetection {
+        matched_rules,
+        profiles: profile_suggestions,
+        skills: skill_suggestions,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn project(files: &[(&str, &str)]) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("skillpilot-detect-{}", uuid::Uuid::new_v4()));
+        for (name, content) in files {
+            let path = root.join(name);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+        root
+    }
+
+    fn contains(pattern: &str) -> ProjectMarker {
+        ProjectMarker::FileContains {
+            path: "package.json".to_string(),
+            pattern: pattern.to_string(),
+        }
+    }
+
+    #[test]
+    fn markers_report_their_evidence() {
+        let root = project(&[("package.json", r#"{"dependencies": {"react": "^18"}}"#)]);
+        let exists = ProjectMarker::FileExists {
+            path: "package.json".to_string(),
+        };
+        assert_eq!(Matcher::new(&exists).evidence(&root).as_deref(), Some("package.json"));
+        assert_eq!(Matcher::new(&contains(r#""react"\s*:"#)).evidence(&root).as_deref(), Some("package.json"));
+        assert_eq!(Matcher::new(&contains(r#""next"\s*:"#)).evidence(&root), None);
+        assert_eq!(Matcher::new(&contains("(unclosed")).evidence(&root), None);
+        assert_eq!(Matcher::new(&ProjectMarker::DocsOnly).evidence(&root), None);
+    }
+
+    #[test]
+    fn docs_only_ignores_hidden_and_build_directories() {
+        let root = project(&[("README.md", "# Docs"), ("guide/intro.md", ""), (".git/config", ""), ("node_modules/x.js", "")]);
+        assert!(is_docs_only(&root));
+        fs::write(root.join("main.rs"), "fn main() {}").unwrap();
+        assert!(!is_docs_only(&root));
+        assert!(!is_docs_only(&project(&[])));
+    }
+
+    #[test]
+    fn bundled_rules_have_localized_reasons() {
+        let rules = bundled_rules();
+        let maven = rules.iter().find(|r| r.id == "java-maven").unwrap();
+        assert_eq!(maven.reason.get("en"), "Maven project");
+        assert_eq!(maven.reason.get("zh-CN"), "Maven 项目");
+        assert!(rules.iter().all(|r| r.markers.iter().all(|m| match Matcher::new(m) {
+            Matcher::FileContains(_, re) => re.is_some(),
+            _ => true,
+        })));
+    }
+}
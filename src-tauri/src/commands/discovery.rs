@@ -0,0 +1,345 @@
+use crate::commands::homes::{active_home_dir, claude_json_path};
+use crate::commands::linker::{project_skills_dir, read_link_entries};
+use crate::commands::resolver::find_skill;
+use crate::commands::{profiles, projects, scanner};
+use crate::commands::settings::{data_dir, default_repo_path};
+use crate::error::AppError;
+use crate::models::{Profile, ProjectConfig, Skill};
+use crate::store::{self, Schema};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// Directories never descended into while looking for projects
+const SKIPPED_DIRS: &[&str] = &["node_modules", "target", "dist", "build", "vendor", "Library"];
+
+/// Parent directories to search (persisted to <data root>/discovery.json)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiscoveryConfig {
+    /// Parent directories scanned for projects containing .claude/
+    pub roots: Vec<String>,
+    /// How deep below each root to look
+    pub max_depth: usize,
+}
+
+impl Default for DiscoveryConfig {
+    fn default() -> Self {
+        Self {
+            roots: Vec::new(),
+            max_depth: 3,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum DiscoverySource {
+    /// Found under a configured parent directory
+    Disk,
+    /// Folder name in ~/.claude/projects/
+    ClaudeProjects,
+    /// Key of the "projects" map in ~/.claude.json
+    ClaudeConfig,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DiscoveredProject {
+    pub path: String,
+    pub sources: Vec<DiscoverySource>,
+    pub has_claude_dir: bool,
+    /// A registered project already uses this path
+    pub already_registered: bool,
+    /// Entries currently in <project>/.claude/skills
+    pub linked_skills: Vec<String>,
+    /// Proposed config, pre-filled from the existing links
+    pub project: ProjectConfig,
+}
+
+/// Schema of discovery.json
+pub(crate) const DISCOVERY_SCHEMA: Schema = Schema {
+    version: 1,
+    migrations: &[store::unversioned],
+};
+
+fn discovery_path() -> Result<PathBuf, AppError> {
+    let dir = data_dir()?;
+    Ok(dir.join("discovery.json"))
+}
+
+/// Encode a path component the way Claude Code names ~/.claude/projects/ folders
+fn encode_component(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect()
+}
+
+/// Resolve the encoded remainder of a Claude project folder name against the file system
+fn decode_from(dir: &Path, rest: &str, depth: usize) -> Option<PathBuf> {
+    if depth > 32 {
+        return None;
+    }
+    for entry in fs::read_dir(dir).ok()?.filter_map(|e| e.ok()) {
+        if !entry.file_type().map(|t| t.is_dir()).unwrap_or(false) {
+            continue;
+        }
+        let encoded = encode_component(&entry.file_name().to_string_lossy());
+        if rest == encoded {
+            return Some(entry.path());
+        }
+        if let Some(tail) = rest.strip_prefix(&encoded).and_then(|t| t.strip_prefix('-')) {
+            if let Some(found) = decode_from(&entry.path(), tail, depth + 1) {
+                return Some(found);
+            }
+        }
+    }
+    None
+}
+
+/// Decode a ~/.claude/projects/ folder name (e.g. "-Users-me-code-app") into an existing path
+fn decode_claude_project_dir(name: &str) -> Option<PathBuf> {
+    let rest = name.strip_prefix('-')?;
+    decode_from(Path::new("/"), rest, 0)
+}
+
+/// Projects Claude Code has been run in, from <claude home>/projects/
+fn claude_history_projects(claude_home: &Path) -> Vec<PathBuf> {
+    let dir = claude_home.join("projects");
+    let entries = match fs::read_dir(&dir) {
+        Ok(e) => e,
+        Err(_) => return Vec::new(),
+    };
+    entries
+        .filter_map(|e| e.ok())
+        .filter_map(|e| decode_claude_project_dir(&e.file_name().to_string_lossy()))
+        .collect()
+}
+
+/// Project paths listed in the home's .claude.json
+fn claude_config_projects(claude_home: &Path) -> Vec<PathBuf> {
+    let content = match claude_json_path(claude_home).and_then(|p| Ok(fs::read_to_string(p)?)) {
+        Ok(c) => c,
+        Err(_) => return Vec::new(),
+    };
+    let value: serde_json::Value = match serde_json::from_str(&content) {
+        Ok(v) => v,
+        Err(_) => return Vec::new(),
+    };
+    value
+        .get("projects")
+        .and_then(|p| p.as_object())
+        .map(|map| map.keys().map(PathBuf::from).collect())
+        .unwrap_or_default()
+}
+
+/// Directories containing .claude/ below the configured roots
+fn disk_projects(config: &DiscoveryConfig) -> Vec<PathBuf> {
+    let mut found = Vec::new();
+    for root in &config.roots {
+        let root = PathBuf::from(shellexpand::tilde(root).to_string());
+        let walker = WalkDir::new(&root)
+            .max_depth(config.max_depth)
+            .into_iter()
+            .filter_entry(|e| {
+                let name = e.file_name().to_string_lossy();
+                e.depth() == 0
+                    || (e.file_type().is_dir()
+                        && !name.starts_with('.')
+                        && !SKIPPED_DIRS.iter().any(|d| name == *d))
+            });
+        for entry in walker.filter_map(|e| e.ok()) {
+            if entry.path().join(".claude").is_dir() {
+                found.push(entry.into_path());
+            }
+        }
+    }
+    found
+}
+
+/// Link name a profile skill ID produces (profile alias, catalog name, or last ID segment without a catalog)
+fn link_name_for(profile: &Profile, sid: &str, skills: &[Skill]) -> String {
+    if let Some(alias) = profile.aliases.get(sid) {
+        return alias.clone();
+    }
+    match find_skill(skills, sid) {
+        Some(skill) => skill.name.clone(),
+        None => sid.rsplit(['/', ':']).next().unwrap_or(sid).to_string(),
+    }
+}
+
+/// Pick profiles whose every skill is linked; leftovers become extra skills
+fn prefill_from_links(linked: &[String], profiles: &[Profile], skills: &[Skill]) -> (Vec<String>, Vec<String>) {
+    let linked_set: HashSet<&String> = linked.iter().collect();
+    let mut covered: HashSet<String> = HashSet::new();
+    let mut profile_ids = Vec::new();
+
+    for profile in profiles {
+        let skill_ids = profile.effective_skill_ids();
+        if skill_ids.is_empty() {
+            continue;
+        }
+        let names: Vec<String> = skill_ids.iter().map(|sid| link_name_for(profile, sid, skills)).collect();
+        if names.iter().all(|n| linked_set.contains(n)) {
+            profile_ids.push(profile.id.clone());
+            covered.extend(names);
+        }
+    }
+
+    let extra_skill_ids = linked
+        .iter()
+        .filter(|name| !covered.contains(*name))
+        .map(|name| match skills.iter().find(|s| &s.name == name) {
+            Some(skill) => skill.id.clone(),
+            None => name.clone(),
+        })
+        .collect();
+
+    (profile_ids, extra_skill_ids)
+}
+
+/// Get the discovery configuration
+#[tauri::command]
+pub fn get_discovery_config() -> Result<DiscoveryConfig, AppError> {
+    Ok(store::read_json(&discovery_path()?, &DISCOVERY_SCHEMA)?.unwrap_or_default())
+}
+
+/// Save the discovery configuration
+#[tauri::command]
+pub fn save_discovery_config(config: DiscoveryConfig) -> Result<DiscoveryConfig, AppError> {
+    store::write_json(&discovery_path()?, &DISCOVERY_SCHEMA, &config)?;
+    Ok(config)
+}
+
+/// Propose projects from the configured roots and Claude Code's own project list
+#[tauri::command]
+pub fn discover_projects(repo_path: Option<String>) -> Result<Vec<DiscoveredProject>, AppError> {
+    let claude_home = active_home_dir()?;
+    // The user's home directory itself is never proposed as a project
+    let home = dirs::home_dir().ok_or(AppError::ConfigDirNotFound)?;
+    let config = get_discovery_config()?;
+    let registered: HashSet<PathBuf> = projects::load_projects()?
+        .into_iter()
+        .map(|p| p.path.canonicalize().unwrap_or(p.path))
+        .collect();
+    let all_profiles = profiles::load_profiles()?;
+    let skills = match repo_path.or_else(default_repo_path) {
+        Some(p) => scanner::scan_skills_repo(Some(p))?,
+        None => Vec::new(),
+    };
+
+    let candidates = disk_projects(&config)
+        .into_iter()
+        .map(|p| (p, DiscoverySource::Disk))
+        .chain(claude_history_projects(&claude_home).into_iter().map(|p| (p, DiscoverySource::ClaudeProjects)))
+        .chain(claude_config_projects(&claude_home).into_iter().map(|p| (p, DiscoverySource::ClaudeConfig)));
+
+    let mut found: Vec<(PathBuf, Vec<DiscoverySource>)> = Vec::new();
+    for (path, source) in candidates {
+        let path = match path.canonicalize() {
+            Ok(p) if p.is_dir() && p != home => p,
+            _ => continue,
+        };
+        match found.iter_mut().find(|(p, _)| *p == path) {
+            Some((_, sources)) => {
+                if !sources.contains(&source) {
+                    sources.push(source);
+                }
+            }
+            None => found.push((path, vec![source])),
+        }
+    }
+
+    let mut discovered = Vec::new();
+    for (path, sources) in found {
+        let path_str = path.to_string_lossy().to_string();
+        let mut linked_skills: Vec<String> = read_link_entries(&project_skills_dir(&path_str))?
+            .into_iter()
+            .map(|e| e.name)
+            .collect();
+        linked_skills.sort();
+        let (profile_ids, extra_skill_ids) = prefill_from_links(&linked_skills, &all_profiles, &skills);
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| path_str.clone());
+
+        discovered.push(DiscoveredProject {
+            has_claude_dir: path.join(".claude").is_dir(),
+            already_registered: registered.contains(&path),
+            sources,
+            linked_skills,
+            project: ProjectConfig {
+                id: uuid::Uuid::new_v4().to_string(),
+                name,
+                path: path.clone(),
+                profile_ids,
+                extra_skill_ids,
+                aliases: HashMap::new(),
+                tags: Vec::new(),
+                link_winners: HashMap::new(),
+            },
+            path: path_str,
+        });
+    }
+
+    discovered.sort_by_key(|d| d.path.to_lowercase());
+    Ok(discovered)
+}
+
+/// Register discovered projects, skipping paths that are already registered
+#[tauri::command]
+pub fn import_discovered_projects(projects: Vec<ProjectConfig>) -> Result<Vec<ProjectConfig>, AppError> {
+    let existing = projects::load_projects()?;
+    let mut known: HashSet<PathBuf> = existing
+        .iter()
+        .map(|p| p.path.canonicalize().unwrap_or_else(|_| p.path.clone()))
+        .collect();
+
+    let mut imported = Vec::new();
+    for project in projects {
+        let key = project.path.canonicalize().unwrap_or_else(|_| project.path.clone());
+        if known.insert(key) {
+            imported.push(projects::save_project(project)?);
+        }
+    }
+    Ok(imported)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_root(dirs: &[&str]) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("skillpilot-discovery-{}", uuid::Uuid::new_v4()));
+        for dir in dirs {
+            fs::create_dir_all(root.join(dir)).unwrap();
+        }
+        root
+    }
+
+    /// Folder name Claude Code would use for a path
+    fn encoded(path: &Path) -> String {
+        encode_component(&path.to_string_lossy())
+    }
+
+    #[test]
+    fn decodes_names_with_dots_and_dashes() {
+        let root = temp_root(&["my.app/sub-dir"]);
+        let path = root.join("my.app/sub-dir");
+        assert_eq!(decode_claude_project_dir(&encoded(&path)), Some(path));
+    }
+
+    #[test]
+    fn backtracks_when_a_shorter_directory_matches_first() {
+        let root = temp_root(&["a", "a-b/c"]);
+        let path = root.join("a-b/c");
+        assert_eq!(decode_claude_project_dir(&encoded(&path)), Some(path));
+    }
+
+    #[test]
+    fn missing_projects_are_not_decoded() {
+        let root = temp_root(&["app"]);
+        assert_eq!(decode_claude_project_dir(&encoded(&root.join("gone"))), None);
+        assert_eq!(decode_claude_project_dir("no-leading-dash"), None);
+    }
+}
@@ -0,0 +1,325 @@
+use crate::commands::linker::{
+    create_skill_link, project_skills_dir, read_link_entries, remove_skill_link, EntryKind, LinkEntry,
+};
+use crate::commands::conditions::{LocalHost, SkippedEntry, TargetContext};
+use crate::commands::resolver::{resolve_project_links, DesiredLink, LinkConflict, ProjectLinks};
+use crate::commands::{profiles, projects, scanner, settings};
+use crate::error::AppError;
+use crate::models::{Profile, ProjectConfig, Skill};
+use serde::Serialize;
+use std::path::Path;
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub enum DriftKind {
+    /// Desired skill has no link
+    Missing,
+    /// Symlink exists but the project does not want it
+    Extra,
+    /// Desired link exists but its target is gone
+    Broken,
+    /// Desired link points somewhere other than the skill's source
+    MismatchedTarget,
+    /// A real directory sits where the desired link should be
+    ReplacedByDirectory,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DriftItem {
+    pub name: String,
+    pub kind: DriftKind,
+    /// Skill the link belongs to (None for Extra)
+    pub skill_id: Option<String>,
+    /// Source path the link should point at (None for Extra)
+    pub expected_source: Option<String>,
+    /// Current symlink target, if any
+    pub actual_target: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ProjectDriftReport {
+    pub project_id: String,
+    pub project_name: String,
+    pub project_path: String,
+    pub items: Vec<DriftItem>,
+    /// Skill IDs from the project config that matched nothing in the catalog
+    pub unresolved_skill_ids: Vec<String>,
+    /// Conditional profile entries left out for this project, with reasons
+    pub skipped_entries: Vec<SkippedEntry>,
+    /// Link names claimed by several skills, with the candidate that gets the link
+    pub conflicts: Vec<LinkConflict>,
+    pub in_sync: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Default)]
+pub struct ProjectReconcileResult {
+    pub project_id: String,
+    pub project_name: String,
+    pub created: Vec<String>,
+    pub removed: Vec<String>,
+    /// Real directories left in place
+    pub skipped: Vec<String>,
+    /// (name, error) pairs; the project name when its skills directory could not be read
+    pub failed: Vec<(String, String)>,
+}
+
+#[derive(Debug, Clone, Serialize, Default)]
+pub struct ReconcileSummary {
+    pub projects: Vec<ProjectReconcileResult>,
+    pub total_created: usize,
+    pub total_removed: usize,
+    pub total_skipped: usize,
+    pub total_failed: usize,
+}
+
+impl ReconcileSummary {
+    pub(crate) fn push(&mut self, result: ProjectReconcileResult) {
+        self.total_created += result.created.len();
+        self.total_removed += result.removed.len();
+        self.total_skipped += result.skipped.len();
+        self.total_failed += result.failed.len();
+        self.projects.push(result);
+    }
+}
+
+/// Compare desired links with the actual entries of a skills directory.
+/// `points_to` decides whether a raw symlink target refers to a source path.
+pub(crate) fn compute_drift(
+    desired: &[DesiredLink],
+    actual: &[LinkEntry],
+    points_to: impl Fn(&str, &Path) -> bool,
+) -> Vec<DriftItem> {
+    let mut items = Vec::new();
+
+    for want in desired {
+        let expected_source = Some(want.source_path.to_string_lossy().to_string());
+        let kind = match actual.iter().find(|e| e.name == want.name) {
+            None => Some(DriftKind::Missing),
+            Some(e) if e.kind == EntryKind::Directory => Some(DriftKind::ReplacedByDirectory),
+            Some(e) if !e.target_exists => Some(DriftKind::Broken),
+            Some(e) => match &e.target {
+                Some(t) if points_to(t, &want.source_path) => None,
+                _ => Some(DriftKind::MismatchedTarget),
+            },
+        };
+        if let Some(kind) = kind {
+            let actual_target = actual
+                .iter()
+                .find(|e| e.name == want.name)
+                .and_then(|e| e.target.clone());
+            items.push(DriftItem {
+                name: want.name.clone(),
+                kind,
+                skill_id: Some(want.skill_id.clone()),
+                expected_source,
+                actual_target,
+            });
+        }
+    }
+
+    for entry in actual {
+        if entry.kind == EntryKind::Symlink && !desired.iter().any(|d| d.name == entry.name) {
+            items.push(DriftItem {
+                name: entry.name.clone(),
+                kind: DriftKind::Extra,
+                skill_id: None,
+                expected_source: None,
+                actual_target: entry.target.clone(),
+            });
+        }
+    }
+
+    items.sort_by_key(|i| i.name.to_lowercase());
+    items
+}
+
+/// Local target comparison: resolve both sides and compare canonical paths
+pub(crate) fn local_points_to(link_dir: &Path) -> impl Fn(&str, &Path) -> bool + '_ {
+    move |target, source| {
+        let resolved = link_dir.join(target);
+        match (resolved.canonicalize(), source.canonicalize()) {
+            (Ok(a), Ok(b)) => a == b,
+            _ => Path::new(target) == source,
+        }
+    }
+}
+
+/// Build the drift report for one project
+pub(crate) fn project_drift_report(
+    project: &ProjectConfig,
+    links: &ProjectLinks,
+    actual: &[LinkEntry],
+    points_to: impl Fn(&str, &Path) -> bool,
+) -> ProjectDriftReport {
+    let items = compute_drift(&links.desired, actual, points_to);
+    ProjectDriftReport {
+        project_id: project.id.clone(),
+        project_name: project.name.clone(),
+        project_path: project.path.to_string_lossy().to_string(),
+        in_sync: items.is_empty(),
+        items,
+        unresolved_skill_ids: links.unresolved.clone(),
+        skipped_entries: links.skipped.clone(),
+        conflicts: links.conflicts.clone(),
+    }
+}
+
+fn local_drift(
+    project: &ProjectConfig,
+    profiles: &[Profile],
+    skills: &[Skill],
+) -> Result<(ProjectDriftReport, Vec<DesiredLink>), AppError> {
+    let dir = project_skills_dir(&project.path.to_string_lossy());
+    let links = resolve_project_links(project, profiles, skills, &TargetContext::project(&LocalHost, project))?;
+    let actual = read_link_entries(&dir)?;
+    let report = project_drift_report(project, &links, &actual, local_points_to(&dir));
+    Ok((report, links.desired))
+}
+
+/// Select all projects, or only the one with the given ID
+pub(crate) fn select_projects(
+    projects: Vec<ProjectConfig>,
+    project_id: Option<String>,
+) -> Result<Vec<ProjectConfig>, AppError> {
+    match project_id {
+        None => Ok(projects),
+        Some(id) => {
+            let found: Vec<ProjectConfig> = projects.into_iter().filter(|p| p.id == id).collect();
+            if found.is_empty() {
+                return Err(AppError::Custom(format!("Project not found: {}", id)));
+            }
+            Ok(found)
+        }
+    }
+}
+
+/// Report drift between desired and actual skill links for one or all projects
+#[tauri::command]
+pub fn detect_project_drift(
+    repo_path: Option<String>,
+    project_id: Option<String>,
+) -> Result<Vec<ProjectDriftReport>, AppError> {
+    let skills = scanner::scan_skills_repo(repo_path)?;
+    let all_profiles = profiles::load_profiles()?;
+    let selected = select_projects(projects::load_projects()?, project_id)?;
+
+    let mut reports = Vec::new();
+    for project in &selected {
+        reports.push(local_drift(project, &all_profiles, &skills)?.0);
+    }
+    Ok(reports)
+}
+
+/// Fix drift across every registered project
+#[tauri::command]
+pub fn reconcile_all_projects(repo_path: Option<String>) -> Result<ReconcileSummary, AppError> {
+    let skills = scanner::scan_skills_repo(repo_path)?;
+    let all_profiles = profiles::load_profiles()?;
+    let mode = settings::link_mode(None)?;
+
+    let mut summary = ReconcileSummary::default();
+    for project in projects::load_projects()? {
+        let mut result = ProjectReconcileResult {
+            project_id: project.id.clone(),
+            project_name: project.name.clone(),
+            ..Default::default()
+        };
+        let (report, desired) = match local_drift(&project, &all_profiles, &skills) {
+            Ok(drift) => drift,
+            Err(e) => {
+                // Recorded against the project as a whole; the other projects still run
+                result.failed.push((project.name.clone(), e.to_string()));
+                summary.push(result);
+                continue;
+            }
+        };
+        let dir = project_skills_dir(&project.path.to_string_lossy());
+
+        for item in &report.items {
+            let outcome = match item.kind {
+                DriftKind::ReplacedByDirectory => {
+                    result.skipped.push(item.name.clone());
+                    continue;
+                }
+                DriftKind::Extra => remove_skill_link(&item.name, &dir).map(|_| &mut result.removed),
+                _ => {
+                    let source = desired
+                        .iter()
+                        .find(|d| d.name == item.name)
+                        .map(|d| d.source_path.clone())
+                        .unwrap_or_default();
+                    create_skill_link(&item.name, &source, &dir, mode).map(|_| &mut result.created)
+                }
+            };
+            match outcome {
+                Ok(list) => list.push(item.name.clone()),
+                Err(e) => result.failed.push((item.name.clone(), e.to_string())),
+            }
+        }
+
+        summary.push(result);
+    }
+
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn desired(name: &str) -> DesiredLink {
+        DesiredLink {
+            name: name.to_string(),
+            skill_id: format!("skills/{}", name),
+            source_path: PathBuf::from(format!("/repo/skills/{}", name)),
+        }
+    }
+
+    fn link(name: &str, target: &str, target_exists: bool) -> LinkEntry {
+        LinkEntry {
+            name: name.to_string(),
+            kind: EntryKind::Symlink,
+            target: Some(target.to_string()),
+            target_exists,
+        }
+    }
+
+    #[test]
+    fn compute_drift_classifies_entries() {
+        let want = ["broken", "dir", "missing", "moved", "ok"].map(desired);
+        let actual = vec![
+            link("ok", "/repo/skills/ok", true),
+            link("moved", "/elsewhere/moved", true),
+            link("broken", "/repo/skills/broken", false),
+            LinkEntry {
+                name: "dir".to_string(),
+                kind: EntryKind::Directory,
+                target: None,
+                target_exists: true,
+            },
+            link("stale", "/repo/skills/stale", true),
+        ];
+
+        let items = compute_drift(&want, &actual, |t, s| Path::new(t) == s);
+        let kinds: Vec<(&str, DriftKind)> = items.iter().map(|i| (i.name.as_str(), i.kind.clone())).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ("broken", DriftKind::Broken),
+                ("dir", DriftKind::ReplacedByDirectory),
+                ("missing", DriftKind::Missing),
+                ("moved", DriftKind::MismatchedTarget),
+                ("stale", DriftKind::Extra),
+            ]
+        );
+        let moved = items.iter().find(|i| i.name == "moved").unwrap();
+        assert_eq!(moved.actual_target.as_deref(), Some("/elsewhere/moved"));
+        assert_eq!(moved.expected_source.as_deref(), Some("/repo/skills/moved"));
+    }
+
+    #[test]
+    fn compute_drift_is_empty_when_in_sync() {
+        let items = compute_drift(&[desired("ok")], &[link("ok", "../ok", true)], |_, _| true);
+        assert!(items.is_empty());
+    }
+}
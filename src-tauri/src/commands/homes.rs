@@ -0,0 +1,134 @@
+use crate::commands::settings::data_dir;
+use crate::error::AppError;
+use crate::models::{ClaudeHome, HomesConfig};
+use crate::store::{self, Schema};
+use std::path::PathBuf;
+
+pub(crate) const DEFAULT_HOME_ID: &str = "default";
+
+/// Schema of homes.json
+const HOMES_SCHEMA: Schema = Schema {
+    version: 1,
+    migrations: &[store::unversioned],
+};
+
+fn homes_path() -> Result<PathBuf, AppError> {
+    let dir = data_dir()?;
+    Ok(dir.join("homes.json"))
+}
+
+fn load_homes_config() -> Result<HomesConfig, AppError> {
+    Ok(store::read_json(&homes_path()?, &HOMES_SCHEMA)?.unwrap_or_default())
+}
+
+/// Change homes.json under the config lock
+fn update_homes_config<R>(f: impl FnOnce(&mut HomesConfig) -> Result<R, AppError>) -> Result<R, AppError> {
+    store::update_json(&homes_path()?, &HOMES_SCHEMA, f)
+}
+
+/// The home Claude Code itself would use: `CLAUDE_CONFIG_DIR`, else ~/.claude
+fn default_home() -> ClaudeHome {
+    let path = std::env::var("CLAUDE_CONFIG_DIR")
+        .ok()
+        .filter(|p| !p.trim().is_empty())
+        .unwrap_or_else(|| "~/.claude".to_string());
+    ClaudeHome {
+        id: DEFAULT_HOME_ID.to_string(),
+        name: "Default".to_string(),
+        path,
+    }
+}
+
+fn expand(path: &str) -> PathBuf {
+    PathBuf::from(shellexpand::tilde(path).to_string())
+}
+
+/// Directory of the selected Claude home
+pub(crate) fn active_home_dir() -> Result<PathBuf, AppError> {
+    Ok(expand(&get_active_claude_home()?.path))
+}
+
+/// ID of the selected Claude home (used to keep per-home state apart)
+pub(crate) fn active_home_id() -> Result<String, AppError> {
+    Ok(load_homes_config()?
+        .active_id
+        .unwrap_or_else(|| DEFAULT_HOME_ID.to_string()))
+}
+
+/// Path of the `.claude.json` that belongs to a home.
+/// Claude Code keeps it next to ~/.claude by default, and inside CLAUDE_CONFIG_DIR otherwise.
+pub(crate) fn claude_json_path(home_dir: &std::path::Path) -> Result<PathBuf, AppError> {
+    let user_home = dirs::home_dir().ok_or(AppError::ConfigDirNotFound)?;
+    if home_dir == user_home.join(".claude") {
+        Ok(user_home.join(".claude.json"))
+    } else {
+        Ok(home_dir.join(".claude.json"))
+    }
+}
+
+/// List Claude homes (the default home first, user entries override it by ID)
+#[tauri::command]
+pub fn list_claude_homes() -> Result<Vec<ClaudeHome>, AppError> {
+    let mut homes = load_homes_config()?.homes;
+    if !homes.iter().any(|h| h.id == DEFAULT_HOME_ID) {
+        homes.insert(0, default_home());
+    }
+    Ok(homes)
+}
+
+/// Create or update a Claude home
+#[tauri::command]
+pub fn save_claude_home(home: ClaudeHome) -> Result<ClaudeHome, AppError> {
+    store::check_file_id("Claude home", &home.id)?;
+    if home.path.trim().is_empty() {
+        return Err(AppError::Custom("Claude home path must not be empty".to_string()));
+    }
+    update_homes_config(|config| {
+        match config.homes.iter_mut().find(|h| h.id == home.id) {
+            Some(existing) => *existing = home.clone(),
+            None => config.homes.push(home.clone()),
+        }
+        Ok(())
+    })?;
+    Ok(home)
+}
+
+/// Delete a Claude home (for the default home this only removes the override).
+/// Selection falls back to the default home if the deleted one was active.
+#[tauri::command]
+pub fn delete_claude_home(id: String) -> Result<(), AppError> {
+    update_homes_config(|config| {
+        config.homes.retain(|h| h.id != id);
+        if config.active_id.as_deref() == Some(id.as_str()) {
+            config.active_id = None;
+        }
+        Ok(())
+    })
+}
+
+/// Get the Claude home user-level operations currently act on
+#[tauri::command]
+pub fn get_active_claude_home() -> Result<ClaudeHome, AppError> {
+    let active_id = active_home_id()?;
+    let homes = list_claude_homes()?;
+    Ok(homes
+        .iter()
+        .find(|h| h.id == active_id)
+        .cloned()
+        .unwrap_or_else(default_home))
+}
+
+/// Select the Claude home for scanning, toggling, profile application and stats
+#[tauri::command]
+pub fn set_active_claude_home(id: String) -> Result<ClaudeHome, AppError> {
+    let home = list_claude_homes()?
+        .into_iter()
+        .find(|h| h.id == id)
+        .ok_or_else(|| AppError::Custom(format!("Claude home not found: {}", id)))?;
+
+    update_homes_config(|config| {
+        config.active_id = Some(id);
+        Ok(())
+    })?;
+    Ok(home)
+}
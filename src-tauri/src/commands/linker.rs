@@ -1,21 +1,120 @@
+use crate::commands::homes::active_home_dir;
+use crate::commands::settings;
 use crate::error::AppError;
-use crate::models::LinkStatus;
+use crate::models::{LinkMode, LinkStatus};
 use std::fs;
 use std::os::unix::fs as unix_fs;
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
 
-fn user_skills_dir() -> Result<PathBuf, AppError> {
-    dirs::home_dir()
-        .map(|h| h.join(".claude").join("skills"))
-        .ok_or(AppError::ConfigDirNotFound)
+/// User-level skills directory of the selected Claude home
+pub(crate) fn user_skills_dir() -> Result<PathBuf, AppError> {
+    Ok(active_home_dir()?.join("skills"))
 }
 
-fn project_skills_dir(project_path: &str) -> PathBuf {
+pub(crate) fn project_skills_dir(project_path: &str) -> PathBuf {
     PathBuf::from(project_path).join(".claude").join("skills")
 }
 
+/// Kind of entry found in a skills directory
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum EntryKind {
+    Symlink,
+    Directory,
+}
+
+/// A single entry of a skills directory (local or remote)
+#[derive(Debug, Clone)]
+pub(crate) struct LinkEntry {
+    pub name: String,
+    pub kind: EntryKind,
+    /// Raw symlink target (None for real directories)
+    pub target: Option<String>,
+    /// Whether the symlink target exists
+    pub target_exists: bool,
+}
+
+/// Read all symlinks and real directories in a local skills directory
+pub(crate) fn read_link_entries(dir: &Path) -> Result<Vec<LinkEntry>, AppError> {
+    let mut entries = Vec::new();
+    if !dir.is_dir() {
+        return Ok(entries);
+    }
+
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let meta = match path.symlink_metadata() {
+            Ok(m) => m,
+            Err(_) => continue,
+        };
+
+        if meta.file_type().is_symlink() {
+            entries.push(LinkEntry {
+                name,
+                kind: EntryKind::Symlink,
+                target: fs::read_link(&path)
+                    .ok()
+                    .map(|t| t.to_string_lossy().to_string()),
+                target_exists: path.exists(),
+            });
+        } else if meta.is_dir() {
+            entries.push(LinkEntry {
+                name,
+                kind: EntryKind::Directory,
+                target: None,
+                target_exists: true,
+            });
+        }
+    }
+
+    Ok(entries)
+}
+
+/// Check that a link name (skill name or alias) is a single plain path component
+pub(crate) fn validate_link_name(name: &str) -> Result<(), AppError> {
+    if name.is_empty() || name == "." || name == ".." || name.contains('/') || name.contains('\0') {
+        return Err(AppError::Custom(format!("Invalid link name: {:?}", name)));
+    }
+    Ok(())
+}
+
+/// Path of `target` relative to the directory `base` (both absolute)
+fn relative_path(target: &Path, base: &Path) -> PathBuf {
+    let target: Vec<Component> = target.components().collect();
+    let base: Vec<Component> = base.components().collect();
+    let common = target.iter().zip(&base).take_while(|(a, b)| a == b).count();
+    let mut rel = PathBuf::new();
+    for _ in common..base.len() {
+        rel.push("..");
+    }
+    for c in &target[common..] {
+        rel.push(c);
+    }
+    rel
+}
+
+/// What a new symlink in `target_dir` stores to reach `source_path`
+fn link_target(source_path: &Path, target_dir: &Path, mode: LinkMode) -> PathBuf {
+    match mode {
+        LinkMode::Absolute => source_path.to_path_buf(),
+        LinkMode::Relative => match (source_path.canonicalize(), target_dir.canonicalize()) {
+            (Ok(source), Ok(dir)) => relative_path(&source, &dir),
+            _ => source_path.to_path_buf(),
+        },
+    }
+}
+
 /// Create a symlink for a skill at the given target directory
-fn create_skill_link(skill_name: &str, source_path: &Path, target_dir: &Path) -> Result<(), AppError> {
+pub(crate) fn create_skill_link(
+    skill_name: &str,
+    source_path: &Path,
+    target_dir: &Path,
+    mode: LinkMode,
+) -> Result<(), AppError> {
+    validate_link_name(skill_name)?;
     fs::create_dir_all(target_dir)?;
     let link_path = target_dir.join(skill_name);
 
@@ -32,12 +131,12 @@ fn create_skill_link(skill_name: &str, source_path: &Path, target_dir: &Path) ->
         }
     }
 
-    unix_fs::symlink(source_path, &link_path)?;
+    unix_fs::symlink(link_target(source_path, target_dir, mode), &link_path)?;
     Ok(())
 }
 
 /// Remove a symlink for a skill at the given target directory
-fn remove_skill_link(skill_name: &str, target_dir: &Path) -> Result<(), AppError> {
+pub(crate) fn remove_skill_link(skill_name: &str, target_dir: &Path) -> Result<(), AppError> {
     let link_path = target_dir.join(skill_name);
 
     if link_path.symlink_metadata().is_ok() {
@@ -54,41 +153,47 @@ fn remove_skill_link(skill_name: &str, target_dir: &Path) -> Result<(), AppError
     Ok(())
 }
 
-/// Toggle a skill's user-level symlink (create or remove)
+/// Toggle a skill's user-level symlink (create or remove), optionally under an alias
 #[tauri::command]
 pub fn toggle_skill_user_level(
     skill_name: String,
     source_path: String,
     currently_active: bool,
+    link_name: Option<String>,
+    link_mode: Option<LinkMode>,
 ) -> Result<LinkStatus, AppError> {
     let target_dir = user_skills_dir()?;
     let source = PathBuf::from(&source_path);
+    let link_name = link_name.unwrap_or(skill_name);
 
     if currently_active {
-        remove_skill_link(&skill_name, &target_dir)?;
+        remove_skill_link(&link_name, &target_dir)?;
         Ok(LinkStatus::Inactive)
     } else {
-        create_skill_link(&skill_name, &source, &target_dir)?;
+        create_skill_link(&link_name, &source, &target_dir, settings::link_mode(link_mode)?)?;
         Ok(LinkStatus::Active)
     }
 }
 
-/// Toggle a skill's project-level symlink
+/// Toggle a skill's project-level symlink, optionally under an alias
 #[tauri::command]
 pub fn toggle_skill_project_level(
     skill_name: String,
     source_path: String,
     project_path: String,
     currently_active: bool,
+    link_name: Option<String>,
+    link_mode: Option<LinkMode>,
 ) -> Result<LinkStatus, AppError> {
     let target_dir = project_skills_dir(&project_path);
     let source = PathBuf::from(&source_path);
+    let link_name = link_name.unwrap_or(skill_name);
 
     if currently_active {
-        remove_skill_link(&skill_name, &target_dir)?;
+        remove_skill_link(&link_name, &target_dir)?;
         Ok(LinkStatus::Inactive)
     } else {
-        create_skill_link(&skill_name, &source, &target_dir)?;
+        create_skill_link(&link_name, &source, &target_dir, settings::link_mode(link_mode)?)?;
         Ok(LinkStatus::Active)
     }
 }
@@ -96,18 +201,20 @@ pub fn toggle_skill_project_level(
 /// Apply a profile: create symlinks for all skills in the profile
 #[tauri::command]
 pub fn apply_profile_links(
-    skill_entries: Vec<(String, String)>, // (name, source_path) pairs
+    skill_entries: Vec<(String, String)>, // (link name or alias, source_path) pairs
     target_path: Option<String>,          // None = user-level, Some = project-level
+    link_mode: Option<LinkMode>,
 ) -> Result<Vec<String>, AppError> {
     let target_dir = match &target_path {
         Some(p) => project_skills_dir(p),
         None => user_skills_dir()?,
     };
+    let mode = settings::link_mode(link_mode)?;
 
     let mut created = Vec::new();
     for (name, source) in &skill_entries {
         let source_path = PathBuf::from(source);
-        match create_skill_link(name, &source_path, &target_dir) {
+        match create_skill_link(name, &source_path, &target_dir, mode) {
             Ok(()) => created.push(name.clone()),
             Err(e) => eprintln!("Failed to link {}: {}", name, e),
         }
@@ -119,10 +226,12 @@ pub fn apply_profile_links(
 /// Sync a project's skills directory: create missing symlinks and remove stale ones
 #[tauri::command]
 pub fn sync_project_links(
-    skill_entries: Vec<(String, String)>, // desired (name, source_path) pairs
+    skill_entries: Vec<(String, String)>, // desired (link name or alias, source_path) pairs
     project_path: String,
+    link_mode: Option<LinkMode>,
 ) -> Result<Vec<String>, AppError> {
     let target_dir = project_skills_dir(&project_path);
+    let mode = settings::link_mode(link_mode)?;
 
     // Collect desired skill names
     let desired_names: std::collections::HashSet<String> =
@@ -153,7 +262,7 @@ pub fn sync_project_links(
     let mut created = Vec::new();
     for (name, source) in &skill_entries {
         let source_path = PathBuf::from(source);
-        match create_skill_link(name, &source_path, &target_dir) {
+        match create_skill_link(name, &source_path, &target_dir, mode) {
             Ok(()) => created.push(name.clone()),
             Err(e) => eprintln!("Failed to link {}: {}", name, e),
         }
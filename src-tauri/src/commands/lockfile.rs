@@ -0,0 +1,337 @@
+use crate::commands::linker::project_skills_dir;
+use crate::commands::scanner::{self, parse_gitmodules};
+use crate::commands::settings;
+use crate::error::AppError;
+use crate::models::{LockedSkill, Skill, SkillLock};
+use crate::store::{self, Schema};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use walkdir::WalkDir;
+
+const LOCK_VERSION: u32 = 1;
+
+/// Schema of skills.lock
+const LOCK_SCHEMA: Schema = Schema {
+    version: 1,
+    migrations: &[store::unversioned],
+};
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LockWriteReport {
+    /// Path of the written lockfile
+    pub lock_path: String,
+    pub lock: SkillLock,
+    /// Links that could not be matched to a scanned skill (not locked)
+    pub unmatched: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub enum LockEntryStatus {
+    /// Link, revision and content all match the lock
+    Ok,
+    /// The containing repo/submodule is at a different commit
+    Drifted,
+    /// Same commit, but the skill's files differ from the locked hash
+    Modified,
+    /// Link points at a different skill directory than the locked one
+    Retargeted,
+    /// Link or skill no longer exists
+    Missing,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LockEntryCheck {
+    pub name: String,
+    pub skill_id: String,
+    pub status: LockEntryStatus,
+    pub locked_revision: Option<String>,
+    pub current_revision: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LockVerifyReport {
+    pub lock_path: String,
+    pub entries: Vec<LockEntryCheck>,
+    /// Links present in the project but not recorded in the lock
+    pub unlocked: Vec<String>,
+    /// True when every entry is Ok and nothing is unlocked
+    pub clean: bool,
+}
+
+fn lock_path(project_path: &str) -> PathBuf {
+    PathBuf::from(project_path).join(".claude").join("skills.lock")
+}
+
+/// Run `git rev-parse HEAD` in the given directory
+pub(crate) fn git_revision(dir: &Path) -> Option<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(["rev-parse", "HEAD"])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let rev = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if rev.is_empty() {
+        None
+    } else {
+        Some(rev)
+    }
+}
+
+/// SHA-256 over every file in a skill directory (relative path + content, sorted)
+pub(crate) fn hash_skill_dir(dir: &Path) -> Result<String, AppError> {
+    let mut files: Vec<PathBuf> = WalkDir::new(dir)
+        .follow_links(true)
+        .into_iter()
+        .filter_entry(|e| e.file_name() != ".git")
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .map(|e| e.into_path())
+        .collect();
+    files.sort();
+
+    let mut hasher = Sha256::new();
+    for file in &files {
+        let rel = file.strip_prefix(dir).unwrap_or(file);
+        hasher.update(rel.to_string_lossy().as_bytes());
+        hasher.update([0u8]);
+        hasher.update(fs::read(file)?);
+        hasher.update([0u8]);
+    }
+
+    let hex: String = hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect();
+    Ok(format!("sha256:{}", hex))
+}
+
+/// Root directory of the repo/submodule a skill belongs to
+pub(crate) fn source_root(skill: &Skill, repo_root: &Path, submodules: &HashMap<String, PathBuf>) -> PathBuf {
+    submodules
+        .get(&skill.source_repo)
+        .cloned()
+        .unwrap_or_else(|| repo_root.to_path_buf())
+}
+
+/// Build a lock entry for a skill linked under the given name
+pub(crate) fn lock_entry(
+    name: &str,
+    skill: &Skill,
+    repo_root: &Path,
+    submodules: &HashMap<String, PathBuf>,
+) -> Result<LockedSkill, AppError> {
+    let root = source_root(skill, repo_root, submodules);
+    let path = skill
+        .source_path
+        .strip_prefix(&root)
+        .map(|r| r.to_string_lossy().to_string())
+        .unwrap_or_else(|_| skill.id.clone());
+
+    Ok(LockedSkill {
+        name: name.to_string(),
+        skill_id: skill.id.clone(),
+        source_repo: skill.source_repo.clone(),
+        path,
+        revision: git_revision(&root),
+        content_hash: hash_skill_dir(&skill.source_path)?,
+    })
+}
+
+/// Status of a locked skill against a fresh entry for the same skill
+fn compare_entry(locked: &LockedSkill, current: &LockedSkill) -> LockEntryStatus {
+    if current.revision != locked.revision {
+        LockEntryStatus::Drifted
+    } else if current.content_hash != locked.content_hash {
+        LockEntryStatus::Modified
+    } else {
+        LockEntryStatus::Ok
+    }
+}
+
+/// Index scanned skills by canonical source path
+fn skills_by_path(skills: &[Skill]) -> HashMap<PathBuf, &Skill> {
+    skills
+        .iter()
+        .filter_map(|s| s.source_path.canonicalize().ok().map(|p| (p, s)))
+        .collect()
+}
+
+/// List symlinks in a skills directory as (name, canonical target or None if broken)
+fn read_symlinks(dir: &Path) -> Result<Vec<(String, Option<PathBuf>)>, AppError> {
+    let mut links = Vec::new();
+    if !dir.is_dir() {
+        return Ok(links);
+    }
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let is_symlink = path
+            .symlink_metadata()
+            .map(|m| m.file_type().is_symlink())
+            .unwrap_or(false);
+        if !is_symlink {
+            continue;
+        }
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        links.push((name, path.canonicalize().ok()));
+    }
+    links.sort_by_key(|(name, _)| name.to_lowercase());
+    Ok(links)
+}
+
+/// Write <project>/.claude/skills.lock from the project's current links
+#[tauri::command]
+pub fn write_project_lock(project_path: String, repo_path: Option<String>) -> Result<LockWriteReport, AppError> {
+    let repo_path = settings::repo_path(repo_path)?;
+    let repo_root = PathBuf::from(&repo_path);
+    let skills = scanner::scan_skills_repo(Some(repo_path.clone()))?;
+    let by_path = skills_by_path(&skills);
+    let submodules = parse_gitmodules(&repo_root);
+
+    let mut locked = Vec::new();
+    let mut unmatched = Vec::new();
+    for (name, target) in read_symlinks(&project_skills_dir(&project_path))? {
+        match target.as_ref().and_then(|t| by_path.get(t)) {
+            Some(skill) => locked.push(lock_entry(&name, skill, &repo_root, &submodules)?),
+            None => unmatched.push(name),
+        }
+    }
+
+    let lock = SkillLock {
+        version: LOCK_VERSION,
+        generated_at: chrono::Utc::now().to_rfc3339(),
+        repo_path,
+        skills: locked,
+    };
+
+    let path = lock_path(&project_path);
+    store::write_json(&path, &LOCK_SCHEMA, &lock)?;
+
+    Ok(LockWriteReport {
+        lock_path: path.to_string_lossy().to_string(),
+        lock,
+        unmatched,
+    })
+}
+
+/// Verify a project's links against its skills.lock
+#[tauri::command]
+pub fn verify_project_lock(project_path: String, repo_path: Option<String>) -> Result<LockVerifyReport, AppError> {
+    let path = lock_path(&project_path);
+    let lock: SkillLock = store::read_json(&path, &LOCK_SCHEMA)?.ok_or_else(|| {
+        AppError::Custom(format!(
+            "Lockfile not found: {}",
+            path.display()
+        ))
+    })?;
+
+    let repo_path = settings::repo_path(repo_path)?;
+    let repo_root = PathBuf::from(&repo_path);
+    let skills = scanner::scan_skills_repo(Some(repo_path))?;
+    let by_path = skills_by_path(&skills);
+    let submodules = parse_gitmodules(&repo_root);
+    let links: HashMap<String, Option<PathBuf>> =
+        read_symlinks(&project_skills_dir(&project_path))?.into_iter().collect();
+
+    let mut entries = Vec::new();
+    for locked in &lock.skills {
+        let linked = links
+            .get(&locked.name)
+            .and_then(|t| t.as_ref())
+            .and_then(|t| by_path.get(t));
+
+        let (status, current_revision) = match linked {
+            None => (LockEntryStatus::Missing, None),
+            Some(skill) if skill.id != locked.skill_id => (LockEntryStatus::Retargeted, None),
+            Some(skill) => {
+                let current = lock_entry(&locked.name, skill, &repo_root, &submodules)?;
+                (compare_entry(locked, &current), current.revision)
+            }
+        };
+
+        entries.push(LockEntryCheck {
+            name: locked.name.clone(),
+            skill_id: locked.skill_id.clone(),
+            status,
+            locked_revision: locked.revision.clone(),
+            current_revision,
+        });
+    }
+
+    let mut unlocked: Vec<String> = links
+        .keys()
+        .filter(|name| !lock.skills.iter().any(|l| &l.name == *name))
+        .cloned()
+        .collect();
+    unlocked.sort();
+
+    let clean = unlocked.is_empty() && entries.iter().all(|e| e.status == LockEntryStatus::Ok);
+
+    Ok(LockVerifyReport {
+        lock_path: path.to_string_lossy().to_string(),
+        entries,
+        unlocked,
+        clean,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn skill_dir(files: &[(&str, &str)]) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("skillpilot-lock-{}", uuid::Uuid::new_v4()));
+        for (name, content) in files {
+            let path = dir.join(name);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+        dir
+    }
+
+    fn locked(revision: Option<&str>, content_hash: &str) -> LockedSkill {
+        LockedSkill {
+            name: "review".to_string(),
+            skill_id: "skills/review".to_string(),
+            source_repo: "main".to_string(),
+            path: "skills/review".to_string(),
+            revision: revision.map(str::to_string),
+            content_hash: content_hash.to_string(),
+        }
+    }
+
+    #[test]
+    fn hash_covers_paths_and_contents_but_not_git() {
+        let files = [("SKILL.md", "# Review"), ("scripts/run.sh", "echo")];
+        let hash = hash_skill_dir(&skill_dir(&files)).unwrap();
+        assert!(hash.starts_with("sha256:"));
+
+        let with_git = skill_dir(&[("SKILL.md", "# Review"), ("scripts/run.sh", "echo"), (".git/HEAD", "x")]);
+        assert_eq!(hash_skill_dir(&with_git).unwrap(), hash);
+
+        let edited = skill_dir(&[("SKILL.md", "# Review!"), ("scripts/run.sh", "echo")]);
+        assert_ne!(hash_skill_dir(&edited).unwrap(), hash);
+        let moved = skill_dir(&[("SKILL.md", "# Review"), ("bin/run.sh", "echo")]);
+        assert_ne!(hash_skill_dir(&moved).unwrap(), hash);
+    }
+
+    #[test]
+    fn a_new_revision_wins_over_a_content_change() {
+        let lock = locked(Some("abc"), "sha256:1");
+        assert_eq!(compare_entry(&lock, &locked(Some("abc"), "sha256:1")), LockEntryStatus::Ok);
+        assert_eq!(compare_entry(&lock, &locked(Some("abc"), "sha256:2")), LockEntryStatus::Modified);
+        assert_eq!(compare_entry(&lock, &locked(Some("def"), "sha256:2")), LockEntryStatus::Drifted);
+        assert_eq!(compare_entry(&lock, &locked(None, "sha256:1")), LockEntryStatus::Drifted);
+    }
+}
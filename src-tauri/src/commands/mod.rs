@@ -5,3 +5,22 @@ pub mod projects;
 pub mod stats;
 pub mod shell;
 pub mod remote;
+pub mod lockfile;
+pub mod resolver;
+pub mod drift;
+pub mod detect;
+pub mod discovery;
+pub mod snapshots;
+pub mod adopt;
+pub mod targets;
+pub mod homes;
+pub mod query;
+pub mod presets;
+pub mod profile_share;
+pub mod validation;
+pub mod profile_history;
+pub mod conditions;
+pub mod trash;
+pub mod settings;
+pub mod backup;
+pub mod sync;
@@ -0,0 +1,186 @@
+use crate::commands::settings::{data_dir, data_subdir};
+use crate::error::AppError;
+use crate::models::{PresetPack, PresetSettings, Profile};
+use crate::store::{self, Schema};
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Pack shipped with the app
+const BUNDLED_PACK: &str = include_str!("../../presets/default.json");
+
+const DEFAULT_LOCALE: &str = "zh-CN";
+
+/// Schema of installed packs (presets/<id>.json)
+const PACK_SCHEMA: Schema = Schema {
+    version: 1,
+    migrations: &[store::unversioned],
+};
+
+/// Schema of presets.json
+const PRESET_SETTINGS_SCHEMA: Schema = Schema {
+    version: 1,
+    migrations: &[store::unversioned],
+};
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PresetPackInfo {
+    pub id: String,
+    /// Localized pack name
+    pub name: String,
+    /// Whether the pack ships with the app
+    pub bundled: bool,
+    /// File of an installed pack
+    pub path: Option<String>,
+    pub profile_ids: Vec<String>,
+}
+
+fn packs_dir() -> Result<PathBuf, AppError> {
+    data_subdir("presets")
+}
+
+fn settings_path() -> Result<PathBuf, AppError> {
+    Ok(data_dir()?.join("presets.json"))
+}
+
+fn bundled_pack() -> PresetPack {
+    serde_json::from_str(BUNDLED_PACK).expect("bundled preset pack is valid JSON")
+}
+
+/// Installed packs with their files, sorted by file name; unreadable files are skipped
+fn installed_packs() -> Result<Vec<(PathBuf, PresetPack)>, AppError> {
+    let mut packs = Vec::new();
+    for entry in fs::read_dir(packs_dir()?)? {
+        let path = entry?.path();
+        if path.extension().is_some_and(|e| e == "json") {
+            match store::read_json::<PresetPack>(&path, &PACK_SCHEMA) {
+                Ok(Some(pack)) => packs.push((path, pack)),
+                Ok(None) => {}
+                Err(e) => eprintln!("Skipping preset pack {}: {}", path.display(), e),
+            }
+        }
+    }
+    packs.sort_by(|a, b| a.0.cmp(&b.0));
+    Ok(packs)
+}
+
+fn load_settings() -> Result<PresetSettings, AppError> {
+    Ok(store::read_json(&settings_path()?, &PRESET_SETTINGS_SCHEMA)?.unwrap_or_default())
+}
+
+fn locale(settings: &PresetSettings) -> &str {
+    settings.locale.as_deref().unwrap_or(DEFAULT_LOCALE)
+}
+
+fn pack_profiles(pack: &PresetPack, locale: &str) -> Vec<Profile> {
+    pack.profiles
+        .iter()
+        .map(|p| Profile {
+            id: p.id.clone(),
+            name: p.name.get(locale),
+            description: p.description.get(locale),
+            color: p.color.clone(),
+            skill_ids: p.skill_ids.clone(),
+            is_preset: true,
+            aliases: p.aliases.clone(),
+            extends: p.extends.clone(),
+            excludes: p.excludes.clone(),
+            kind: p.kind.clone(),
+            query: p.query.clone(),
+            conditions: p.conditions.clone(),
+            resolved: None,
+        })
+        .collect()
+}
+
+/// Locale that presets and other bundled texts are shown in
+pub(crate) fn preset_locale() -> Result<String, AppError> {
+    Ok(locale(&load_settings()?).to_string())
+}
+
+/// All visible presets: the bundled pack, then installed packs (a later preset replaces one
+/// with the same ID in place), minus hidden ones
+pub(crate) fn load_presets() -> Result<Vec<Profile>, AppError> {
+    let settings = load_settings()?;
+    let locale = locale(&settings);
+
+    let mut presets: Vec<Profile> = Vec::new();
+    let packs = std::iter::once(bundled_pack()).chain(installed_packs()?.into_iter().map(|(_, p)| p));
+    for pack in packs {
+        for profile in pack_profiles(&pack, locale) {
+            match presets.iter_mut().find(|p| p.id == profile.id) {
+                Some(existing) => *existing = profile,
+                None => presets.push(profile),
+            }
+        }
+    }
+
+    presets.retain(|p| !settings.hidden.contains(&p.id));
+    Ok(presets)
+}
+
+/// IDs of hidden presets
+pub(crate) fn hidden_presets() -> Result<Vec<String>, AppError> {
+    Ok(load_settings()?.hidden)
+}
+
+fn pack_info(pack: &PresetPack, path: Option<&Path>, locale: &str) -> PresetPackInfo {
+    PresetPackInfo {
+        id: pack.id.clone(),
+        name: pack.name.get(locale),
+        bundled: path.is_none(),
+        path: path.map(|p| p.to_string_lossy().to_string()),
+        profile_ids: pack.profiles.iter().map(|p| p.id.clone()).collect(),
+    }
+}
+
+/// List the bundled and installed preset packs
+#[tauri::command]
+pub fn list_preset_packs() -> Result<Vec<PresetPackInfo>, AppError> {
+    let settings = load_settings()?;
+    let locale = locale(&settings);
+
+    let mut packs = vec![pack_info(&bundled_pack(), None, locale)];
+    for (path, pack) in installed_packs()? {
+        packs.push(pack_info(&pack, Some(&path), locale));
+    }
+    Ok(packs)
+}
+
+/// Install a preset pack from a JSON file (replaces an installed pack with the same ID)
+#[tauri::command]
+pub fn install_preset_pack(path: String) -> Result<PresetPackInfo, AppError> {
+    let content = fs::read_to_string(&path)?;
+    let pack: PresetPack = serde_json::from_str(&content)?;
+    if pack.id.is_empty() || pack.id.contains(['/', '\\']) || pack.id.starts_with('.') {
+        return Err(AppError::Custom(format!("Invalid preset pack ID: {:?}", pack.id)));
+    }
+
+    let dest = packs_dir()?.join(format!("{}.json", pack.id));
+    store::write_json(&dest, &PACK_SCHEMA, &pack)?;
+    Ok(pack_info(&pack, Some(&dest), locale(&load_settings()?)))
+}
+
+/// Remove an installed preset pack
+#[tauri::command]
+pub fn remove_preset_pack(id: String) -> Result<(), AppError> {
+    for (path, pack) in installed_packs()? {
+        if pack.id == id {
+            store::remove(&path, &PACK_SCHEMA)?;
+        }
+    }
+    Ok(())
+}
+
+/// Get preset display settings (hidden presets, locale)
+#[tauri::command]
+pub fn get_preset_settings() -> Result<PresetSettings, AppError> {
+    load_settings()
+}
+
+/// Save preset display settings
+#[tauri::command]
+pub fn save_preset_settings(settings: PresetSettings) -> Result<PresetSettings, AppError> {
+    store::write_json(&settings_path()?, &PRESET_SETTINGS_SCHEMA, &settings)?;
+    Ok(settings)
+}
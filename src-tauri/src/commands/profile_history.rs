@@ -0,0 +1,330 @@
+use crate::commands::drift::{compute_drift, local_points_to, DriftItem};
+use crate::commands::linker::{project_skills_dir, read_link_entries, user_skills_dir};
+use crate::commands::conditions::{LocalHost, SkippedEntry, TargetContext};
+use crate::commands::resolver::resolve_profile_links;
+use crate::commands::settings::load_settings;
+use crate::commands::{profiles, projects, scanner};
+use crate::commands::settings::data_subdir;
+use crate::error::AppError;
+use crate::models::{Profile, ProfileVersion};
+use crate::store::{self, Schema};
+use serde::Serialize;
+use std::fs;
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FieldChange {
+    pub field: String,
+    pub from: serde_json::Value,
+    pub to: serde_json::Value,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ProfileVersionDiff {
+    pub profile_id: String,
+    /// Version compared from (None = current profile)
+    pub from_version: Option<u32>,
+    /// Version compared to (None = current profile)
+    pub to_version: Option<u32>,
+    pub added_skills: Vec<String>,
+    pub removed_skills: Vec<String>,
+    /// Other declared fields that differ (name, aliases, extends, ...)
+    pub changed_fields: Vec<FieldChange>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ProfileLinkDiff {
+    pub profile_id: String,
+    /// Version compared (None = current profile)
+    pub version: Option<u32>,
+    /// Project path, or None for the user-level skills directory
+    pub target_path: Option<String>,
+    /// Missing/mismatched links the profile wants, and links it does not declare (Extra)
+    pub items: Vec<DriftItem>,
+    /// Skill IDs of the profile that matched nothing in the catalog
+    pub unresolved_skill_ids: Vec<String>,
+    /// Conditional entries left out for this target, with reasons
+    pub skipped_entries: Vec<SkippedEntry>,
+}
+
+/// Schema of recorded versions, locally and on remote servers
+pub(crate) const VERSION_SCHEMA: Schema = Schema {
+    version: 1,
+    migrations: &[store::unversioned],
+};
+
+fn history_dir(profile_id: &str) -> Result<PathBuf, AppError> {
+    let dir = data_subdir("profile-history")?.join(profile_id);
+    fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
+fn declared(profile: &Profile) -> Profile {
+    Profile {
+        resolved: None,
+        ..profile.clone()
+    }
+}
+
+fn same_content(a: &Profile, b: &Profile) -> bool {
+    serde_json::to_value(declared(a)).ok() == serde_json::to_value(declared(b)).ok()
+}
+
+/// Versions to add when `profile` is saved over `previous` (the file being replaced), and
+/// version numbers to prune beyond `max_versions`. The previous file is recorded first when it
+/// has no history yet; saving unchanged content adds nothing.
+pub(crate) fn plan_record(
+    versions: &[ProfileVersion],
+    previous: Option<&Profile>,
+    profile: &Profile,
+    max_versions: usize,
+) -> (Vec<ProfileVersion>, Vec<u32>) {
+    let now = chrono::Utc::now().to_rfc3339();
+    let mut latest = versions.iter().max_by_key(|v| v.version).cloned();
+    let mut added = Vec::new();
+
+    if let (None, Some(prev)) = (&latest, previous) {
+        let first = ProfileVersion {
+            version: 1,
+            saved_at: now.clone(),
+            profile: declared(prev),
+        };
+        added.push(first.clone());
+        latest = Some(first);
+    }
+    if latest.as_ref().is_some_and(|l| same_content(&l.profile, profile)) {
+        return (added, Vec::new());
+    }
+    added.push(ProfileVersion {
+        version: latest.map_or(1, |l| l.version + 1),
+        saved_at: now,
+        profile: declared(profile),
+    });
+
+    let mut numbers: Vec<u32> = versions.iter().chain(added.iter()).map(|v| v.version).collect();
+    numbers.sort_unstable();
+    let excess = numbers.len().saturating_sub(max_versions);
+    (added, numbers.into_iter().take(excess).collect())
+}
+
+/// Version `version` of a profile, or the current profile for None
+pub(crate) fn pick_version(
+    versions: &[ProfileVersion],
+    current: Option<&Profile>,
+    profile_id: &str,
+    version: Option<u32>,
+) -> Result<Profile, AppError> {
+    match version {
+        Some(n) => versions
+            .iter()
+            .find(|v| v.version == n)
+            .map(|v| v.profile.clone())
+            .ok_or_else(|| AppError::Custom(format!("Version {} of profile {} not found", n, profile_id))),
+        None => current
+            .map(declared)
+            .ok_or_else(|| AppError::Custom(format!("Profile not found: {}", profile_id))),
+    }
+}
+
+fn field_values(profile: &Profile) -> serde_json::Map<String, serde_json::Value> {
+    match serde_json::to_value(declared(profile)) {
+        Ok(serde_json::Value::Object(map)) => map,
+        _ => serde_json::Map::new(),
+    }
+}
+
+/// Compare two states of a profile
+pub(crate) fn diff_profiles(from: &Profile, to: &Profile, from_version: Option<u32>, to_version: Option<u32>) -> ProfileVersionDiff {
+    let from_fields = field_values(from);
+    let to_fields = field_values(to);
+    let changed_fields = to_fields
+        .iter()
+        .filter(|(k, _)| !matches!(k.as_str(), "id" | "skill_ids" | "resolved"))
+        .filter(|(k, v)| from_fields.get(*k) != Some(v))
+        .map(|(k, v)| FieldChange {
+            field: k.clone(),
+            from: from_fields.get(k).cloned().unwrap_or(serde_json::Value::Null),
+            to: v.clone(),
+        })
+        .collect();
+
+    ProfileVersionDiff {
+        profile_id: to.id.clone(),
+        from_version,
+        to_version,
+        added_skills: to.skill_ids.iter().filter(|s| !from.skill_ids.contains(s)).cloned().collect(),
+        removed_skills: from.skill_ids.iter().filter(|s| !to.skill_ids.contains(s)).cloned().collect(),
+        changed_fields,
+    }
+}
+
+/// Read all recorded versions of a profile, oldest first; unreadable files are skipped
+fn load_versions(profile_id: &str) -> Result<Vec<ProfileVersion>, AppError> {
+    let mut versions = Vec::new();
+    for entry in fs::read_dir(history_dir(profile_id)?)? {
+        let path = entry?.path();
+        if path.extension().is_some_and(|e| e == "json") {
+            if let Ok(Some(version)) = store::read_json::<ProfileVersion>(&path, &VERSION_SCHEMA) {
+                versions.push(version);
+            }
+        }
+    }
+    versions.sort_by_key(|v| v.version);
+    Ok(versions)
+}
+
+/// Record a saved profile in its local history
+pub(crate) fn record_version(previous: Option<&Profile>, profile: &Profile) -> Result<(), AppError> {
+    let dir = history_dir(&profile.id)?;
+    let max_versions = load_settings()?.thresholds.max_profile_versions;
+    let (added, pruned) = plan_record(&load_versions(&profile.id)?, previous, profile, max_versions);
+    for version in added {
+        store::write_json(&dir.join(format!("{}.json", version.version)), &VERSION_SCHEMA, &version)?;
+    }
+    for n in pruned {
+        let _ = store::remove(&dir.join(format!("{}.json", n)), &VERSION_SCHEMA);
+    }
+    Ok(())
+}
+
+fn current_profile(profile_id: &str) -> Result<Option<Profile>, AppError> {
+    Ok(profiles::load_profiles()?.into_iter().find(|p| p.id == profile_id))
+}
+
+/// List the saved versions of a profile, newest first
+#[tauri::command]
+pub fn list_profile_versions(profile_id: String) -> Result<Vec<ProfileVersion>, AppError> {
+    let mut versions = load_versions(&profile_id)?;
+    versions.reverse();
+    Ok(versions)
+}
+
+/// Compare two versions of a profile (None = the current profile)
+#[tauri::command]
+pub fn diff_profile_versions(
+    profile_id: String,
+    from_version: Option<u32>,
+    to_version: Option<u32>,
+) -> Result<ProfileVersionDiff, AppError> {
+    let versions = load_versions(&profile_id)?;
+    let current = current_profile(&profile_id)?;
+    let from = pick_version(&versions, current.as_ref(), &profile_id, from_version)?;
+    let to = pick_version(&versions, current.as_ref(), &profile_id, to_version)?;
+    Ok(diff_profiles(&from, &to, from_version, to_version))
+}
+
+/// Compare a profile (or one of its versions) with the live links of the user-level
+/// skills directory or a project's
+#[tauri::command]
+pub fn diff_profile_with_links(
+    profile_id: String,
+    version: Option<u32>,
+    project_path: Option<String>,
+    repo_path: Option<String>,
+) -> Result<ProfileLinkDiff, AppError> {
+    let all = profiles::load_profiles()?;
+    let current = all.iter().find(|p| p.id == profile_id);
+    let profile = pick_version(&load_versions(&profile_id)?, current, &profile_id, version)?;
+
+    let skills = scanner::scan_skills_repo(repo_path)?;
+    let ctx = TargetContext::for_path(&LocalHost, project_path.as_deref(), &projects::load_projects()?);
+    let links = resolve_profile_links(&profile, &all, &skills, &ctx)?;
+
+    let dir = match &project_path {
+        Some(p) => project_skills_dir(p),
+        None => user_skills_dir()?,
+    };
+    let actual = read_link_entries(&dir)?;
+    Ok(ProfileLinkDiff {
+        profile_id,
+        version,
+        target_path: project_path,
+        items: compute_drift(&links.desired, &actual, local_points_to(&dir)),
+        unresolved_skill_ids: links.unresolved,
+        skipped_entries: links.skipped,
+    })
+}
+
+/// Restore an earlier version of a profile; the restore itself becomes a new version
+#[tauri::command]
+pub fn restore_profile_version(profile_id: String, version: u32) -> Result<Profile, AppError> {
+    let versions = load_versions(&profile_id)?;
+    let profile = pick_version(&versions, None, &profile_id, Some(version))?;
+    profiles::save_profile(profile)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ProfileKind;
+    use std::collections::HashMap;
+
+    fn profile(skill_ids: &[&str]) -> Profile {
+        Profile {
+            id: "web".to_string(),
+            name: "Web".to_string(),
+            description: String::new(),
+            color: "#000".to_string(),
+            skill_ids: skill_ids.iter().map(|s| s.to_string()).collect(),
+            is_preset: false,
+            aliases: HashMap::new(),
+            extends: Vec::new(),
+            excludes: Vec::new(),
+            kind: ProfileKind::Static,
+            query: None,
+            conditions: HashMap::new(),
+            resolved: None,
+        }
+    }
+
+    fn version(version: u32, skill_ids: &[&str]) -> ProfileVersion {
+        ProfileVersion {
+            version,
+            saved_at: String::new(),
+            profile: profile(skill_ids),
+        }
+    }
+
+    fn numbers(versions: &[ProfileVersion]) -> Vec<u32> {
+        versions.iter().map(|v| v.version).collect()
+    }
+
+    #[test]
+    fn first_save_records_the_replaced_file_too() {
+        let (added, pruned) = plan_record(&[], Some(&profile(&["a"])), &profile(&["a", "b"]), 10);
+        assert_eq!(numbers(&added), vec![1, 2]);
+        assert_eq!(added[0].profile.skill_ids, vec!["a"]);
+        assert!(pruned.is_empty());
+
+        let (added, _) = plan_record(&[], None, &profile(&["a"]), 10);
+        assert_eq!(numbers(&added), vec![1]);
+    }
+
+    #[test]
+    fn unchanged_saves_add_nothing_and_old_versions_are_pruned() {
+        let history = [version(1, &["a"]), version(2, &["a", "b"]), version(3, &["b"])];
+        let (added, pruned) = plan_record(&history, Some(&profile(&["b"])), &profile(&["b"]), 2);
+        assert!(added.is_empty() && pruned.is_empty());
+
+        let (added, pruned) = plan_record(&history, Some(&profile(&["b"])), &profile(&["c"]), 2);
+        assert_eq!(numbers(&added), vec![4]);
+        assert_eq!(pruned, vec![1, 2]);
+    }
+
+    #[test]
+    fn diff_lists_skill_and_field_changes() {
+        let from = profile(&["a", "b"]);
+        let mut to = profile(&["b", "c"]);
+        to.name = "Frontend".to_string();
+
+        let diff = diff_profiles(&from, &to, Some(1), None);
+        assert_eq!(diff.added_skills, vec!["c"]);
+        assert_eq!(diff.removed_skills, vec!["a"]);
+        let fields: Vec<&str> = diff.changed_fields.iter().map(|c| c.field.as_str()).collect();
+        assert_eq!(fields, vec!["name"]);
+        assert_eq!(diff.changed_fields[0].from, serde_json::json!("Web"));
+
+        assert!(pick_version(&[version(1, &["a"])], None, "web", Some(2)).is_err());
+        assert_eq!(pick_version(&[], Some(&to), "web", None).unwrap().name, "Frontend");
+    }
+}
@@ -0,0 +1,323 @@
+use crate::commands::lockfile::{git_revision, source_root};
+use crate::commands::profiles::{self, resolve_profile};
+use crate::commands::resolver::find_skill;
+use crate::commands::scanner::{self, parse_gitmodules};
+use crate::error::AppError;
+use crate::models::{BundledSkill, Profile, ProfileBundle, Skill};
+use crate::store;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const BUNDLE_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum ExportFormat {
+    Json,
+    Yaml,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportReport {
+    pub path: String,
+    /// Exported profile IDs, including ancestors pulled in through `extends`
+    pub profile_ids: Vec<String>,
+    /// Number of skills described in the bundle
+    pub skill_count: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MissingSkill {
+    pub skill_id: String,
+    /// Imported profiles that reference the skill
+    pub profile_ids: Vec<String>,
+    /// Source repo recorded in the bundle, if any
+    pub source_repo: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RevisionMismatch {
+    pub skill_id: String,
+    pub bundled: String,
+    pub current: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Default)]
+pub struct ImportReport {
+    pub imported: Vec<String>,
+    /// Profiles that already exist and were left alone (import without overwrite)
+    pub skipped: Vec<String>,
+    /// Whether referenced skills were checked against a scanned catalog
+    pub catalog_checked: bool,
+    pub missing_skills: Vec<MissingSkill>,
+    /// Skills present in the catalog at a different git revision than the bundle records
+    pub revision_mismatches: Vec<RevisionMismatch>,
+}
+
+/// Accepted import documents: a bundle, a list of profiles or a single profile
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ImportDocument {
+    Bundle(ProfileBundle),
+    List(Vec<Profile>),
+    Single(Box<Profile>),
+}
+
+fn format_for(path: &Path, format: Option<ExportFormat>) -> ExportFormat {
+    format.unwrap_or_else(|| match path.extension().and_then(|e| e.to_str()) {
+        Some("yaml") | Some("yml") => ExportFormat::Yaml,
+        _ => ExportFormat::Json,
+    })
+}
+
+/// Selected profiles plus every ancestor they extend, in selection order
+fn with_ancestors(ids: &[String], all: &[Profile]) -> Result<Vec<Profile>, AppError> {
+    let mut selected: Vec<Profile> = Vec::new();
+    let mut queue: Vec<String> = ids.to_vec();
+    while let Some(id) = queue.first().cloned() {
+        queue.remove(0);
+        if selected.iter().any(|p| p.id == id) {
+            continue;
+        }
+        let profile = all
+            .iter()
+            .find(|p| p.id == id)
+            .ok_or_else(|| AppError::Custom(format!("Profile not found: {}", id)))?;
+        queue.extend(
+            profile
+                .extends
+                .iter()
+                .filter(|p| all.iter().any(|a| &a.id == *p))
+                .cloned(),
+        );
+        selected.push(profile.clone());
+    }
+    Ok(selected)
+}
+
+/// Describe the catalog skills a set of profiles resolves to
+fn bundled_skills(
+    profiles: &[Profile],
+    all: &[Profile],
+    skills: &[Skill],
+    repo_root: &Path,
+) -> Vec<BundledSkill> {
+    let submodules = parse_gitmodules(repo_root);
+    let mut bundled: Vec<BundledSkill> = Vec::new();
+    for profile in profiles {
+        for resolved in resolve_profile(profile, all, Some(skills)).skills {
+            let skill = match find_skill(skills, &resolved.skill_id) {
+                Some(s) => s,
+                None => continue,
+            };
+            if bundled.iter().any(|b| b.skill_id == skill.id) {
+                continue;
+            }
+            bundled.push(BundledSkill {
+                skill_id: skill.id.clone(),
+                name: skill.name.clone(),
+                source_repo: skill.source_repo.clone(),
+                revision: git_revision(&source_root(skill, repo_root, &submodules)),
+            });
+        }
+    }
+    bundled
+}
+
+/// Export profiles to a JSON or YAML file, optionally with the referenced skills' sources and revisions
+#[tauri::command]
+pub fn export_profiles(
+    profile_ids: Vec<String>,
+    path: String,
+    format: Option<ExportFormat>,
+    include_skills: bool,
+    repo_path: Option<String>,
+) -> Result<ExportReport, AppError> {
+    let all = profiles::load_profiles()?;
+    let mut selected = with_ancestors(&profile_ids, &all)?;
+
+    let skills = match (include_skills, &repo_path) {
+        (true, Some(repo)) => bundled_skills(
+            &selected,
+            &all,
+            &scanner::scan_skills_repo(Some(repo.clone()))?,
+            Path::new(repo),
+        ),
+        (true, None) => {
+            return Err(AppError::Custom(
+                "A repository path is required to include skills".to_string(),
+            ))
+        }
+        (false, _) => Vec::new(),
+    };
+
+    for profile in &mut selected {
+        profile.resolved = None;
+    }
+    let bundle = ProfileBundle {
+        version: BUNDLE_VERSION,
+        exported_at: chrono::Utc::now().to_rfc3339(),
+        profiles: selected,
+        skills,
+    };
+
+    let dest = PathBuf::from(&path);
+    let content = match format_for(&dest, format) {
+        ExportFormat::Json => serde_json::to_string_pretty(&bundle)?,
+        ExportFormat::Yaml => serde_yaml::to_string(&bundle)?,
+    };
+    fs::write(&dest, content)?;
+
+    Ok(ExportReport {
+        path,
+        profile_ids: bundle.profiles.iter().map(|p| p.id.clone()).collect(),
+        skill_count: bundle.skills.len(),
+    })
+}
+
+/// Parse an import file (JSON or YAML) into a bundle
+fn read_bundle(path: &Path) -> Result<ProfileBundle, AppError> {
+    let content = fs::read_to_string(path)?;
+    let document: ImportDocument = match format_for(path, None) {
+        ExportFormat::Json => serde_json::from_str(&content)?,
+        ExportFormat::Yaml => serde_yaml::from_str(&content)?,
+    };
+    Ok(match document {
+        ImportDocument::Bundle(bundle) => bundle,
+        ImportDocument::List(profiles) => ProfileBundle {
+            version: BUNDLE_VERSION,
+            exported_at: String::new(),
+            profiles,
+            skills: Vec::new(),
+        },
+        ImportDocument::Single(profile) => ProfileBundle {
+            version: BUNDLE_VERSION,
+            exported_at: String::new(),
+            profiles: vec![*profile],
+            skills: Vec::new(),
+        },
+    })
+}
+
+/// Compare the imported profiles' skills with the local catalog
+fn check_catalog(
+    bundle: &ProfileBundle,
+    skills: &[Skill],
+    repo_root: &Path,
+    report: &mut ImportReport,
+) {
+    let submodules = parse_gitmodules(repo_root);
+    let imported = report.imported.clone();
+    for profile in bundle.profiles.iter().filter(|p| imported.contains(&p.id)) {
+        for sid in &profile.skill_ids {
+            if find_skill(skills, sid).is_some() {
+                continue;
+            }
+            match report
+                .missing_skills
+                .iter_mut()
+                .find(|m| &m.skill_id == sid)
+            {
+                Some(missing) => missing.profile_ids.push(profile.id.clone()),
+                None => report.missing_skills.push(MissingSkill {
+                    skill_id: sid.clone(),
+                    profile_ids: vec![profile.id.clone()],
+                    source_repo: bundle
+                        .skills
+                        .iter()
+                        .find(|b| &b.skill_id == sid || &b.name == sid)
+                        .map(|b| b.source_repo.clone()),
+                }),
+            }
+        }
+    }
+
+    for bundled in &bundle.skills {
+        let (skill, revision) = match (find_skill(skills, &bundled.skill_id), &bundled.revision) {
+            (Some(s), Some(r)) => (s, r),
+            _ => continue,
+        };
+        let current = git_revision(&source_root(skill, repo_root, &submodules));
+        if current.as_ref() != Some(revision) {
+            report.revision_mismatches.push(RevisionMismatch {
+                skill_id: bundled.skill_id.clone(),
+                bundled: revision.clone(),
+                current,
+            });
+        }
+    }
+}
+
+/// Import profiles from a JSON or YAML file (a bundle, a list or a single profile).
+/// Nothing is imported when a profile ID is not a safe file name. Existing profiles are kept
+/// unless `overwrite`; with `repo_path` the referenced skills are checked against the local catalog.
+#[tauri::command]
+pub fn import_profiles(
+    path: String,
+    overwrite: bool,
+    repo_path: Option<String>,
+) -> Result<ImportReport, AppError> {
+    let bundle = read_bundle(Path::new(&path))?;
+    for profile in &bundle.profiles {
+        store::check_file_id("profile", &profile.id)?;
+    }
+    let mut report = ImportReport::default();
+
+    let existing = profiles::load_profiles()?;
+    for profile in &bundle.profiles {
+        if existing.iter().any(|p| p.id == profile.id) && !overwrite {
+            report.skipped.push(profile.id.clone());
+            continue;
+        }
+        profiles::save_profile(profile.clone())?;
+        report.imported.push(profile.id.clone());
+    }
+
+    if let Some(repo) = repo_path {
+        let skills = scanner::scan_skills_repo(Some(repo.clone()))?;
+        check_catalog(&bundle, &skills, Path::new(&repo), &mut report);
+        report.catalog_checked = true;
+    }
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PROFILE: &str = r##"{"id":"web","name":"Web","description":"","color":"#000","skill_ids":["a"],"is_preset":false}"##;
+
+    fn import_file(name: &str, content: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("skillpilot-share-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(name);
+        fs::write(&path, content).unwrap();
+        path
+    }
+
+    #[test]
+    fn read_bundle_accepts_a_list_or_a_single_profile() {
+        let list = read_bundle(&import_file("list.json", &format!("[{}]", PROFILE))).unwrap();
+        let single = read_bundle(&import_file("single.json", PROFILE)).unwrap();
+        for bundle in [list, single] {
+            assert_eq!(bundle.profiles.len(), 1);
+            assert_eq!(bundle.profiles[0].skill_ids, vec!["a"]);
+            assert!(bundle.skills.is_empty());
+        }
+    }
+
+    #[test]
+    fn read_bundle_reads_yaml() {
+        let yaml = "version: 1\nexported_at: ''\nskills: []\nprofiles:\n  - id: web\n    name: Web\n    description: ''\n    color: '#000'\n    skill_ids: [a, b]\n    is_preset: false\n";
+        let bundle = read_bundle(&import_file("bundle.yaml", yaml)).unwrap();
+        assert_eq!(bundle.profiles[0].skill_ids, vec!["a", "b"]);
+    }
+
+    #[test]
+    fn import_refuses_profile_ids_that_leave_the_profiles_directory() {
+        let path = import_file("evil.json", &PROFILE.replace(r#""web""#, r#""../web""#));
+        let err = import_profiles(path.to_string_lossy().to_string(), true, None).unwrap_err();
+        assert!(err.to_string().contains("Invalid profile ID"), "{}", err);
+    }
+}
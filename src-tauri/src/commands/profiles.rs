@@ -1,27 +1,180 @@
+use crate::commands::linker::{project_skills_dir, read_link_entries, user_skills_dir, EntryKind, LinkEntry};
+use crate::commands::presets::{hidden_presets, load_presets};
+use crate::commands::profile_history::record_version;
+use crate::commands::query::evaluate_query;
+use crate::commands::scanner;
+use crate::commands::settings::{data_subdir, default_repo_path};
+use crate::commands::trash::move_to_trash;
 use crate::error::AppError;
-use crate::models::Profile;
+use crate::models::{Profile, ProfileKind, ResolvedProfile, ResolvedSkill, Skill, TrashKind};
+use crate::store::{self, Schema};
+use serde::Serialize;
+use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-fn config_dir() -> Result<PathBuf, AppError> {
-    dirs::home_dir()
-        .map(|h| h.join(".claude-skill-manager"))
-        .ok_or(AppError::ConfigDirNotFound)
+/// Default color for generated profiles (first swatch in the profile editor)
+const DEFAULT_PROFILE_COLOR: &str = "#5e6ad2";
+
+#[derive(Debug, Clone, Serialize)]
+pub struct UnmatchedLink {
+    pub name: String,
+    pub target: Option<String>,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ProfileFromLinks {
+    pub profile: Profile,
+    /// (link name, skill ID) pairs that made it into the profile
+    pub matched: Vec<(String, String)>,
+    pub unmatched: Vec<UnmatchedLink>,
 }
 
+/// Schema of the user profile files
+pub(crate) const PROFILE_SCHEMA: Schema = Schema {
+    version: 1,
+    migrations: &[store::unversioned],
+};
+
 fn profiles_dir() -> Result<PathBuf, AppError> {
-    let dir = config_dir()?.join("profiles");
-    fs::create_dir_all(&dir)?;
-    Ok(dir)
+    data_subdir("profiles")
 }
 
 fn profile_path(id: &str) -> Result<PathBuf, AppError> {
     Ok(profiles_dir()?.join(format!("{}.json", id)))
 }
 
-/// List all profiles (presets + user-created, user overrides take priority)
+/// Flatten a profile's skills: parents first (each with its own excludes applied), then query
+/// matches (dynamic profiles, when a catalog is given), then its own skills.
+/// `stack` holds the chain being resolved; an edge back into it is cut and reported as a cycle.
+fn flatten_profile(
+    profile: &Profile,
+    profiles: &[Profile],
+    skills_catalog: Option<&[Skill]>,
+    stack: &mut Vec<String>,
+    out: &mut ResolvedProfile,
+) -> Vec<ResolvedSkill> {
+    let mut skills: Vec<ResolvedSkill> = Vec::new();
+    let add = |skill: ResolvedSkill, skills: &mut Vec<ResolvedSkill>| {
+        if !skills.iter().any(|s| s.skill_id == skill.skill_id) {
+            skills.push(skill);
+        }
+    };
+
+    for parent_id in &profile.extends {
+        if let Some(pos) = stack.iter().position(|id| id == parent_id) {
+            if out.cycle.is_none() {
+                let mut path = stack[pos..].to_vec();
+                path.push(parent_id.clone());
+                out.cycle = Some(path);
+            }
+            continue;
+        }
+        let parent = match profiles.iter().find(|p| &p.id == parent_id) {
+            Some(p) => p,
+            None => {
+                if !out.missing_parents.contains(parent_id) {
+                    out.missing_parents.push(parent_id.clone());
+                }
+                continue;
+            }
+        };
+        stack.push(parent_id.clone());
+        for skill in flatten_profile(parent, profiles, skills_catalog, stack, out) {
+            add(skill, &mut skills);
+        }
+        stack.pop();
+    }
+
+    if profile.kind == ProfileKind::Dynamic {
+        match (&profile.query, skills_catalog) {
+            (Some(query), Some(catalog)) => {
+                for sid in evaluate_query(query, catalog) {
+                    add(
+                        ResolvedSkill {
+                            skill_id: sid,
+                            from_profile_id: profile.id.clone(),
+                        },
+                        &mut skills,
+                    );
+                }
+            }
+            (Some(_), None) => out.query_pending = true,
+            (None, _) => {}
+        }
+    }
+
+    for sid in &profile.skill_ids {
+        add(
+            ResolvedSkill {
+                skill_id: sid.clone(),
+                from_profile_id: profile.id.clone(),
+            },
+            &mut skills,
+        );
+    }
+
+    skills.retain(|s| !profile.excludes.contains(&s.skill_id));
+    skills
+}
+
+/// Resolve one profile against the full profile list (and the catalog, for dynamic profiles)
+pub(crate) fn resolve_profile(profile: &Profile, profiles: &[Profile], skills: Option<&[Skill]>) -> ResolvedProfile {
+    let mut out = ResolvedProfile::default();
+    let mut stack = vec![profile.id.clone()];
+    out.skills = flatten_profile(profile, profiles, skills, &mut stack, &mut out);
+    out
+}
+
+/// Fill in the resolved form of every profile in the list
+pub(crate) fn resolve_profiles(profiles: &mut [Profile], skills: Option<&[Skill]>) {
+    let resolved: Vec<ResolvedProfile> = profiles
+        .iter()
+        .map(|p| resolve_profile(p, profiles, skills))
+        .collect();
+    for (profile, r) in profiles.iter_mut().zip(resolved) {
+        profile.resolved = Some(r);
+    }
+}
+
+/// Resolve a profile about to be saved, rejecting it if it would create an inheritance cycle
+pub(crate) fn check_inheritance(profile: &Profile, existing: &[Profile]) -> Result<ResolvedProfile, AppError> {
+    let mut profiles: Vec<Profile> = existing.iter().filter(|p| p.id != profile.id).cloned().collect();
+    profiles.push(profile.clone());
+
+    let resolved = resolve_profile(profile, &profiles, None);
+    if let Some(cycle) = &resolved.cycle {
+        return Err(AppError::Custom(format!(
+            "Profile inheritance cycle: {}",
+            cycle.join(" -> ")
+        )));
+    }
+    Ok(resolved)
+}
+
+/// Whether any profile needs the catalog to be resolved
+pub(crate) fn has_dynamic_profiles(profiles: &[Profile]) -> bool {
+    profiles.iter().any(|p| p.kind == ProfileKind::Dynamic)
+}
+
+/// List all profiles (presets + user-created, user overrides take priority),
+/// each with its declared fields and its resolved skill list.
+/// Dynamic profiles are evaluated against a scan of `repo_path` (or the configured repo root) when there is one.
 #[tauri::command]
-pub fn list_profiles() -> Result<Vec<Profile>, AppError> {
+pub fn list_profiles(repo_path: Option<String>) -> Result<Vec<Profile>, AppError> {
+    let mut profiles = load_profiles()?;
+    if let Some(repo_path) = repo_path.or_else(default_repo_path) {
+        if has_dynamic_profiles(&profiles) {
+            let skills = scanner::scan_skills_repo(Some(repo_path))?;
+            resolve_profiles(&mut profiles, Some(&skills));
+        }
+    }
+    Ok(profiles)
+}
+
+/// Load all profiles, resolved without a catalog (dynamic queries stay pending)
+pub(crate) fn load_profiles() -> Result<Vec<Profile>, AppError> {
     // Load user profiles from disk first
     let mut user_profiles: std::collections::HashMap<String, Profile> = std::collections::HashMap::new();
     let dir = profiles_dir()?;
@@ -29,17 +182,25 @@ pub fn list_profiles() -> Result<Vec<Profile>, AppError> {
         for entry in fs::read_dir(&dir)? {
             let entry = entry?;
             if entry.path().extension().is_some_and(|e| e == "json") {
-                let content = fs::read_to_string(entry.path())?;
-                if let Ok(profile) = serde_json::from_str::<Profile>(&content) {
-                    user_profiles.insert(profile.id.clone(), profile);
+                match store::read_json::<Profile>(&entry.path(), &PROFILE_SCHEMA) {
+                    Ok(Some(profile)) => {
+                        user_profiles.insert(profile.id.clone(), profile);
+                    }
+                    Err(e @ AppError::UnsupportedSchemaVersion { .. }) => return Err(e),
+                    _ => {}
                 }
             }
         }
     }
 
+    // Overrides of hidden presets are hidden along with them
+    for id in hidden_presets()? {
+        user_profiles.remove(&id);
+    }
+
     // Build final list: for presets, use user override if exists
     let mut profiles: Vec<Profile> = Vec::new();
-    for preset in Profile::presets() {
+    for preset in load_presets()? {
         if let Some(user_version) = user_profiles.remove(&preset.id) {
             profiles.push(user_version);
         } else {
@@ -52,43 +213,294 @@ pub fn list_profiles() -> Result<Vec<Profile>, AppError> {
         profiles.push(profile);
     }
 
+    resolve_profiles(&mut profiles, None);
     Ok(profiles)
 }
 
-/// Create or update a profile
+/// Create or update a profile (only the declared form is stored)
 #[tauri::command]
-pub fn save_profile(profile: Profile) -> Result<Profile, AppError> {
+pub fn save_profile(mut profile: Profile) -> Result<Profile, AppError> {
+    let resolved = check_inheritance(&profile, &load_profiles()?)?;
+    profile.resolved = None;
+
     let path = profile_path(&profile.id)?;
-    let json = serde_json::to_string_pretty(&profile)?;
-    fs::write(&path, json)?;
+    // The replaced file, or the preset a first override replaces
+    let previous: Option<Profile> = match store::read_json(&path, &PROFILE_SCHEMA) {
+        Ok(Some(existing)) => Some(existing),
+        Ok(None) => load_presets()?.into_iter().find(|p| p.id == profile.id),
+        Err(_) => None,
+    };
+    store::write_json(&path, &PROFILE_SCHEMA, &profile)?;
+    record_version(previous.as_ref(), &profile)?;
+
+    profile.resolved = Some(resolved);
     Ok(profile)
 }
 
-/// Delete a user-created profile
+/// Whether a user profile file (created profile or preset override) exists
+pub(crate) fn user_profile_exists(id: &str) -> Result<bool, AppError> {
+    Ok(profile_path(id)?.exists())
+}
+
+/// Put back the file of a profile that was trashed without parsing
+pub(crate) fn restore_raw_profile(id: &str, content: &str) -> Result<(), AppError> {
+    fs::write(profile_path(id)?, content)?;
+    Ok(())
+}
+
+/// Delete a user-created profile (or a preset override), keeping it in the trash. A file
+/// that does not parse is trashed as its raw contents; one written by a newer version is
+/// left alone.
 #[tauri::command]
 pub fn delete_profile(id: String) -> Result<(), AppError> {
     let path = profile_path(&id)?;
-    if path.exists() {
-        fs::remove_file(&path)?;
+    match store::read_json::<Profile>(&path, &PROFILE_SCHEMA) {
+        Ok(Some(profile)) => move_to_trash(TrashKind::Profile, &profile.id, &profile.name, &profile)?,
+        Ok(None) => {}
+        Err(AppError::Json(_)) => move_to_trash(TrashKind::Profile, &id, &id, fs::read_to_string(&path)?)?,
+        Err(e) => return Err(e),
     }
-    Ok(())
+    store::remove(&path, &PROFILE_SCHEMA)
 }
 
 /// Get a single profile by ID
 #[tauri::command]
 pub fn get_profile(id: String) -> Result<Profile, AppError> {
     // Check presets first
-    if let Some(preset) = Profile::presets().into_iter().find(|p| p.id == id) {
-        return Ok(preset);
+    let mut profile = match load_presets()?.into_iter().find(|p| p.id == id) {
+        Some(preset) => preset,
+        None => {
+            // Check user profiles
+            store::read_json(&profile_path(&id)?, &PROFILE_SCHEMA)?
+                .ok_or_else(|| AppError::Custom(format!("Profile not found: {}", id)))?
+        }
+    };
+
+    profile.resolved = Some(resolve_profile(&profile, &load_profiles()?, None));
+    Ok(profile)
+}
+
+/// Map skills-directory entries back to scanned skills and build a profile from them.
+/// `points_to` decides whether a raw symlink target refers to a skill's source path.
+pub(crate) fn profile_from_link_entries(
+    name: String,
+    entries: &[LinkEntry],
+    skills: &[Skill],
+    points_to: impl Fn(&str, &Path) -> bool,
+) -> ProfileFromLinks {
+    let mut matched = Vec::new();
+    let mut unmatched = Vec::new();
+    let mut aliases = HashMap::new();
+
+    for entry in entries {
+        let reason = match (&entry.kind, &entry.target) {
+            (EntryKind::Directory, _) => "Real directory, not managed by a symlink",
+            (EntryKind::Symlink, _) if !entry.target_exists => "Broken link",
+            (EntryKind::Symlink, Some(target)) => {
+                match skills.iter().find(|s| points_to(target, &s.source_path)) {
+                    Some(skill) => {
+                        if !matched.iter().any(|(_, id): &(String, String)| id == &skill.id) {
+                            matched.push((entry.name.clone(), skill.id.clone()));
+                            if entry.name != skill.name {
+                                aliases.insert(skill.id.clone(), entry.name.clone());
+                            }
+                        }
+                        continue;
+                    }
+                    None => "Target is not a skill in the scanned repository",
+                }
+            }
+            (EntryKind::Symlink, None) => "Unreadable link",
+        };
+        unmatched.push(UnmatchedLink {
+            name: entry.name.clone(),
+            target: entry.target.clone(),
+            reason: reason.to_string(),
+        });
     }
 
-    // Check user profiles
-    let path = profile_path(&id)?;
-    if path.exists() {
-        let content = fs::read_to_string(&path)?;
-        let profile: Profile = serde_json::from_str(&content)?;
-        return Ok(profile);
+    matched.sort_by_key(|(link, _)| link.to_lowercase());
+    unmatched.sort_by_key(|u| u.name.to_lowercase());
+    let profile = Profile {
+        id: uuid::Uuid::new_v4().to_string(),
+        name,
+        description: format!("Created from {} existing links", matched.len()),
+        color: DEFAULT_PROFILE_COLOR.to_string(),
+        skill_ids: matched.iter().map(|(_, id)| id.clone()).collect(),
+        is_preset: false,
+        aliases,
+        extends: Vec::new(),
+        excludes: Vec::new(),
+        kind: ProfileKind::Static,
+        query: None,
+        conditions: HashMap::new(),
+        resolved: None,
+    };
+
+    ProfileFromLinks {
+        profile,
+        matched,
+        unmatched,
+    }
+}
+
+/// Turn the current user-level (or a project's) links into a new saved profile
+#[tauri::command]
+pub fn create_profile_from_links(
+    name: String,
+    repo_path: Option<String>,
+    project_path: Option<String>,
+) -> Result<ProfileFromLinks, AppError> {
+    let dir = match &project_path {
+        Some(p) => project_skills_dir(p),
+        None => user_skills_dir()?,
+    };
+    let skills = scanner::scan_skills_repo(repo_path)?;
+    let entries = read_link_entries(&dir)?;
+
+    let result = profile_from_link_entries(name, &entries, &skills, |target, source| {
+        match (dir.join(target).canonicalize(), source.canonicalize()) {
+            (Ok(a), Ok(b)) => a == b,
+            _ => false,
+        }
+    });
+    save_profile(result.profile.clone())?;
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::LinkStatus;
+
+    fn skill(id: &str) -> Skill {
+        Skill {
+            id: id.to_string(),
+            name: id.rsplit('/').next().unwrap_or(id).to_string(),
+            description: String::new(),
+            source_path: PathBuf::from(format!("/repo/{}", id)),
+            source_repo: "repo".to_string(),
+            category: None,
+            tags: Vec::new(),
+            has_scripts: false,
+            has_references: false,
+            link_status_user: LinkStatus::Inactive,
+            link_name_user: None,
+            dependencies: Vec::new(),
+            raw_content: String::new(),
+        }
     }
 
-    Err(AppError::Custom(format!("Profile not found: {}", id)))
+    fn link(name: &str, target: &str, target_exists: bool) -> LinkEntry {
+        LinkEntry {
+            name: name.to_string(),
+            kind: EntryKind::Symlink,
+            target: Some(target.to_string()),
+            target_exists,
+        }
+    }
+
+    fn profile(id: &str, extends: &[&str], skill_ids: &[&str]) -> Profile {
+        Profile {
+            id: id.to_string(),
+            name: id.to_string(),
+            description: String::new(),
+            color: String::new(),
+            skill_ids: skill_ids.iter().map(|s| s.to_string()).collect(),
+            is_preset: false,
+            aliases: HashMap::new(),
+            extends: extends.iter().map(|s| s.to_string()).collect(),
+            excludes: Vec::new(),
+            kind: ProfileKind::Static,
+            query: None,
+            conditions: HashMap::new(),
+            resolved: None,
+        }
+    }
+
+    fn skill_ids(resolved: &ResolvedProfile) -> Vec<(&str, &str)> {
+        resolved
+            .skills
+            .iter()
+            .map(|s| (s.skill_id.as_str(), s.from_profile_id.as_str()))
+            .collect()
+    }
+
+    fn points_to(target: &str, source: &Path) -> bool {
+        Path::new(target) == source
+    }
+
+    #[test]
+    fn links_become_skills_and_aliases() {
+        let skills = [skill("a/review"), skill("b/deploy")];
+        let entries = [
+            link("review", "/repo/a/review", true),
+            link("ship-it", "/repo/b/deploy", true),
+            link("deploy", "/repo/b/deploy", true),
+        ];
+
+        let result = profile_from_link_entries("Mine".to_string(), &entries, &skills, points_to);
+        assert_eq!(result.profile.skill_ids, vec!["a/review", "b/deploy"]);
+        assert_eq!(
+            result.matched,
+            vec![("review".to_string(), "a/review".to_string()), ("ship-it".to_string(), "b/deploy".to_string())]
+        );
+        assert_eq!(result.profile.aliases.get("b/deploy").map(String::as_str), Some("ship-it"));
+        assert!(result.unmatched.is_empty());
+    }
+
+    #[test]
+    fn unmatched_entries_say_why() {
+        let entries = [
+            LinkEntry {
+                name: "local".to_string(),
+                kind: EntryKind::Directory,
+                target: None,
+                target_exists: true,
+            },
+            link("broken", "/repo/gone", false),
+            link("other", "/elsewhere/x", true),
+        ];
+
+        let result = profile_from_link_entries("Mine".to_string(), &entries, &[skill("a/review")], points_to);
+        assert!(result.profile.skill_ids.is_empty());
+        let reasons: Vec<(&str, &str)> = result
+            .unmatched
+            .iter()
+            .map(|u| (u.name.as_str(), u.reason.as_str()))
+            .collect();
+        assert_eq!(
+            reasons,
+            vec![
+                ("broken", "Broken link"),
+                ("local", "Real directory, not managed by a symlink"),
+                ("other", "Target is not a skill in the scanned repository"),
+            ]
+        );
+    }
+
+    #[test]
+    fn parents_come_first_and_excludes_apply_to_inherited_skills() {
+        let base = profile("base", &[], &["lint", "format"]);
+        let mut web = profile("web", &["base", "gone"], &["react", "lint"]);
+        web.excludes = vec!["format".to_string()];
+
+        let resolved = resolve_profile(&web, &[base, web.clone()], None);
+        assert_eq!(skill_ids(&resolved), vec![("lint", "base"), ("react", "web")]);
+        assert_eq!(resolved.missing_parents, vec!["gone"]);
+        assert_eq!(resolved.cycle, None);
+    }
+
+    #[test]
+    fn inheritance_cycles_are_cut_and_reported() {
+        let profiles = [profile("a", &["b"], &["x"]), profile("b", &["c"], &["y"]), profile("c", &["a"], &["z"])];
+
+        let resolved = resolve_profile(&profiles[0], &profiles, None);
+        assert_eq!(skill_ids(&resolved), vec![("z", "c"), ("y", "b"), ("x", "a")]);
+        assert_eq!(resolved.cycle, Some(vec!["a".into(), "b".into(), "c".into(), "a".into()]));
+
+        let err = check_inheritance(&profiles[2], &profiles).unwrap_err();
+        assert!(err.to_string().contains("c -> a -> b -> c"), "{}", err);
+        assert!(check_inheritance(&profile("c", &[], &["z"]), &profiles).is_ok());
+    }
 }
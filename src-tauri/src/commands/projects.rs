@@ -1,31 +1,63 @@
+use crate::commands::trash::move_to_trash;
+use crate::commands::settings::{data_dir, load_settings};
 use crate::error::AppError;
-use crate::models::ProjectConfig;
-use std::fs;
-use std::path::PathBuf;
+use crate::models::{ProjectConfig, TrashKind};
+use crate::store::{self, Schema};
+use std::path::{Path, PathBuf};
+
+/// Schema of projects.json
+pub(crate) const PROJECTS_SCHEMA: Schema = Schema {
+    version: 1,
+    migrations: &[store::unversioned],
+};
 
 fn projects_path() -> Result<PathBuf, AppError> {
-    let dir = dirs::home_dir()
-        .map(|h| h.join(".claude-skill-manager"))
-        .ok_or(AppError::ConfigDirNotFound)?;
-    fs::create_dir_all(&dir)?;
+    let dir = data_dir()?;
     Ok(dir.join("projects.json"))
 }
 
-fn load_projects() -> Result<Vec<ProjectConfig>, AppError> {
-    let path = projects_path()?;
-    if !path.exists() {
-        return Ok(Vec::new());
+/// `~/...` as a path below the home directory
+fn expand_home(path: &Path) -> PathBuf {
+    match (path.strip_prefix("~"), dirs::home_dir()) {
+        (Ok(rest), Some(home)) => home.join(rest),
+        _ => path.to_path_buf(),
+    }
+}
+
+/// A path below the home directory as `~/...`
+fn collapse_home(path: &Path) -> PathBuf {
+    match dirs::home_dir().and_then(|home| path.strip_prefix(home).ok().map(Path::to_path_buf)) {
+        Some(rest) => Path::new("~").join(rest),
+        None => path.to_path_buf(),
+    }
+}
+
+pub(crate) fn load_projects() -> Result<Vec<ProjectConfig>, AppError> {
+    let mut projects: Vec<ProjectConfig> = store::read_json(&projects_path()?, &PROJECTS_SCHEMA)?.unwrap_or_default();
+    for project in projects.iter_mut() {
+        project.path = expand_home(&project.path);
     }
-    let content = fs::read_to_string(&path)?;
-    let projects: Vec<ProjectConfig> = serde_json::from_str(&content)?;
     Ok(projects)
 }
 
-fn save_projects(projects: &[ProjectConfig]) -> Result<(), AppError> {
-    let path = projects_path()?;
-    let json = serde_json::to_string_pretty(projects)?;
-    fs::write(&path, json)?;
-    Ok(())
+/// Change the registered projects under the config lock. While config sync is on, paths
+/// below the home directory are stored as `~/...` so projects.json is the same on every machine.
+pub(crate) fn update_projects<R>(
+    f: impl FnOnce(&mut Vec<ProjectConfig>) -> Result<R, AppError>,
+) -> Result<R, AppError> {
+    let portable = load_settings()?.sync.enabled;
+    store::update_json(&projects_path()?, &PROJECTS_SCHEMA, |projects: &mut Vec<ProjectConfig>| {
+        for project in projects.iter_mut() {
+            project.path = expand_home(&project.path);
+        }
+        let result = f(projects)?;
+        if portable {
+            for project in projects.iter_mut() {
+                project.path = collapse_home(&project.path);
+            }
+        }
+        Ok(result)
+    })
 }
 
 /// List all registered projects
@@ -37,23 +69,54 @@ pub fn list_projects() -> Result<Vec<ProjectConfig>, AppError> {
 /// Add or update a project
 #[tauri::command]
 pub fn save_project(project: ProjectConfig) -> Result<ProjectConfig, AppError> {
-    let mut projects = load_projects()?;
-
-    if let Some(existing) = projects.iter_mut().find(|p| p.id == project.id) {
-        *existing = project.clone();
-    } else {
-        projects.push(project.clone());
-    }
-
-    save_projects(&projects)?;
+    update_projects(|projects| {
+        if let Some(existing) = projects.iter_mut().find(|p| p.id == project.id) {
+            *existing = project.clone();
+        } else {
+            projects.push(project.clone());
+        }
+        Ok(())
+    })?;
     Ok(project)
 }
 
-/// Delete a project
+/// Delete a project, keeping it in the trash
 #[tauri::command]
 pub fn delete_project(id: String) -> Result<(), AppError> {
-    let mut projects = load_projects()?;
-    projects.retain(|p| p.id != id);
-    save_projects(&projects)?;
-    Ok(())
+    update_projects(|projects| {
+        if let Some(project) = projects.iter().find(|p| p.id == id) {
+            move_to_trash(TrashKind::Project, &project.id, &project.name, project)?;
+        }
+        projects.retain(|p| p.id != id);
+        Ok(())
+    })
+}
+
+/// Record which skill gets a link name claimed by several skills (None clears the choice)
+pub(crate) fn set_link_winner(project: &mut ProjectConfig, link_name: String, skill_id: Option<String>) {
+    match skill_id {
+        Some(id) => {
+            project.link_winners.insert(link_name, id);
+        }
+        None => {
+            project.link_winners.remove(&link_name);
+        }
+    }
+}
+
+/// Choose the skill that gets a conflicting link name in a project
+#[tauri::command]
+pub fn set_project_link_winner(
+    project_id: String,
+    link_name: String,
+    skill_id: Option<String>,
+) -> Result<ProjectConfig, AppError> {
+    update_projects(|projects| {
+        let project = projects
+            .iter_mut()
+            .find(|p| p.id == project_id)
+            .ok_or_else(|| AppError::Custom(format!("Project not found: {}", project_id)))?;
+        set_link_winner(project, link_name, skill_id);
+        Ok(project.clone())
+    })
 }
@@ -0,0 +1,172 @@
+use crate::commands::scanner;
+use crate::error::AppError;
+use crate::models::{FrontmatterMatch, ProfileQuery, Skill};
+use regex::Regex;
+
+/// Translate a path glob into an anchored regex: `**` spans directories, `*` and `?` do not
+pub(crate) fn glob_to_regex(glob: &str) -> Option<Regex> {
+    let mut pattern = String::from("^");
+    let mut chars = glob.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                // `**/` also matches zero directories
+                if chars.peek() == Some(&'/') {
+                    chars.next();
+                    pattern.push_str("(?:.*/)?");
+                } else {
+                    pattern.push_str(".*");
+                }
+            }
+            '*' => pattern.push_str("[^/]*"),
+            '?' => pattern.push_str("[^/]"),
+            _ => pattern.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    pattern.push('$');
+    Regex::new(&pattern).ok()
+}
+
+/// Frontmatter of a skill as a YAML mapping (null without one)
+fn frontmatter_value(skill: &Skill) -> serde_yaml::Value {
+    scanner::parse_frontmatter(&skill.raw_content)
+}
+
+fn yaml_scalar(value: &serde_yaml::Value) -> Option<String> {
+    match value {
+        serde_yaml::Value::String(s) => Some(s.clone()),
+        serde_yaml::Value::Bool(b) => Some(b.to_string()),
+        serde_yaml::Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+fn frontmatter_matches(frontmatter: &serde_yaml::Value, m: &FrontmatterMatch) -> bool {
+    let field = match frontmatter.get(m.field.as_str()) {
+        Some(v) => v,
+        None => return false,
+    };
+    let expected = match &m.value {
+        Some(v) => v,
+        None => return true,
+    };
+    match field {
+        serde_yaml::Value::Sequence(items) => items
+            .iter()
+            .filter_map(yaml_scalar)
+            .any(|item| &item == expected),
+        other => yaml_scalar(other).as_ref() == Some(expected),
+    }
+}
+
+/// Whether a skill matches any criterion of a query
+pub(crate) fn query_matches(query: &ProfileQuery, globs: &[Regex], skill: &Skill) -> bool {
+    if query.source_repos.contains(&skill.source_repo) {
+        return true;
+    }
+    if skill.category.as_ref().is_some_and(|c| query.categories.contains(c)) {
+        return true;
+    }
+    if skill.tags.iter().any(|t| query.tags.contains(t)) {
+        return true;
+    }
+    if globs.iter().any(|g| g.is_match(&skill.id)) {
+        return true;
+    }
+    if !query.frontmatter.is_empty() {
+        let frontmatter = frontmatter_value(skill);
+        return query
+            .frontmatter
+            .iter()
+            .any(|m| frontmatter_matches(&frontmatter, m));
+    }
+    false
+}
+
+/// IDs of the catalog skills a query selects, in catalog order
+pub(crate) fn evaluate_query(query: &ProfileQuery, skills: &[Skill]) -> Vec<String> {
+    let globs: Vec<Regex> = query.path_globs.iter().filter_map(|g| glob_to_regex(g)).collect();
+    skills
+        .iter()
+        .filter(|s| query_matches(query, &globs, s))
+        .map(|s| s.id.clone())
+        .collect()
+}
+
+/// Preview which skills a dynamic profile query selects in the current scan
+#[tauri::command]
+pub fn preview_profile_query(query: ProfileQuery, repo_path: Option<String>) -> Result<Vec<String>, AppError> {
+    let skills = scanner::scan_skills_repo(repo_path)?;
+    Ok(evaluate_query(&query, &skills))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::LinkStatus;
+    use std::path::PathBuf;
+
+    fn skill(id: &str, raw_content: &str) -> Skill {
+        Skill {
+            id: id.to_string(),
+            name: id.rsplit('/').next().unwrap_or(id).to_string(),
+            description: String::new(),
+            source_path: PathBuf::from(format!("/repo/{}", id)),
+            source_repo: id.split('/').next().unwrap_or(id).to_string(),
+            category: None,
+            tags: Vec::new(),
+            has_scripts: false,
+            has_references: false,
+            link_status_user: LinkStatus::Inactive,
+            link_name_user: None,
+            dependencies: Vec::new(),
+            raw_content: raw_content.to_string(),
+        }
+    }
+
+    fn frontmatter(field: &str, value: Option<&str>) -> FrontmatterMatch {
+        FrontmatterMatch {
+            field: field.to_string(),
+            value: value.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn globs_keep_single_stars_within_a_directory() {
+        let re = glob_to_regex("market/skills/*").unwrap();
+        assert!(re.is_match("market/skills/seo"));
+        assert!(!re.is_match("market/skills/seo/extra"));
+
+        let re = glob_to_regex("**/review?").unwrap();
+        assert!(re.is_match("review1"));
+        assert!(re.is_match("a/b/review2"));
+        assert!(!re.is_match("a/reviews/x"));
+        assert!(glob_to_regex("a.b+(c)").unwrap().is_match("a.b+(c)"));
+    }
+
+    #[test]
+    fn any_criterion_selects_a_skill() {
+        let skills = [
+            skill("market/seo", ""),
+            skill("tools/lint", "---\nname: lint\nlanguages: [rust, go]\n---\n"),
+            skill("tools/deploy", "---\nname: deploy\nstage: 2\n---\n"),
+            skill("tools/plain", "# No frontmatter"),
+        ];
+
+        let query = ProfileQuery {
+            source_repos: vec!["market".to_string()],
+            frontmatter: vec![frontmatter("languages", Some("go")), frontmatter("stage", Some("2"))],
+            ..Default::default()
+        };
+        assert_eq!(evaluate_query(&query, &skills), vec!["market/seo", "tools/lint", "tools/deploy"]);
+
+        let query = ProfileQuery {
+            path_globs: vec!["tools/p*".to_string()],
+            frontmatter: vec![frontmatter("stage", None)],
+            ..Default::default()
+        };
+        assert_eq!(evaluate_query(&query, &skills), vec!["tools/deploy", "tools/plain"]);
+        assert!(evaluate_query(&ProfileQuery::default(), &skills).is_empty());
+    }
+}
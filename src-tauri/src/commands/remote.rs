@@ -1,11 +1,38 @@
+use crate::commands::settings::{data_dir, load_settings};
 use crate::error::AppError;
 use crate::models::remote::{ConnectionStatus, RemoteServer};
-use crate::models::{LinkStatus, Profile, ProjectConfig, Skill};
-use crate::ssh::connection::{delete_keychain_password, save_keychain_password, SshPool};
-use crate::ssh::executor::{exec_command_checked, shell_escape};
+use crate::models::{LinkStatus, Profile, ProjectConfig, Skill, SkillFrontmatter, TrashKind};
+use crate::ssh::connection::{save_keychain_password, SshPool};
+use crate::ssh::executor::{exec_command_checked, shell_escape, shell_path};
+use crate::commands::drift::{
+    compute_drift, project_drift_report, select_projects, DriftKind, ProjectDriftReport, ProjectReconcileResult,
+    ReconcileSummary,
+};
+use crate::commands::linker::{EntryKind, LinkEntry};
+use crate::commands::presets::{hidden_presets, load_presets};
+use crate::commands::profile_history::{
+    diff_profiles, pick_version, plan_record, ProfileLinkDiff, ProfileVersionDiff, VERSION_SCHEMA,
+};
+use crate::commands::projects::{set_link_winner, PROJECTS_SCHEMA};
+use crate::commands::trash::move_to_trash;
+use crate::commands::profiles::{
+    check_inheritance, has_dynamic_profiles, profile_from_link_entries, resolve_profiles, ProfileFromLinks,
+    PROFILE_SCHEMA,
+};
+use crate::commands::conditions::{
+    link_entries, max_glob_depth, resolve_for_target, TargetContext, TargetHost, TargetResolution, SKIPPED_DIRS,
+};
+use crate::commands::resolver::{resolve_profile_links, resolve_project_links, DesiredLink};
 use crate::commands::scanner;
+use crate::commands::snapshots::{
+    diff_entries, new_snapshot, plan_restore, snapshot_entries, RestoreReport, SnapshotDiff, SNAPSHOT_SCHEMA,
+};
+use crate::models::{ProfileVersion, Snapshot, SnapshotEntry};
+use crate::store::{self, Schema};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use ssh2::Session;
 use std::collections::HashSet;
-use std::fs;
 use std::path::{Path, PathBuf};
 use tauri::State;
 
@@ -13,30 +40,25 @@ use tauri::State;
 // Config file helpers
 // ============================================================
 
+/// Schema of remotes.json
+pub(crate) const REMOTES_SCHEMA: Schema = Schema {
+    version: 1,
+    migrations: &[store::unversioned],
+};
+
 fn remotes_path() -> Result<PathBuf, AppError> {
-    dirs::home_dir()
-        .map(|h| h.join(".claude-skill-manager").join("remotes.json"))
-        .ok_or(AppError::ConfigDirNotFound)
+    Ok(data_dir()?.join("remotes.json"))
 }
 
-fn load_remotes() -> Result<Vec<RemoteServer>, AppError> {
-    let path = remotes_path()?;
-    if !path.exists() {
-        return Ok(Vec::new());
-    }
-    let data = fs::read_to_string(&path)?;
-    let servers: Vec<RemoteServer> = serde_json::from_str(&data)?;
-    Ok(servers)
+pub(crate) fn load_remotes() -> Result<Vec<RemoteServer>, AppError> {
+    Ok(store::read_json(&remotes_path()?, &REMOTES_SCHEMA)?.unwrap_or_default())
 }
 
-fn save_remotes(servers: &[RemoteServer]) -> Result<(), AppError> {
-    let path = remotes_path()?;
-    if let Some(parent) = path.parent() {
-        fs::create_dir_all(parent)?;
-    }
-    let data = serde_json::to_string_pretty(servers)?;
-    fs::write(&path, data)?;
-    Ok(())
+/// Change the configured servers under the config lock
+pub(crate) fn update_remotes<R>(
+    f: impl FnOnce(&mut Vec<RemoteServer>) -> Result<R, AppError>,
+) -> Result<R, AppError> {
+    store::update_json(&remotes_path()?, &REMOTES_SCHEMA, f)
 }
 
 fn find_server(servers: &[RemoteServer], id: &str) -> Result<RemoteServer, AppError> {
@@ -63,6 +85,93 @@ fn remote_skills_dir(server: &RemoteServer) -> String {
         .unwrap_or_else(|| "~/.claude/skills".to_string())
 }
 
+/// Contents of a remote file, empty if it does not exist
+fn cat_remote(session: &Session, path: &str) -> Result<String, AppError> {
+    exec_command_checked(session, &format!("cat {} 2>/dev/null || true", shell_path(path)))
+}
+
+/// Read a config file from the remote server, or None if it does not exist. A file that
+/// no longer parses is replaced by its backup when that one does; an older file is migrated
+/// in memory and rewritten with the current schema on the next save.
+fn read_remote_json<T: DeserializeOwned>(
+    session: &Session,
+    path: &str,
+    schema: &Schema,
+) -> Result<Option<T>, AppError> {
+    let output = cat_remote(session, path)?;
+    if output.trim().is_empty() {
+        return Ok(None);
+    }
+    match store::parse_json(Path::new(path), &output, schema) {
+        Err(e @ AppError::Json(_)) => {
+            let backup = format!("{}.bak", path);
+            match store::parse_json(Path::new(&backup), &cat_remote(session, &backup)?, schema) {
+                Ok(value) => {
+                    eprintln!("Reading backup of {}: {}", path, e);
+                    Ok(Some(value))
+                }
+                Err(_) => Err(e),
+            }
+        }
+        result => result.map(Some),
+    }
+}
+
+/// Write a config file on the remote server through a temporary file and a rename, keeping
+/// the previous version as backup and refusing to replace one written by a newer version
+fn write_remote_json<T: Serialize + ?Sized>(
+    session: &Session,
+    path: &str,
+    schema: &Schema,
+    value: &T,
+) -> Result<(), AppError> {
+    let current = cat_remote(session, path)?;
+    store::check_replaceable(Path::new(path), &current, schema)?;
+    let target = shell_path(path);
+    let tmp = shell_path(&format!("{}.tmp", path));
+    let mut script = vec![
+        "set -e".to_string(),
+        format!("mkdir -p \"$(dirname {})\"", target),
+        format!("cat > {} << 'SKILLPILOT_EOF'\n{}\nSKILLPILOT_EOF", tmp, store::envelope(schema, value)?),
+    ];
+    if serde_json::from_str::<serde_json::Value>(&current).is_ok() {
+        script.push(format!("cp {} {}", target, shell_path(&format!("{}.bak", path))));
+    }
+    script.push(format!("mv -f {} {}", tmp, target));
+    exec_command_checked(session, &script.join("\n"))?;
+    Ok(())
+}
+
+/// The remote config dir held against other clients for a read-modify-write, released on
+/// drop. A lock left behind by a dropped connection is taken over after about 10 seconds.
+struct RemoteLock<'a> {
+    session: &'a Session,
+    path: String,
+}
+
+impl<'a> RemoteLock<'a> {
+    fn acquire(session: &'a Session, config_dir: &str) -> Result<Self, AppError> {
+        let path = format!("{}/.lock.d", config_dir);
+        let lock = shell_path(&path);
+        let script = [
+            format!("mkdir -p {} || exit 1", shell_path(config_dir)),
+            "i=0".to_string(),
+            format!("until mkdir {} 2>/dev/null; do", lock),
+            format!("  i=$((i+1)); if [ $i -ge 10 ]; then rmdir {} 2>/dev/null || true; i=0; fi", lock),
+            "  sleep 1".to_string(),
+            "done".to_string(),
+        ];
+        exec_command_checked(session, &script.join("\n"))?;
+        Ok(RemoteLock { session, path })
+    }
+}
+
+impl Drop for RemoteLock<'_> {
+    fn drop(&mut self) {
+        let _ = exec_command_checked(self.session, &format!("rmdir {}", shell_path(&self.path)));
+    }
+}
+
 // ============================================================
 // Server configuration CRUD
 // ============================================================
@@ -76,25 +185,28 @@ pub fn list_remote_servers() -> Result<Vec<RemoteServer>, AppError> {
 /// Save (create or update) a remote server configuration
 #[tauri::command]
 pub fn save_remote_server(server: RemoteServer) -> Result<RemoteServer, AppError> {
-    let mut servers = load_remotes()?;
-    if let Some(existing) = servers.iter_mut().find(|s| s.id == server.id) {
-        *existing = server.clone();
-    } else {
-        servers.push(server.clone());
-    }
-    save_remotes(&servers)?;
+    update_remotes(|servers| {
+        if let Some(existing) = servers.iter_mut().find(|s| s.id == server.id) {
+            *existing = server.clone();
+        } else {
+            servers.push(server.clone());
+        }
+        Ok(())
+    })?;
     Ok(server)
 }
 
-/// Delete a remote server configuration
+/// Delete a remote server configuration, keeping it in the trash.
+/// The Keychain entry stays until the trash item is purged.
 #[tauri::command]
 pub fn delete_remote_server(id: String) -> Result<(), AppError> {
-    let mut servers = load_remotes()?;
-    servers.retain(|s| s.id != id);
-    save_remotes(&servers)?;
-    // Clean up Keychain entry
-    delete_keychain_password(&id);
-    Ok(())
+    update_remotes(|servers| {
+        if let Some(server) = servers.iter().find(|s| s.id == id) {
+            move_to_trash(TrashKind::RemoteServer, &server.id, &server.name, server)?;
+        }
+        servers.retain(|s| s.id != id);
+        Ok(())
+    })
 }
 
 /// Save SSH credential (password or passphrase) to macOS Keychain
@@ -151,9 +263,10 @@ pub fn remote_init_config(
 
     let cmd = format!(
         "mkdir -p {config}/profiles && mkdir -p {skills} && \
-         test -f {config}/projects.json || echo '[]' > {config}/projects.json",
-        config = shell_escape(&config_dir),
-        skills = shell_escape(&skills_dir),
+         test -f {config}/projects.json || echo {empty} > {config}/projects.json",
+        config = shell_path(&config_dir),
+        skills = shell_path(&skills_dir),
+        empty = shell_escape(&store::envelope(&PROJECTS_SCHEMA, &[] as &[ProjectConfig])?),
     );
 
     exec_command_checked(&session, &cmd)?;
@@ -173,9 +286,13 @@ pub fn remote_scan_skills_repo(
     let servers = load_remotes()?;
     let server = find_server(&servers, &server_id)?;
     let session = ssh_pool.get_or_connect(&server)?;
+    scan_remote_skills(&session, &server)
+}
 
+/// Scan the remote repository over an open session
+fn scan_remote_skills(session: &Session, server: &RemoteServer) -> Result<Vec<Skill>, AppError> {
     let repo_path = &server.remote_repo_path;
-    let skills_dir = remote_skills_dir(&server);
+    let skills_dir = remote_skills_dir(server);
 
     // 1. Batch find all SKILL.md files and their content in one command
     let find_cmd = format!(
@@ -186,26 +303,19 @@ pub fn remote_scan_skills_repo(
          -not -path '*/.idea/*' -not -path '*/target/*' \
          -not -path '*/.vscode/*' \
          -exec sh -c 'echo \"===SP_SEP===\" && echo \"PATH:$0\" && cat \"$0\"' {{}} \\;",
-        shell_escape(repo_path)
+        shell_path(repo_path)
     );
-    let output = exec_command_checked(&session, &find_cmd).unwrap_or_default();
+    let output = exec_command_checked(session, &find_cmd).unwrap_or_default();
 
-    // 2. Get user-level symlink info in one command
-    let links_cmd = format!(
-        "ls -la {} 2>/dev/null || true",
-        shell_escape(&skills_dir)
-    );
-    let links_output = exec_command_checked(&session, &links_cmd).unwrap_or_default();
-
-    // Parse symlink info into a set of active skill names
-    let active_links: HashSet<String> = parse_ls_symlinks(&links_output);
+    // 2. Get user-level link entries in one command
+    let links = list_remote_link_entries(session, &skills_dir)?;
 
     // 3. Try to get .gitmodules content for submodule detection
     let gitmodules_cmd = format!(
         "cat {}/.gitmodules 2>/dev/null || true",
-        shell_escape(repo_path)
+        shell_path(repo_path)
     );
-    let gitmodules_content = exec_command_checked(&session, &gitmodules_cmd).unwrap_or_default();
+    let gitmodules_content = exec_command_checked(session, &gitmodules_cmd).unwrap_or_default();
 
     // Parse gitmodules content
     let submodules = parse_gitmodules_content(&gitmodules_content, Path::new(repo_path));
@@ -213,7 +323,6 @@ pub fn remote_scan_skills_repo(
     // 4. Parse the find output into Skills
     let repo_root = PathBuf::from(repo_path);
     let mut skills: Vec<Skill> = Vec::new();
-    let mut seen_names: HashSet<String> = HashSet::new();
 
     for block in output.split("===SP_SEP===") {
         let block = block.trim();
@@ -235,7 +344,7 @@ pub fn remote_scan_skills_repo(
             None => continue,
         };
 
-        let frontmatter = scanner::parse_frontmatter(&content);
+        let frontmatter: SkillFrontmatter = scanner::parse_frontmatter(&content);
 
         let dir_name = skill_dir
             .file_name()
@@ -244,11 +353,6 @@ pub fn remote_scan_skills_repo(
 
         let name = frontmatter.name.unwrap_or_else(|| dir_name.clone());
 
-        if seen_names.contains(&name) {
-            continue;
-        }
-        seen_names.insert(name.clone());
-
         let id = skill_dir
             .strip_prefix(&repo_root)
             .map(|r| r.to_string_lossy().to_string())
@@ -274,13 +378,6 @@ pub fn remote_scan_skills_repo(
         let has_scripts = false;
         let has_references = false;
 
-        // Link status from the pre-fetched symlink data
-        let link_status_user = if active_links.contains(&name) {
-            LinkStatus::Active
-        } else {
-            LinkStatus::Inactive
-        };
-
         skills.push(Skill {
             id,
             name,
@@ -291,13 +388,16 @@ pub fn remote_scan_skills_repo(
             tags: frontmatter.tags,
             has_scripts,
             has_references,
-            link_status_user,
+            link_status_user: LinkStatus::Inactive,
+            link_name_user: None,
             dependencies,
             raw_content: content,
         });
     }
 
-    skills.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+    scanner::sort_skills(&mut skills);
+    // Link status from the pre-fetched link entries
+    scanner::assign_user_link_statuses(&mut skills, &links, remote_points_to(&remote_home(session)?));
     Ok(skills)
 }
 
@@ -322,7 +422,7 @@ pub fn remote_toggle_skill_user_level(
     if currently_active {
         let cmd = format!(
             "rm -f {}/{}",
-            shell_escape(&skills_dir),
+            shell_path(&skills_dir),
             shell_escape(&skill_name)
         );
         exec_command_checked(&session, &cmd)?;
@@ -330,9 +430,9 @@ pub fn remote_toggle_skill_user_level(
     } else {
         let cmd = format!(
             "mkdir -p {} && ln -sf {} {}/{}",
-            shell_escape(&skills_dir),
-            shell_escape(&source_path),
-            shell_escape(&skills_dir),
+            shell_path(&skills_dir),
+            shell_path(&source_path),
+            shell_path(&skills_dir),
             shell_escape(&skill_name)
         );
         exec_command_checked(&session, &cmd)?;
@@ -358,7 +458,7 @@ pub fn remote_toggle_skill_project_level(
     if currently_active {
         let cmd = format!(
             "rm -f {}/{}",
-            shell_escape(&target_dir),
+            shell_path(&target_dir),
             shell_escape(&skill_name)
         );
         exec_command_checked(&session, &cmd)?;
@@ -366,9 +466,9 @@ pub fn remote_toggle_skill_project_level(
     } else {
         let cmd = format!(
             "mkdir -p {} && ln -sf {} {}/{}",
-            shell_escape(&target_dir),
-            shell_escape(&source_path),
-            shell_escape(&target_dir),
+            shell_path(&target_dir),
+            shell_path(&source_path),
+            shell_path(&target_dir),
             shell_escape(&skill_name)
         );
         exec_command_checked(&session, &cmd)?;
@@ -392,19 +492,19 @@ pub fn remote_sync_project_links(
     let desired_names: HashSet<String> = skill_entries.iter().map(|(n, _)| n.clone()).collect();
 
     // Get current symlinks
-    let ls_cmd = format!("ls -la {} 2>/dev/null || true", shell_escape(&target_dir));
+    let ls_cmd = format!("ls -la {} 2>/dev/null || true", shell_path(&target_dir));
     let ls_output = exec_command_checked(&session, &ls_cmd).unwrap_or_default();
     let existing_links = parse_ls_symlinks(&ls_output);
 
     // Build batch command: remove stale + create desired
-    let mut cmds: Vec<String> = vec![format!("mkdir -p {}", shell_escape(&target_dir))];
+    let mut cmds: Vec<String> = vec![format!("mkdir -p {}", shell_path(&target_dir))];
 
     // Remove stale symlinks
     for link_name in &existing_links {
         if !desired_names.contains(link_name) {
             cmds.push(format!(
                 "rm -f {}/{}",
-                shell_escape(&target_dir),
+                shell_path(&target_dir),
                 shell_escape(link_name)
             ));
         }
@@ -414,8 +514,8 @@ pub fn remote_sync_project_links(
     for (name, source) in &skill_entries {
         cmds.push(format!(
             "ln -sf {} {}/{}",
-            shell_escape(source),
-            shell_escape(&target_dir),
+            shell_path(source),
+            shell_path(&target_dir),
             shell_escape(name)
         ));
     }
@@ -443,12 +543,12 @@ pub fn remote_apply_profile_links(
         None => remote_skills_dir(&server),
     };
 
-    let mut cmds: Vec<String> = vec![format!("mkdir -p {}", shell_escape(&dir))];
+    let mut cmds: Vec<String> = vec![format!("mkdir -p {}", shell_path(&dir))];
     for (name, source) in &skill_entries {
         cmds.push(format!(
             "ln -sf {} {}/{}",
-            shell_escape(source),
-            shell_escape(&dir),
+            shell_path(source),
+            shell_path(&dir),
             shell_escape(name)
         ));
     }
@@ -459,6 +559,50 @@ pub fn remote_apply_profile_links(
     Ok(skill_entries.iter().map(|(n, _)| n.clone()).collect())
 }
 
+/// Resolve a profile for the remote user-level skills directory (None) or a remote project,
+/// evaluating entry conditions and explaining the entries that were skipped
+#[tauri::command]
+pub fn remote_resolve_profile_for_target(
+    server_id: String,
+    profile_id: String,
+    target_path: Option<String>,
+    ssh_pool: State<SshPool>,
+) -> Result<TargetResolution, AppError> {
+    let servers = load_remotes()?;
+    let server = find_server(&servers, &server_id)?;
+    let session = ssh_pool.get_or_connect(&server)?;
+
+    let profiles = load_remote_profiles(&session, &server)?;
+    let profile = profiles
+        .iter()
+        .find(|p| p.id == profile_id)
+        .ok_or_else(|| AppError::Custom(format!("Profile not found: {}", profile_id)))?;
+    let skills = scan_remote_skills(&session, &server)?;
+    let projects = read_remote_projects(&session, &remote_config_dir(&server))?;
+    let host = RemoteHost::new(&session, &server);
+    let ctx = TargetContext::for_path(&host, target_path.as_deref(), &projects);
+    resolve_for_target(profile, &profiles, &skills, &ctx)
+}
+
+/// Links a remote project should have, with aliases, entry conditions and its link winners
+/// applied. Saving a project or profile syncs the project's links from this.
+#[tauri::command]
+pub fn remote_resolve_project_link_entries(
+    server_id: String,
+    project: ProjectConfig,
+    ssh_pool: State<SshPool>,
+) -> Result<Vec<(String, String)>, AppError> {
+    let servers = load_remotes()?;
+    let server = find_server(&servers, &server_id)?;
+    let session = ssh_pool.get_or_connect(&server)?;
+
+    let profiles = load_remote_profiles(&session, &server)?;
+    let skills = scan_remote_skills(&session, &server)?;
+    let host = RemoteHost::new(&session, &server);
+    let links = resolve_project_links(&project, &profiles, &skills, &TargetContext::project(&host, &project))?;
+    Ok(link_entries(links.desired))
+}
+
 /// Clean broken symlinks on the remote server
 #[tauri::command]
 pub fn remote_clean_broken_links(
@@ -478,7 +622,7 @@ pub fn remote_clean_broken_links(
     // Find broken symlinks
     let cmd = format!(
         "find {} -maxdepth 1 -type l ! -exec test -e {{}} \\; -print 2>/dev/null || true",
-        shell_escape(&dir)
+        shell_path(&dir)
     );
     let output = exec_command_checked(&session, &cmd).unwrap_or_default();
 
@@ -490,7 +634,7 @@ pub fn remote_clean_broken_links(
         }
         if let Some(name) = Path::new(line).file_name() {
             let name_str = name.to_string_lossy().to_string();
-            let rm_cmd = format!("rm -f {}", shell_escape(line));
+            let rm_cmd = format!("rm -f {}", shell_path(line));
             if exec_command_checked(&session, &rm_cmd).is_ok() {
                 cleaned.push(name_str);
             }
@@ -514,7 +658,7 @@ pub fn remote_get_project_skill_links(
 
     let cmd = format!(
         "ls -la {} 2>/dev/null || true",
-        shell_escape(&target_dir)
+        shell_path(&target_dir)
     );
     let output = exec_command_checked(&session, &cmd).unwrap_or_default();
 
@@ -530,7 +674,7 @@ pub fn remote_get_project_skill_links(
                 // Check if target exists
                 let check_cmd = format!(
                     "test -e {}/{} && echo 'exists' || echo 'broken'",
-                    shell_escape(&target_dir),
+                    shell_path(&target_dir),
                     shell_escape(&name)
                 );
                 let status_out = exec_command_checked(&session, &check_cmd).unwrap_or_default();
@@ -563,7 +707,7 @@ pub fn remote_get_user_skill_links(
 
     let cmd = format!(
         "ls -la {} 2>/dev/null || true",
-        shell_escape(&skills_dir)
+        shell_path(&skills_dir)
     );
     let output = exec_command_checked(&session, &cmd).unwrap_or_default();
 
@@ -576,7 +720,7 @@ pub fn remote_get_user_skill_links(
         if let Some((name, target, true)) = parse_ls_entry(line) {
             let check_cmd = format!(
                 "test -e {}/{} && echo 'valid' || echo 'invalid'",
-                shell_escape(&skills_dir),
+                shell_path(&skills_dir),
                 shell_escape(&name)
             );
             let status_out = exec_command_checked(&session, &check_cmd).unwrap_or_default();
@@ -601,14 +745,26 @@ pub fn remote_list_profiles(
     let servers = load_remotes()?;
     let server = find_server(&servers, &server_id)?;
     let session = ssh_pool.get_or_connect(&server)?;
-    let config_dir = remote_config_dir(&server);
+    let mut profiles = load_remote_profiles(&session, &server)?;
+
+    // Dynamic profiles are evaluated against a fresh scan of the remote repository
+    if has_dynamic_profiles(&profiles) {
+        let skills = scan_remote_skills(&session, &server)?;
+        resolve_profiles(&mut profiles, Some(&skills));
+    }
+    Ok(profiles)
+}
+
+/// Read remote profiles merged with presets over an open session
+fn load_remote_profiles(session: &Session, server: &RemoteServer) -> Result<Vec<Profile>, AppError> {
+    let config_dir = remote_config_dir(server);
 
     // Read all profile JSON files
     let cmd = format!(
         "for f in {}/profiles/*.json; do [ -f \"$f\" ] && echo '===PROFILE_SEP===' && cat \"$f\"; done 2>/dev/null || true",
-        shell_escape(&config_dir)
+        shell_path(&config_dir)
     );
-    let output = exec_command_checked(&session, &cmd).unwrap_or_default();
+    let output = exec_command_checked(session, &cmd).unwrap_or_default();
 
     // Parse user profiles
     let mut user_profiles: Vec<Profile> = Vec::new();
@@ -617,29 +773,33 @@ pub fn remote_list_profiles(
         if block.is_empty() {
             continue;
         }
-        if let Ok(profile) = serde_json::from_str::<Profile>(block) {
+        if let Ok(profile) = store::parse_json::<Profile>(Path::new(&config_dir), block, &PROFILE_SCHEMA) {
             user_profiles.push(profile);
         }
     }
 
     // Merge with presets (same logic as local profiles.rs)
     let mut result: Vec<Profile> = Vec::new();
+    let presets = load_presets()?;
+    let hidden = hidden_presets()?;
+    user_profiles.retain(|p| !hidden.contains(&p.id));
 
-    for preset in Profile::presets() {
+    for preset in &presets {
         if let Some(user_p) = user_profiles.iter().find(|p| p.id == preset.id) {
             result.push(user_p.clone());
         } else {
-            result.push(preset);
+            result.push(preset.clone());
         }
     }
 
     // Add non-preset user profiles
     for p in &user_profiles {
-        if !Profile::presets().iter().any(|preset| preset.id == p.id) {
+        if !presets.iter().any(|preset| preset.id == p.id) {
             result.push(p.clone());
         }
     }
 
+    resolve_profiles(&mut result, None);
     Ok(result)
 }
 
@@ -653,19 +813,55 @@ pub fn remote_save_profile(
     let servers = load_remotes()?;
     let server = find_server(&servers, &server_id)?;
     let session = ssh_pool.get_or_connect(&server)?;
-    let config_dir = remote_config_dir(&server);
 
-    let json = serde_json::to_string_pretty(&profile)?;
-    let cmd = format!(
-        "mkdir -p {}/profiles && cat > {}/profiles/{}.json << 'SKILLPILOT_EOF'\n{}\nSKILLPILOT_EOF",
-        shell_escape(&config_dir),
-        shell_escape(&config_dir),
-        shell_escape(&profile.id),
-        json
-    );
-    exec_command_checked(&session, &cmd)?;
+    let resolved = check_inheritance(&profile, &load_remote_profiles(&session, &server)?)?;
+    write_remote_profile(&session, &server, &profile)?;
+
+    Ok(Profile {
+        resolved: Some(resolved),
+        ..profile
+    })
+}
+
+/// Write a profile JSON file to the remote config dir (declared form only)
+fn write_remote_profile(session: &Session, server: &RemoteServer, profile: &Profile) -> Result<(), AppError> {
+    let config_dir = remote_config_dir(server);
+    let _lock = RemoteLock::acquire(session, &config_dir)?;
+
+    let declared = Profile {
+        resolved: None,
+        ..profile.clone()
+    };
+    let path = format!("{}/profiles/{}.json", config_dir, profile.id);
+    let previous = match read_remote_json::<Profile>(session, &path, &PROFILE_SCHEMA) {
+        Ok(Some(p)) => Some(p),
+        Err(e @ AppError::UnsupportedSchemaVersion { .. }) => return Err(e),
+        Ok(None) | Err(_) => load_presets()?.into_iter().find(|p| p.id == profile.id),
+    };
+
+    write_remote_json(session, &path, &PROFILE_SCHEMA, &declared)?;
+    record_remote_version(session, &config_dir, previous.as_ref(), &declared)
+}
+
+/// Turn the remote user-level (or a remote project's) links into a new saved profile
+#[tauri::command]
+pub fn remote_create_profile_from_links(
+    server_id: String,
+    name: String,
+    project_path: Option<String>,
+    ssh_pool: State<SshPool>,
+) -> Result<ProfileFromLinks, AppError> {
+    let servers = load_remotes()?;
+    let server = find_server(&servers, &server_id)?;
+    let session = ssh_pool.get_or_connect(&server)?;
+
+    let skills = scan_remote_skills(&session, &server)?;
+    let entries = list_remote_link_entries(&session, &remote_target_dir(&server, &project_path))?;
 
-    Ok(profile)
+    let home = remote_home(&session)?;
+    let result = profile_from_link_entries(name, &entries, &skills, remote_points_to(&home));
+    write_remote_profile(&session, &server, &result.profile)?;
+    Ok(result)
 }
 
 /// Delete a profile from the remote server
@@ -682,7 +878,7 @@ pub fn remote_delete_profile(
 
     let cmd = format!(
         "rm -f {}/profiles/{}.json",
-        shell_escape(&config_dir),
+        shell_path(&config_dir),
         shell_escape(&id)
     );
     exec_command_checked(&session, &cmd)?;
@@ -690,6 +886,159 @@ pub fn remote_delete_profile(
     Ok(())
 }
 
+// ============================================================
+// Remote Profile History
+// ============================================================
+
+fn remote_history_dir(config_dir: &str, profile_id: &str) -> String {
+    format!("{}/profile-history/{}", shell_path(config_dir), shell_escape(profile_id))
+}
+
+/// Read the recorded versions of a remote profile, oldest first
+fn load_remote_versions(session: &Session, config_dir: &str, profile_id: &str) -> Result<Vec<ProfileVersion>, AppError> {
+    let cmd = format!(
+        "for f in {}/*.json; do [ -f \"$f\" ] && echo '===VERSION_SEP===' && cat \"$f\"; done 2>/dev/null || true",
+        remote_history_dir(config_dir, profile_id)
+    );
+    let output = exec_command_checked(session, &cmd)?;
+    let mut versions: Vec<ProfileVersion> = output
+        .split("===VERSION_SEP===")
+        .filter_map(|block| store::parse_json(Path::new(config_dir), block.trim(), &VERSION_SCHEMA).ok())
+        .collect();
+    versions.sort_by_key(|v| v.version);
+    Ok(versions)
+}
+
+/// Record a saved remote profile in its history
+fn record_remote_version(
+    session: &Session,
+    config_dir: &str,
+    previous: Option<&Profile>,
+    profile: &Profile,
+) -> Result<(), AppError> {
+    let max_versions = load_settings()?.thresholds.max_profile_versions;
+    let (added, pruned) = plan_record(
+        &load_remote_versions(session, config_dir, &profile.id)?,
+        previous,
+        profile,
+        max_versions,
+    );
+    let dir = remote_history_dir(config_dir, &profile.id);
+    let mut script = vec!["set -e".to_string(), format!("mkdir -p {}", dir)];
+    for version in added {
+        script.push(format!(
+            "cat > {}/{}.json << 'SKILLPILOT_EOF'\n{}\nSKILLPILOT_EOF",
+            dir,
+            version.version,
+            store::envelope(&VERSION_SCHEMA, &version)?
+        ));
+    }
+    for n in pruned {
+        script.push(format!("rm -f {}/{}.json", dir, n));
+    }
+    exec_command_checked(session, &script.join("\n"))?;
+    Ok(())
+}
+
+/// List the saved versions of a remote profile, newest first
+#[tauri::command]
+pub fn remote_list_profile_versions(
+    server_id: String,
+    profile_id: String,
+    ssh_pool: State<SshPool>,
+) -> Result<Vec<ProfileVersion>, AppError> {
+    let servers = load_remotes()?;
+    let server = find_server(&servers, &server_id)?;
+    let session = ssh_pool.get_or_connect(&server)?;
+
+    let mut versions = load_remote_versions(&session, &remote_config_dir(&server), &profile_id)?;
+    versions.reverse();
+    Ok(versions)
+}
+
+/// Compare two versions of a remote profile (None = the current profile)
+#[tauri::command]
+pub fn remote_diff_profile_versions(
+    server_id: String,
+    profile_id: String,
+    from_version: Option<u32>,
+    to_version: Option<u32>,
+    ssh_pool: State<SshPool>,
+) -> Result<ProfileVersionDiff, AppError> {
+    let servers = load_remotes()?;
+    let server = find_server(&servers, &server_id)?;
+    let session = ssh_pool.get_or_connect(&server)?;
+
+    let versions = load_remote_versions(&session, &remote_config_dir(&server), &profile_id)?;
+    let profiles = load_remote_profiles(&session, &server)?;
+    let current = profiles.iter().find(|p| p.id == profile_id);
+    let from = pick_version(&versions, current, &profile_id, from_version)?;
+    let to = pick_version(&versions, current, &profile_id, to_version)?;
+    Ok(diff_profiles(&from, &to, from_version, to_version))
+}
+
+/// Compare a remote profile (or one of its versions) with the live links of the remote
+/// user-level skills directory or a remote project's
+#[tauri::command]
+pub fn remote_diff_profile_with_links(
+    server_id: String,
+    profile_id: String,
+    version: Option<u32>,
+    project_path: Option<String>,
+    ssh_pool: State<SshPool>,
+) -> Result<ProfileLinkDiff, AppError> {
+    let servers = load_remotes()?;
+    let server = find_server(&servers, &server_id)?;
+    let session = ssh_pool.get_or_connect(&server)?;
+
+    let versions = load_remote_versions(&session, &remote_config_dir(&server), &profile_id)?;
+    let profiles = load_remote_profiles(&session, &server)?;
+    let current = profiles.iter().find(|p| p.id == profile_id);
+    let profile = pick_version(&versions, current, &profile_id, version)?;
+
+    let skills = scan_remote_skills(&session, &server)?;
+    let host = RemoteHost::new(&session, &server);
+    let projects = read_remote_projects(&session, &remote_config_dir(&server))?;
+    let links = resolve_profile_links(
+        &profile,
+        &profiles,
+        &skills,
+        &TargetContext::for_path(&host, project_path.as_deref(), &projects),
+    )?;
+    let actual = list_remote_link_entries(&session, &remote_target_dir(&server, &project_path))?;
+    Ok(ProfileLinkDiff {
+        profile_id,
+        version,
+        target_path: project_path,
+        items: compute_drift(&links.desired, &actual, remote_points_to(&remote_home(&session)?)),
+        unresolved_skill_ids: links.unresolved,
+        skipped_entries: links.skipped,
+    })
+}
+
+/// Restore an earlier version of a remote profile; the restore itself becomes a new version
+#[tauri::command]
+pub fn remote_restore_profile_version(
+    server_id: String,
+    profile_id: String,
+    version: u32,
+    ssh_pool: State<SshPool>,
+) -> Result<Profile, AppError> {
+    let servers = load_remotes()?;
+    let server = find_server(&servers, &server_id)?;
+    let session = ssh_pool.get_or_connect(&server)?;
+
+    let versions = load_remote_versions(&session, &remote_config_dir(&server), &profile_id)?;
+    let profile = pick_version(&versions, None, &profile_id, Some(version))?;
+    let resolved = check_inheritance(&profile, &load_remote_profiles(&session, &server)?)?;
+    write_remote_profile(&session, &server, &profile)?;
+
+    Ok(Profile {
+        resolved: Some(resolved),
+        ..profile
+    })
+}
+
 // ============================================================
 // Remote Projects
 // ============================================================
@@ -703,16 +1052,8 @@ pub fn remote_list_projects(
     let servers = load_remotes()?;
     let server = find_server(&servers, &server_id)?;
     let session = ssh_pool.get_or_connect(&server)?;
-    let config_dir = remote_config_dir(&server);
-
-    let cmd = format!(
-        "cat {}/projects.json 2>/dev/null || echo '[]'",
-        shell_escape(&config_dir)
-    );
-    let output = exec_command_checked(&session, &cmd)?;
-    let projects: Vec<ProjectConfig> = serde_json::from_str(output.trim())?;
 
-    Ok(projects)
+    read_remote_projects(&session, &remote_config_dir(&server))
 }
 
 /// Save a project to the remote server
@@ -727,13 +1068,8 @@ pub fn remote_save_project(
     let session = ssh_pool.get_or_connect(&server)?;
     let config_dir = remote_config_dir(&server);
 
-    // Read existing projects
-    let read_cmd = format!(
-        "cat {}/projects.json 2>/dev/null || echo '[]'",
-        shell_escape(&config_dir)
-    );
-    let output = exec_command_checked(&session, &read_cmd)?;
-    let mut projects: Vec<ProjectConfig> = serde_json::from_str(output.trim())?;
+    let _lock = RemoteLock::acquire(&session, &config_dir)?;
+    let mut projects = read_remote_projects(&session, &config_dir)?;
 
     // Update or add
     if let Some(existing) = projects.iter_mut().find(|p| p.id == project.id) {
@@ -742,18 +1078,39 @@ pub fn remote_save_project(
         projects.push(project.clone());
     }
 
-    // Write back
-    let json = serde_json::to_string_pretty(&projects)?;
-    let write_cmd = format!(
-        "cat > {}/projects.json << 'SKILLPILOT_EOF'\n{}\nSKILLPILOT_EOF",
-        shell_escape(&config_dir),
-        json
-    );
-    exec_command_checked(&session, &write_cmd)?;
+    write_remote_projects(&session, &config_dir, &projects)?;
 
     Ok(project)
 }
 
+/// Choose the skill that gets a conflicting link name in a remote project
+#[tauri::command]
+pub fn remote_set_project_link_winner(
+    server_id: String,
+    project_id: String,
+    link_name: String,
+    skill_id: Option<String>,
+    ssh_pool: State<SshPool>,
+) -> Result<ProjectConfig, AppError> {
+    let servers = load_remotes()?;
+    let server = find_server(&servers, &server_id)?;
+    let session = ssh_pool.get_or_connect(&server)?;
+    let config_dir = remote_config_dir(&server);
+
+    let _lock = RemoteLock::acquire(&session, &config_dir)?;
+    let mut projects = read_remote_projects(&session, &config_dir)?;
+    let project = projects
+        .iter_mut()
+        .find(|p| p.id == project_id)
+        .ok_or_else(|| AppError::Custom(format!("Project not found: {}", project_id)))?;
+    set_link_winner(project, link_name, skill_id);
+    let updated = project.clone();
+
+    write_remote_projects(&session, &config_dir, &projects)?;
+
+    Ok(updated)
+}
+
 /// Delete a project from the remote server
 #[tauri::command]
 pub fn remote_delete_project(
@@ -766,28 +1123,433 @@ pub fn remote_delete_project(
     let session = ssh_pool.get_or_connect(&server)?;
     let config_dir = remote_config_dir(&server);
 
-    // Read existing projects
-    let read_cmd = format!(
-        "cat {}/projects.json 2>/dev/null || echo '[]'",
-        shell_escape(&config_dir)
+    let _lock = RemoteLock::acquire(&session, &config_dir)?;
+    let mut projects = read_remote_projects(&session, &config_dir)?;
+    projects.retain(|p| p.id != id);
+
+    write_remote_projects(&session, &config_dir, &projects)?;
+
+    Ok(())
+}
+
+// ============================================================
+// Remote Drift
+// ============================================================
+
+/// A remote server as a target host for profile entry conditions
+struct RemoteHost<'a> {
+    session: &'a Session,
+    server_id: String,
+}
+
+impl<'a> RemoteHost<'a> {
+    fn new(session: &'a Session, server: &RemoteServer) -> Self {
+        RemoteHost {
+            session,
+            server_id: server.id.clone(),
+        }
+    }
+}
+
+impl TargetHost for RemoteHost<'_> {
+    fn os(&self) -> Result<String, AppError> {
+        let uname = exec_command_checked(self.session, "uname -s")?;
+        Ok(match uname.trim().to_lowercase().as_str() {
+            "darwin" => "macos".to_string(),
+            other => other.to_string(),
+        })
+    }
+
+    fn server_id(&self) -> Option<&str> {
+        Some(&self.server_id)
+    }
+
+    fn file_exists(&self, path: &str) -> Result<bool, AppError> {
+        let cmd = format!("test -e {} && echo yes || true", shell_path(path));
+        Ok(exec_command_checked(self.session, &cmd)?.trim() == "yes")
+    }
+
+    fn list_files(&self, root: &str) -> Result<Vec<String>, AppError> {
+        let prune: Vec<String> = SKIPPED_DIRS.iter().map(|d| format!("-name {}", shell_escape(d))).collect();
+        let cmd = format!(
+            "cd {} 2>/dev/null || exit 0; find . -maxdepth {} \\( {} \\) -prune -o -print",
+            shell_path(root),
+            max_glob_depth(),
+            prune.join(" -o ")
+        );
+        Ok(exec_command_checked(self.session, &cmd)?
+            .lines()
+            .filter_map(|l| l.strip_prefix("./"))
+            .map(|l| l.to_string())
+            .collect())
+    }
+}
+
+/// Read a remote project's skills directory entries in one command
+fn list_remote_link_entries(session: &Session, dir: &str) -> Result<Vec<LinkEntry>, AppError> {
+    let cmd = format!(
+        "cd {} 2>/dev/null || exit 0; for f in * .[!.]*; do \
+         if [ -L \"$f\" ]; then \
+           if [ -e \"$f\" ]; then s=ok; else s=broken; fi; \
+           printf 'L\\t%s\\t%s\\t%s\\n' \"$f\" \"$(readlink \"$f\")\" \"$s\"; \
+         elif [ -d \"$f\" ]; then printf 'D\\t%s\\t\\tok\\n' \"$f\"; fi; \
+         done",
+        shell_path(dir)
     );
-    let output = exec_command_checked(&session, &read_cmd)?;
-    let mut projects: Vec<ProjectConfig> = serde_json::from_str(output.trim())?;
+    let output = exec_command_checked(session, &cmd)?;
 
-    projects.retain(|p| p.id != id);
+    let mut entries = Vec::new();
+    for line in output.lines() {
+        let parts: Vec<&str> = line.splitn(4, '\t').collect();
+        if parts.len() < 4 {
+            continue;
+        }
+        let kind = match parts[0] {
+            "L" => EntryKind::Symlink,
+            "D" => EntryKind::Directory,
+            _ => continue,
+        };
+        entries.push(LinkEntry {
+            name: parts[1].to_string(),
+            target: (kind == EntryKind::Symlink).then(|| parts[2].to_string()),
+            kind,
+            target_exists: parts[3] == "ok",
+        });
+    }
+    Ok(entries)
+}
+
+/// Read projects.json from the remote config dir
+fn read_remote_projects(session: &Session, config_dir: &str) -> Result<Vec<ProjectConfig>, AppError> {
+    let path = format!("{}/projects.json", config_dir);
+    Ok(read_remote_json(session, &path, &PROJECTS_SCHEMA)?.unwrap_or_default())
+}
+
+/// Write projects.json to the remote config dir
+fn write_remote_projects(session: &Session, config_dir: &str, projects: &[ProjectConfig]) -> Result<(), AppError> {
+    write_remote_json(session, &format!("{}/projects.json", config_dir), &PROJECTS_SCHEMA, projects)
+}
+
+/// Home directory of the remote user
+fn remote_home(session: &Session) -> Result<String, AppError> {
+    Ok(exec_command_checked(session, "printf '%s' \"$HOME\"")?.trim().to_string())
+}
+
+/// Expand a leading `~` the way the remote shell does
+fn expand_remote_home(path: &str, home: &str) -> String {
+    match path.strip_prefix('~') {
+        Some(rest) if rest.is_empty() || rest.starts_with('/') => format!("{}{}", home, rest),
+        _ => path.to_string(),
+    }
+}
+
+/// Remote target comparison: ln -s stores the source path verbatim, so `~` (expanded when
+/// linking, kept in configured paths) is resolved against the remote home on both sides
+fn remote_points_to(home: &str) -> impl Fn(&str, &Path) -> bool + '_ {
+    move |target, source| {
+        let source = expand_remote_home(&source.to_string_lossy(), home);
+        expand_remote_home(target, home).trim_end_matches('/') == source.trim_end_matches('/')
+    }
+}
+
+/// Compute drift for the selected remote projects, keeping desired links for reconcile
+fn remote_drift(
+    session: &Session,
+    server: &RemoteServer,
+    project_id: Option<String>,
+) -> Result<Vec<(ProjectDriftReport, Vec<DesiredLink>)>, AppError> {
+    let skills = scan_remote_skills(session, server)?;
+    let profiles = load_remote_profiles(session, server)?;
+    let projects = select_projects(
+        read_remote_projects(session, &remote_config_dir(server))?,
+        project_id,
+    )?;
+
+    let host = RemoteHost::new(session, server);
+    let home = remote_home(session)?;
+    let mut reports = Vec::new();
+    for project in &projects {
+        let dir = format!("{}/.claude/skills", project.path.to_string_lossy());
+        let links = resolve_project_links(project, &profiles, &skills, &TargetContext::project(&host, project))?;
+        let actual = list_remote_link_entries(session, &dir)?;
+        let report = project_drift_report(project, &links, &actual, remote_points_to(&home));
+        reports.push((report, links.desired));
+    }
+    Ok(reports)
+}
+
+/// Report drift for one or all projects stored on the remote server
+#[tauri::command]
+pub fn remote_detect_project_drift(
+    server_id: String,
+    project_id: Option<String>,
+    ssh_pool: State<SshPool>,
+) -> Result<Vec<ProjectDriftReport>, AppError> {
+    let servers = load_remotes()?;
+    let server = find_server(&servers, &server_id)?;
+    let session = ssh_pool.get_or_connect(&server)?;
+
+    Ok(remote_drift(&session, &server, project_id)?
+        .into_iter()
+        .map(|(report, _)| report)
+        .collect())
+}
+
+/// Fix drift across every project stored on the remote server
+#[tauri::command]
+pub fn remote_reconcile_all_projects(
+    server_id: String,
+    ssh_pool: State<SshPool>,
+) -> Result<ReconcileSummary, AppError> {
+    let servers = load_remotes()?;
+    let server = find_server(&servers, &server_id)?;
+    let session = ssh_pool.get_or_connect(&server)?;
+
+    let mut summary = ReconcileSummary::default();
+    for (report, desired) in remote_drift(&session, &server, None)? {
+        let dir = format!("{}/.claude/skills", report.project_path);
+        let mut result = ProjectReconcileResult {
+            project_id: report.project_id.clone(),
+            project_name: report.project_name.clone(),
+            ..Default::default()
+        };
+
+        // (name, command, whether it creates a link)
+        let mut changes: Vec<(String, String, bool)> = Vec::new();
+        for item in &report.items {
+            let link = format!("{}/{}", shell_path(&dir), shell_escape(&item.name));
+            match item.kind {
+                DriftKind::ReplacedByDirectory => result.skipped.push(item.name.clone()),
+                DriftKind::Extra => changes.push((item.name.clone(), format!("rm -f {}", link), false)),
+                _ => {
+                    if let Some(want) = desired.iter().find(|d| d.name == item.name) {
+                        let cmd = format!("ln -sfn {} {}", shell_path(&want.source_path.to_string_lossy()), link);
+                        changes.push((item.name.clone(), cmd, true));
+                    }
+                }
+            }
+        }
+
+        if changes.is_empty() {
+            summary.push(result);
+            continue;
+        }
+
+        // Every change runs on its own; failures are reported per item as "index error"
+        let mut script = vec![format!("mkdir -p {} || exit 1", shell_path(&dir))];
+        for (i, (_, cmd, _)) in changes.iter().enumerate() {
+            script.push(format!(
+                "e=$({} 2>&1) || printf '{} %s\\n' \"$(printf '%s' \"$e\" | tr '\\n' ' ')\"",
+                cmd,
+                i
+            ));
+        }
+        let failures = match exec_command_checked(&session, &script.join("\n")) {
+            Ok(output) => parse_item_failures(&output),
+            Err(e) => (0..changes.len()).map(|i| (i, e.to_string())).collect(),
+        };
+        for (i, (name, _, creates)) in changes.into_iter().enumerate() {
+            match failures.iter().find(|(index, _)| *index == i) {
+                Some((_, error)) => result.failed.push((name, error.clone())),
+                None if creates => result.created.push(name),
+                None => result.removed.push(name),
+            }
+        }
+        summary.push(result);
+    }
+
+    Ok(summary)
+}
+
+/// (index, error) for each "index error" line of a reconcile script's output
+fn parse_item_failures(output: &str) -> Vec<(usize, String)> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (index, error) = line.split_once(' ')?;
+            Some((index.parse().ok()?, error.trim().to_string()))
+        })
+        .collect()
+}
+
+// ============================================================
+// Remote Snapshots
+// ============================================================
+
+/// Skills directory a snapshot refers to on the remote server
+fn remote_target_dir(server: &RemoteServer, target_path: &Option<String>) -> String {
+    match target_path {
+        Some(p) => format!("{}/.claude/skills", p),
+        None => remote_skills_dir(server),
+    }
+}
+
+/// Directory a remote snapshot belongs to: the one it was taken from, else resolved from its target
+fn remote_snapshot_dir(server: &RemoteServer, snapshot: &Snapshot) -> String {
+    snapshot
+        .skills_dir
+        .clone()
+        .unwrap_or_else(|| remote_target_dir(server, &snapshot.target_path))
+}
+
+fn read_remote_snapshot(session: &Session, config_dir: &str, id: &str) -> Result<Snapshot, AppError> {
+    store::check_file_id("snapshot", id)?;
+    let path = format!("{}/snapshots/{}.json", config_dir, id);
+    read_remote_json(session, &path, &SNAPSHOT_SCHEMA)?
+        .ok_or_else(|| AppError::Custom(format!("Snapshot not found: {}", id)))
+}
 
-    // Write back
-    let json = serde_json::to_string_pretty(&projects)?;
-    let write_cmd = format!(
-        "cat > {}/projects.json << 'SKILLPILOT_EOF'\n{}\nSKILLPILOT_EOF",
-        shell_escape(&config_dir),
-        json
+fn current_remote_entries(session: &Session, dir: &str) -> Result<Vec<SnapshotEntry>, AppError> {
+    Ok(snapshot_entries(&list_remote_link_entries(session, dir)?))
+}
+
+/// Shell script that rebuilds `dir` with the given links beside it and swaps it in,
+/// moving real entries across and rolling back if any step fails. A symlinked `dir`
+/// is resolved first so the directory it points to is swapped and the link kept.
+fn restore_script(dir: &str, links: &[SnapshotEntry]) -> String {
+    let mut steps: Vec<String> = vec![
+        "rm -rf \"$S\" \"$O\"".to_string(),
+        "mkdir -p \"$(dirname \"$D\")\" \"$S\"".to_string(),
+    ];
+    for link in links {
+        steps.push(format!(
+            "ln -s {} \"$S\"/{}",
+            shell_path(link.target.as_deref().unwrap_or_default()),
+            shell_escape(&link.name)
+        ));
+    }
+    steps.push(
+        "(if [ -d \"$D\" ]; then for f in \"$D\"/* \"$D\"/.[!.]*; do \
+         [ -L \"$f\" ] && continue; [ -e \"$f\" ] || continue; mv \"$f\" \"$S\"/ || exit 1; done; \
+         mv \"$D\" \"$O\"; fi)"
+            .to_string(),
     );
-    exec_command_checked(&session, &write_cmd)?;
+    steps.push("mv \"$S\" \"$D\"".to_string());
+    steps.push("rm -rf \"$O\"".to_string());
+
+    let rollback = "if [ ! -e \"$D\" ] && [ -d \"$O\" ]; then mv \"$O\" \"$D\"; fi; \
+         if [ -d \"$S\" ]; then for f in \"$S\"/* \"$S\"/.[!.]*; do \
+         [ -L \"$f\" ] && continue; [ -e \"$f\" ] && mv \"$f\" \"$D\"/; done; rm -rf \"$S\"; fi; exit 1";
+
+    format!(
+        "D={dir}; if [ -L \"$D\" ]; then D=\"$(cd \"$D\" && pwd -P)\" || exit 1; fi; S=\"$(dirname \"$D\")/.$(basename \"$D\").sp-restore\"; O=\"$(dirname \"$D\")/.$(basename \"$D\").sp-old\"; \
+         {{ {steps}; }} || {{ {rollback}; }}",
+        dir = shell_path(dir),
+        steps = steps.join(" && "),
+        rollback = rollback
+    )
+}
+
+/// Save the state of a remote skills directory as a named snapshot
+#[tauri::command]
+pub fn remote_create_snapshot(
+    server_id: String,
+    name: String,
+    target_path: Option<String>,
+    ssh_pool: State<SshPool>,
+) -> Result<Snapshot, AppError> {
+    let servers = load_remotes()?;
+    let server = find_server(&servers, &server_id)?;
+    let session = ssh_pool.get_or_connect(&server)?;
+    let config_dir = remote_config_dir(&server);
+
+    let dir = remote_target_dir(&server, &target_path);
+    let entries = list_remote_link_entries(&session, &dir)?;
+    let snapshot = new_snapshot(name, target_path, dir, &entries);
+
+    let path = format!("{}/snapshots/{}.json", config_dir, snapshot.id);
+    write_remote_json(&session, &path, &SNAPSHOT_SCHEMA, &snapshot)?;
+
+    Ok(snapshot)
+}
+
+/// List snapshots stored on the remote server, newest first
+#[tauri::command]
+pub fn remote_list_snapshots(
+    server_id: String,
+    ssh_pool: State<SshPool>,
+) -> Result<Vec<Snapshot>, AppError> {
+    let servers = load_remotes()?;
+    let server = find_server(&servers, &server_id)?;
+    let session = ssh_pool.get_or_connect(&server)?;
+    let config_dir = remote_config_dir(&server);
+
+    let cmd = format!(
+        "for f in {}/snapshots/*.json; do [ -f \"$f\" ] && echo '===SNAPSHOT_SEP===' && cat \"$f\"; done 2>/dev/null || true",
+        shell_path(&config_dir)
+    );
+    let output = exec_command_checked(&session, &cmd).unwrap_or_default();
+
+    let mut snapshots: Vec<Snapshot> = output
+        .split("===SNAPSHOT_SEP===")
+        .map(|b| b.trim())
+        .filter(|b| !b.is_empty())
+        .filter_map(|b| store::parse_json(Path::new(&config_dir), b, &SNAPSHOT_SCHEMA).ok())
+        .collect();
+    snapshots.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    Ok(snapshots)
+}
 
+/// Delete a snapshot from the remote server
+#[tauri::command]
+pub fn remote_delete_snapshot(
+    server_id: String,
+    id: String,
+    ssh_pool: State<SshPool>,
+) -> Result<(), AppError> {
+    store::check_file_id("snapshot", &id)?;
+    let servers = load_remotes()?;
+    let server = find_server(&servers, &server_id)?;
+    let session = ssh_pool.get_or_connect(&server)?;
+    let config_dir = remote_config_dir(&server);
+
+    let cmd = format!(
+        "rm -f {}/snapshots/{}.json",
+        shell_path(&config_dir),
+        shell_escape(&id)
+    );
+    exec_command_checked(&session, &cmd)?;
     Ok(())
 }
 
+/// Compare a remote snapshot with the current state of its directory
+#[tauri::command]
+pub fn remote_diff_snapshot(
+    server_id: String,
+    id: String,
+    ssh_pool: State<SshPool>,
+) -> Result<SnapshotDiff, AppError> {
+    let servers = load_remotes()?;
+    let server = find_server(&servers, &server_id)?;
+    let session = ssh_pool.get_or_connect(&server)?;
+
+    let snapshot = read_remote_snapshot(&session, &remote_config_dir(&server), &id)?;
+    let current = current_remote_entries(&session, &remote_snapshot_dir(&server, &snapshot))?;
+    Ok(diff_entries(&snapshot, &current))
+}
+
+/// Restore a remote skills directory to a snapshot.
+/// Builds the new directory beside the live one and swaps it in with `mv`, rolling back on failure.
+#[tauri::command]
+pub fn remote_restore_snapshot(
+    server_id: String,
+    id: String,
+    ssh_pool: State<SshPool>,
+) -> Result<RestoreReport, AppError> {
+    let servers = load_remotes()?;
+    let server = find_server(&servers, &server_id)?;
+    let session = ssh_pool.get_or_connect(&server)?;
+
+    let snapshot = read_remote_snapshot(&session, &remote_config_dir(&server), &id)?;
+    let dir = remote_snapshot_dir(&server, &snapshot);
+    let current = current_remote_entries(&session, &dir)?;
+    let (links, report) = plan_restore(&snapshot, &current);
+
+    exec_command_checked(&session, &restore_script(&dir, &links))?;
+
+    Ok(report)
+}
+
 // ============================================================
 // Remote Shell (file reading)
 // ============================================================
@@ -812,7 +1574,7 @@ pub fn remote_list_skill_files(
 
     let cmd = format!(
         "find {} -type f -not -name '.*' 2>/dev/null | sort || true",
-        shell_escape(&dir)
+        shell_path(&dir)
     );
     let output = exec_command_checked(&session, &cmd).unwrap_or_default();
 
@@ -850,7 +1612,7 @@ pub fn remote_read_file_content(
     let server = find_server(&servers, &server_id)?;
     let session = ssh_pool.get_or_connect(&server)?;
 
-    let cmd = format!("cat {}", shell_escape(&path));
+    let cmd = format!("cat {}", shell_path(&path));
     exec_command_checked(&session, &cmd)
 }
 
@@ -949,3 +1711,29 @@ fn parse_gitmodules_content(
 
     modules
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn points_to_expands_the_remote_home_on_both_sides() {
+        let points_to = remote_points_to("/home/me");
+        assert!(points_to("/home/me/skills/a", Path::new("~/skills/a/")));
+        assert!(points_to("~/skills/a", Path::new("/home/me/skills/a")));
+        assert!(!points_to("~other/skills/a", Path::new("/home/me/skills/a")));
+        assert!(!points_to("/home/me/skills/b", Path::new("~/skills/a")));
+    }
+
+    #[test]
+    fn item_failures_are_read_per_line() {
+        let output = "1 ln: failed to create symbolic link 'b': No such file \n3 \n";
+        assert_eq!(
+            parse_item_failures(output),
+            vec![
+                (1, "ln: failed to create symbolic link 'b': No such file".to_string()),
+                (3, String::new()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,321 @@
+use crate::commands::conditions::{SkippedEntry, TargetContext};
+use crate::commands::linker::validate_link_name;
+use crate::commands::profiles::resolve_profile;
+use crate::error::AppError;
+use crate::models::{Profile, ProjectConfig, Skill};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// A link the resolver wants to exist in a skills directory
+#[derive(Debug, Clone)]
+pub(crate) struct DesiredLink {
+    /// Link name inside the skills directory
+    pub name: String,
+    /// Skill ID the link belongs to
+    pub skill_id: String,
+    /// Skill directory the link should point at
+    pub source_path: PathBuf,
+}
+
+/// Find a scanned skill by ID or name (same matching as the frontend); a name shared by
+/// several skills finds the first in catalog order (see `scanner::sort_skills`)
+pub(crate) fn find_skill<'a>(skills: &'a [Skill], sid: &str) -> Option<&'a Skill> {
+    skills.iter().find(|s| s.id == sid || s.name == sid)
+}
+
+/// Valid alias for a skill; aliases may be keyed by the ID as written in the profile or by the catalog ID
+fn alias_for(aliases: &HashMap<String, String>, skill: &Skill, sid: &str) -> Option<String> {
+    aliases
+        .get(sid)
+        .or_else(|| aliases.get(&skill.id))
+        .filter(|a| validate_link_name(a).is_ok())
+        .cloned()
+}
+
+/// Link name for a skill of a profile: the profile's alias, then the skill name
+pub(crate) fn profile_link_name(skill: &Skill, sid: &str, profile: Option<&Profile>) -> String {
+    profile
+        .and_then(|p| alias_for(&p.aliases, skill, sid))
+        .unwrap_or_else(|| skill.name.clone())
+}
+
+/// Link name for a skill: the project's alias, then the profile's, then the skill name
+fn link_name(skill: &Skill, sid: &str, profile: Option<&Profile>, project: &ProjectConfig) -> String {
+    alias_for(&project.aliases, skill, sid).unwrap_or_else(|| profile_link_name(skill, sid, profile))
+}
+
+/// One of several skills that resolve to the same link name
+#[derive(Debug, Clone, Serialize)]
+pub struct ConflictCandidate {
+    /// Catalog skill ID
+    pub skill_id: String,
+    pub source_path: String,
+    /// Profile that contributes the skill (None for the project's extra skills)
+    pub profile_id: Option<String>,
+}
+
+/// Different skills competing for one link name in a target
+#[derive(Debug, Clone, Serialize)]
+pub struct LinkConflict {
+    pub link_name: String,
+    /// Candidates in resolution order
+    pub candidates: Vec<ConflictCandidate>,
+    /// Skill ID that gets linked
+    pub winner: String,
+    /// Whether the winner was recorded in the project config (otherwise the first candidate wins)
+    pub explicit: bool,
+}
+
+/// Links resolved for one target
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ProjectLinks {
+    pub desired: Vec<DesiredLink>,
+    /// Skill IDs that matched nothing in the catalog
+    pub unresolved: Vec<String>,
+    /// Conditional entries whose conditions do not hold for the target
+    /// (left out unless another entry links the same skill)
+    pub skipped: Vec<SkippedEntry>,
+    /// Link names claimed by more than one skill
+    pub conflicts: Vec<LinkConflict>,
+}
+
+/// Pick the candidate recorded as winner for a link name, falling back to the first one.
+/// The recorded winner may be written as a catalog ID or a skill name.
+fn pick_winner(candidates: &[(ConflictCandidate, DesiredLink)], recorded: Option<&String>, skills: &[Skill]) -> (usize, bool) {
+    let recorded_id = recorded.and_then(|w| find_skill(skills, w)).map(|s| s.id.as_str());
+    match candidates.iter().position(|(c, _)| Some(c.skill_id.as_str()) == recorded_id) {
+        Some(i) => (i, true),
+        None => (0, false),
+    }
+}
+
+/// Resolve a project's profiles (inheritance and dynamic queries included) + extra skills
+/// into one link per link name, leaving out entries whose conditions do not hold for the
+/// target described by `ctx`. Different skills with the same link name are reported as
+/// conflicts; the project's recorded winner gets the link, otherwise the first candidate.
+/// Fails when the target host cannot be inspected to evaluate a condition.
+pub(crate) fn resolve_project_links(
+    project: &ProjectConfig,
+    profiles: &[Profile],
+    skills: &[Skill],
+    ctx: &TargetContext,
+) -> Result<ProjectLinks, AppError> {
+    let mut links = ProjectLinks::default();
+    // link name -> candidates, in first-seen order
+    let mut by_name: Vec<(String, Vec<(ConflictCandidate, DesiredLink)>)> = Vec::new();
+
+    // (skill ID, applied profile, profile declaring the entry)
+    let profile_ids = project
+        .profile_ids
+        .iter()
+        .filter_map(|pid| profiles.iter().find(|p| &p.id == pid))
+        .flat_map(|p| {
+            resolve_profile(p, profiles, Some(skills))
+                .skills
+                .into_iter()
+                .map(move |s| {
+                    let declaring = profiles.iter().find(|d| d.id == s.from_profile_id);
+                    (s.skill_id, Some(p), declaring)
+                })
+        });
+    let extra_ids = project.extra_skill_ids.iter().map(|sid| (sid.clone(), None, None));
+
+    for (sid, profile, declaring) in profile_ids.chain(extra_ids) {
+        let skill = find_skill(skills, &sid);
+        if let Some(declaring) = declaring {
+            let reasons = ctx.skip_reasons(declaring, &sid, skill.map(|s| s.id.as_str()))?;
+            if !reasons.is_empty() {
+                links.skipped.push(SkippedEntry {
+                    skill_id: sid,
+                    profile_id: declaring.id.clone(),
+                    reasons,
+                });
+                continue;
+            }
+        }
+        let skill = match skill {
+            Some(skill) => skill,
+            None => {
+                if !links.unresolved.contains(&sid) {
+                    links.unresolved.push(sid);
+                }
+                continue;
+            }
+        };
+
+        let name = link_name(skill, &sid, profile, project);
+        let candidate = (
+            ConflictCandidate {
+                skill_id: skill.id.clone(),
+                source_path: skill.source_path.to_string_lossy().to_string(),
+                profile_id: profile.map(|p| p.id.clone()),
+            },
+            DesiredLink {
+                name: name.clone(),
+                skill_id: skill.id.clone(),
+                source_path: skill.source_path.clone(),
+            },
+        );
+        match by_name.iter_mut().find(|(n, _)| *n == name) {
+            Some((_, candidates)) => {
+                if !candidates.iter().any(|(c, _)| c.skill_id == skill.id) {
+                    candidates.push(candidate);
+                }
+            }
+            None => by_name.push((name, vec![candidate])),
+        }
+    }
+
+    for (name, mut candidates) in by_name {
+        let (index, explicit) = pick_winner(&candidates, project.link_winners.get(&name), skills);
+        if candidates.len() > 1 {
+            links.conflicts.push(LinkConflict {
+                link_name: name,
+                candidates: candidates.iter().map(|(c, _)| c.clone()).collect(),
+                winner: candidates[index].0.skill_id.clone(),
+                explicit,
+            });
+        }
+        links.desired.push(candidates.swap_remove(index).1);
+    }
+
+    let desired = &links.desired;
+    links
+        .skipped
+        .retain(|e| !find_skill(skills, &e.skill_id).is_some_and(|s| desired.iter().any(|d| d.skill_id == s.id)));
+    Ok(links)
+}
+
+/// Resolve a single profile for a target, as a project applying only that profile.
+/// The given profile replaces the stored one with the same ID (e.g. an older version).
+pub(crate) fn resolve_profile_links(
+    profile: &Profile,
+    profiles: &[Profile],
+    skills: &[Skill],
+    ctx: &TargetContext,
+) -> Result<ProjectLinks, AppError> {
+    let mut all: Vec<Profile> = profiles.iter().filter(|p| p.id != profile.id).cloned().collect();
+    all.push(profile.clone());
+    let project = ProjectConfig {
+        id: String::new(),
+        name: String::new(),
+        path: PathBuf::from(ctx.project_path().unwrap_or_default()),
+        profile_ids: vec![profile.id.clone()],
+        extra_skill_ids: Vec::new(),
+        aliases: HashMap::new(),
+        tags: Vec::new(),
+        link_winners: HashMap::new(),
+    };
+    resolve_project_links(&project, &all, skills, ctx)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::conditions::LocalHost;
+    use crate::models::{EntryCondition, LinkStatus, ProfileKind};
+
+    fn skill(id: &str) -> Skill {
+        Skill {
+            id: id.to_string(),
+            name: id.rsplit('/').next().unwrap_or(id).to_string(),
+            description: String::new(),
+            source_path: PathBuf::from(format!("/repo/{}", id)),
+            source_repo: "repo".to_string(),
+            category: None,
+            tags: Vec::new(),
+            has_scripts: false,
+            has_references: false,
+            link_status_user: LinkStatus::Inactive,
+            link_name_user: None,
+            dependencies: Vec::new(),
+            raw_content: String::new(),
+        }
+    }
+
+    fn profile(skill_ids: &[&str], conditions: HashMap<String, Vec<EntryCondition>>) -> Profile {
+        Profile {
+            id: "base".to_string(),
+            name: "Base".to_string(),
+            description: String::new(),
+            color: String::new(),
+            skill_ids: skill_ids.iter().map(|s| s.to_string()).collect(),
+            is_preset: false,
+            aliases: HashMap::new(),
+            extends: Vec::new(),
+            excludes: Vec::new(),
+            kind: ProfileKind::Static,
+            query: None,
+            conditions,
+            resolved: None,
+        }
+    }
+
+    fn project(extra_skill_ids: &[&str], link_winners: &[(&str, &str)]) -> ProjectConfig {
+        ProjectConfig {
+            id: "p".to_string(),
+            name: "p".to_string(),
+            path: PathBuf::from("/work/p"),
+            profile_ids: vec!["base".to_string()],
+            extra_skill_ids: extra_skill_ids.iter().map(|s| s.to_string()).collect(),
+            aliases: HashMap::new(),
+            tags: Vec::new(),
+            link_winners: link_winners.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect(),
+        }
+    }
+
+    fn resolve(project: &ProjectConfig, profile: Profile, skills: &[Skill]) -> ProjectLinks {
+        let ctx = TargetContext::project(&LocalHost, project);
+        resolve_project_links(project, &[profile], skills, &ctx).unwrap()
+    }
+
+    fn needs_tag(sid: &str) -> HashMap<String, Vec<EntryCondition>> {
+        let tag = EntryCondition::ProjectTag { tag: "infra".to_string() };
+        HashMap::from([(sid.to_string(), vec![tag])])
+    }
+
+    #[test]
+    fn entries_failing_their_conditions_are_skipped() {
+        let skills = [skill("skills/a"), skill("skills/tf")];
+        let links = resolve(&project(&[], &[]), profile(&["skills/a", "skills/tf"], needs_tag("skills/tf")), &skills);
+
+        assert_eq!(links.desired.len(), 1);
+        assert_eq!(links.desired[0].skill_id, "skills/a");
+        assert_eq!(links.skipped.len(), 1);
+        assert_eq!(links.skipped[0].skill_id, "skills/tf");
+        assert_eq!(links.skipped[0].profile_id, "base");
+        assert!(!links.skipped[0].reasons.is_empty());
+    }
+
+    #[test]
+    fn skipped_entry_linked_by_another_entry_is_not_reported() {
+        let skills = [skill("skills/tf")];
+        let links = resolve(&project(&["skills/tf"], &[]), profile(&["skills/tf"], needs_tag("skills/tf")), &skills);
+
+        assert_eq!(links.desired.len(), 1);
+        assert!(links.skipped.is_empty());
+    }
+
+    #[test]
+    fn recorded_winner_gets_the_link() {
+        let skills = [skill("skills/a"), skill("other/a")];
+        let links = resolve(&project(&["other/a"], &[("a", "other/a")]), profile(&["skills/a"], HashMap::new()), &skills);
+
+        assert_eq!(links.desired.len(), 1);
+        assert_eq!(links.desired[0].skill_id, "other/a");
+        assert_eq!(links.conflicts[0].winner, "other/a");
+        assert!(links.conflicts[0].explicit);
+    }
+
+    #[test]
+    fn first_candidate_wins_without_a_recorded_winner() {
+        let skills = [skill("skills/a"), skill("other/a")];
+        let links = resolve(&project(&["other/a", "gone"], &[]), profile(&["skills/a"], HashMap::new()), &skills);
+
+        assert_eq!(links.desired[0].skill_id, "skills/a");
+        assert_eq!(links.conflicts[0].candidates.len(), 2);
+        assert!(!links.conflicts[0].explicit);
+        assert_eq!(links.unresolved, vec!["gone"]);
+    }
+}
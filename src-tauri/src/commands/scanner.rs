@@ -1,7 +1,10 @@
+use crate::commands::settings;
+use crate::commands::linker::{read_link_entries, user_skills_dir, EntryKind, LinkEntry};
 use crate::error::AppError;
 use crate::models::{LinkStatus, Skill, SkillFrontmatter};
 use regex::Regex;
-use std::collections::{HashMap, HashSet};
+use serde::de::DeserializeOwned;
+use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 use walkdir::WalkDir;
@@ -19,15 +22,15 @@ const EXCLUDED_DIRS: &[&str] = &[
     ".vscode",
 ];
 
-/// Parse YAML frontmatter from SKILL.md content
-pub(crate) fn parse_frontmatter(content: &str) -> SkillFrontmatter {
+/// Parse YAML frontmatter from SKILL.md content (into `SkillFrontmatter`, or any mapping)
+pub(crate) fn parse_frontmatter<T: DeserializeOwned + Default>(content: &str) -> T {
     if !content.starts_with("---") {
-        return SkillFrontmatter::default();
+        return T::default();
     }
 
     let parts: Vec<&str> = content.splitn(3, "---").collect();
     if parts.len() < 3 {
-        return SkillFrontmatter::default();
+        return T::default();
     }
 
     let yaml_str = parts[1].trim();
@@ -129,62 +132,86 @@ pub(crate) fn extract_dependencies(content: &str) -> Vec<String> {
     deps
 }
 
-/// Check the link status of a skill in the user-level skills directory
-fn check_user_link_status(skill_name: &str, skill_source: &Path) -> LinkStatus {
-    let user_skills_dir = dirs::home_dir()
-        .map(|h| h.join(".claude").join("skills").join(skill_name));
-
-    let link_path = match user_skills_dir {
-        Some(p) => p,
-        None => return LinkStatus::Inactive,
+/// Assign user-level link statuses from the entries of a skills directory.
+/// A symlink pointing at a skill's directory counts for that skill under any name (aliases);
+/// otherwise the entry named after the skill decides, unless it belongs to a same-named sibling.
+pub(crate) fn assign_user_link_statuses(
+    skills: &mut [Skill],
+    links: &[LinkEntry],
+    points_to: impl Fn(&str, &Path) -> bool,
+) {
+    let owner_of = |target: &str, skills: &[Skill]| -> Option<String> {
+        skills
+            .iter()
+            .find(|s| points_to(target, &s.source_path))
+            .map(|s| s.id.clone())
     };
+    let owners: Vec<Option<String>> = links
+        .iter()
+        .map(|l| match (&l.kind, &l.target) {
+            (EntryKind::Symlink, Some(t)) if l.target_exists => owner_of(t, skills),
+            _ => None,
+        })
+        .collect();
 
-    if !link_path.exists() && !link_path.symlink_metadata().is_ok() {
-        return LinkStatus::Inactive;
-    }
-
-    // Check if it's a symlink
-    match link_path.symlink_metadata() {
-        Ok(meta) => {
-            if meta.file_type().is_symlink() {
-                // Check if target is valid
-                if link_path.exists() {
-                    // Verify it points to the right source
-                    match fs::read_link(&link_path) {
-                        Ok(target) => {
-                            let resolved = if target.is_relative() {
-                                link_path.parent().unwrap_or(Path::new("")).join(&target)
-                            } else {
-                                target
-                            };
-                            if let (Ok(a), Ok(b)) = (resolved.canonicalize(), skill_source.canonicalize()) {
-                                if a == b {
-                                    LinkStatus::Active
-                                } else {
-                                    LinkStatus::Active // Points to different source but still works
-                                }
-                            } else {
-                                LinkStatus::Active
-                            }
-                        }
-                        Err(_) => LinkStatus::Active,
-                    }
-                } else {
-                    LinkStatus::Broken
-                }
-            } else if meta.is_dir() {
-                LinkStatus::Direct
+    for skill in skills.iter_mut() {
+        let pointing: Vec<&LinkEntry> = links
+            .iter()
+            .zip(&owners)
+            .filter(|(_, owner)| owner.as_deref() == Some(skill.id.as_str()))
+            .map(|(l, _)| l)
+            .collect();
+        if !pointing.is_empty() {
+            skill.link_status_user = LinkStatus::Active;
+            skill.link_name_user = if pointing.iter().any(|l| l.name == skill.name) {
+                None
             } else {
-                LinkStatus::Inactive
-            }
+                pointing.iter().map(|l| l.name.clone()).min()
+            };
+            continue;
         }
-        Err(_) => LinkStatus::Inactive,
+
+        skill.link_name_user = None;
+        skill.link_status_user = match links.iter().zip(&owners).find(|(l, _)| l.name == skill.name) {
+            None => LinkStatus::Inactive,
+            Some((l, _)) if l.kind == EntryKind::Directory => LinkStatus::Direct,
+            Some((l, _)) if !l.target_exists => LinkStatus::Broken,
+            // Linked under this name, but to another skill in the catalog
+            Some((_, Some(_))) => LinkStatus::Inactive,
+            // Points to a different source but still works
+            Some((_, None)) => LinkStatus::Active,
+        };
     }
 }
 
-/// Scan the entire skills repository and return all discovered skills
+/// Assign link statuses against the selected home's user-level skills directory
+fn assign_local_user_link_statuses(skills: &mut [Skill]) {
+    let dir = match user_skills_dir() {
+        Ok(d) => d,
+        Err(_) => return,
+    };
+    let links = read_link_entries(&dir).unwrap_or_default();
+    assign_user_link_statuses(skills, &links, |target, source| {
+        match (dir.join(target).canonicalize(), source.canonicalize()) {
+            (Ok(a), Ok(b)) => a == b,
+            _ => Path::new(target) == source,
+        }
+    });
+}
+
+/// Order a scanned catalog by name. Same-named skills are all kept (links tell them apart
+/// through aliases); name lookups take the first, so they are ordered the same way locally
+/// and remotely: shortest ID (the top-most directory) first, then by ID.
+pub(crate) fn sort_skills(skills: &mut [Skill]) {
+    skills.sort_by_key(|s| (s.name.to_lowercase(), s.id.len(), s.id.clone()));
+}
+
+/// Scan the entire skills repository (default: the configured repo root) and return all
+/// discovered skills, minus those matching the configured ignore patterns
 #[tauri::command]
-pub fn scan_skills_repo(repo_path: String) -> Result<Vec<Skill>, AppError> {
+pub fn scan_skills_repo(repo_path: Option<String>) -> Result<Vec<Skill>, AppError> {
+    let repo_path = settings::repo_path(repo_path)?;
+    let ignored = settings::ignore_regexes(&settings::load_settings()?);
     let repo_root = PathBuf::from(&repo_path);
     if !repo_root.is_dir() {
         return Err(AppError::Custom(format!(
@@ -195,7 +222,6 @@ pub fn scan_skills_repo(repo_path: String) -> Result<Vec<Skill>, AppError> {
 
     let submodules = parse_gitmodules(&repo_root);
     let mut skills: Vec<Skill> = Vec::new();
-    let mut seen_names: HashSet<String> = HashSet::new();
 
     for entry in WalkDir::new(&repo_root)
         .follow_links(true)
@@ -231,7 +257,7 @@ pub fn scan_skills_repo(repo_path: String) -> Result<Vec<Skill>, AppError> {
             Err(_) => continue,
         };
 
-        let frontmatter = parse_frontmatter(&content);
+        let frontmatter: SkillFrontmatter = parse_frontmatter(&content);
 
         // Determine name: frontmatter > directory name
         let dir_name = skill_dir
@@ -241,17 +267,14 @@ pub fn scan_skills_repo(repo_path: String) -> Result<Vec<Skill>, AppError> {
 
         let name = frontmatter.name.unwrap_or_else(|| dir_name.clone());
 
-        // Dedup: keep first occurrence (shortest path wins due to WalkDir order)
-        if seen_names.contains(&name) {
-            continue;
-        }
-        seen_names.insert(name.clone());
-
         // Build relative ID
         let id = skill_dir
             .strip_prefix(&repo_root)
             .map(|r| r.to_string_lossy().to_string())
             .unwrap_or_else(|_| dir_name.clone());
+        if ignored.iter().any(|r| r.is_match(&id)) {
+            continue;
+        }
 
         let description = frontmatter
             .description
@@ -272,7 +295,6 @@ pub fn scan_skills_repo(repo_path: String) -> Result<Vec<Skill>, AppError> {
         let dependencies = extract_dependencies(&content);
         let has_scripts = skill_dir.join("scripts").is_dir();
         let has_references = skill_dir.join("references").is_dir();
-        let link_status_user = check_user_link_status(&name, &skill_dir);
 
         skills.push(Skill {
             id,
@@ -284,26 +306,22 @@ pub fn scan_skills_repo(repo_path: String) -> Result<Vec<Skill>, AppError> {
             tags: frontmatter.tags,
             has_scripts,
             has_references,
-            link_status_user,
+            link_status_user: LinkStatus::Inactive,
+            link_name_user: None,
             dependencies,
             raw_content: content,
         });
     }
 
-    // Sort by name for consistent ordering
-    skills.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+    sort_skills(&mut skills);
+    assign_local_user_link_statuses(&mut skills);
 
     Ok(skills)
 }
 
 /// Refresh link statuses for all skills
 #[tauri::command]
-pub fn refresh_link_statuses(skills: Vec<Skill>) -> Vec<Skill> {
+pub fn refresh_link_statuses(mut skills: Vec<Skill>) -> Vec<Skill> {
+    assign_local_user_link_statuses(&mut skills);
     skills
-        .into_iter()
-        .map(|mut s| {
-            s.link_status_user = check_user_link_status(&s.name, &s.source_path);
-            s
-        })
-        .collect()
 }
@@ -0,0 +1,296 @@
+use crate::commands::query::glob_to_regex;
+use crate::commands::targets::find_target;
+use crate::error::AppError;
+use crate::models::{AppSettings, LinkMode, SyncSettings};
+use crate::store::{self, Schema};
+use regex::Regex;
+use serde::Serialize;
+use std::ffi::OsString;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use tauri::{AppHandle, Emitter};
+
+/// Environment variable that relocates the data root
+pub(crate) const DATA_DIR_ENV: &str = "SKILLPILOT_DATA_DIR";
+
+/// Directory next to the executable that switches on portable mode when it exists
+pub(crate) const PORTABLE_DIR_NAME: &str = "skillpilot-data";
+
+/// Event emitted with the new settings after they are updated
+pub(crate) const SETTINGS_CHANGED_EVENT: &str = "settings-changed";
+
+/// Schema of config.json
+pub(crate) const SETTINGS_SCHEMA: Schema = Schema {
+    version: 1,
+    migrations: &[store::unversioned],
+};
+
+/// Where the data root comes from
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+pub enum DataRootSource {
+    /// The SKILLPILOT_DATA_DIR environment variable
+    Env,
+    /// A skillpilot-data directory next to the executable
+    Portable,
+    /// ~/.claude-skill-manager
+    Default,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DataRoot {
+    pub path: PathBuf,
+    pub source: DataRootSource,
+}
+
+static DATA_ROOT: OnceLock<DataRoot> = OnceLock::new();
+
+fn portable_dir() -> Option<PathBuf> {
+    let exe = std::env::current_exe().ok()?;
+    let dir = exe.parent()?.join(PORTABLE_DIR_NAME);
+    dir.is_dir().then_some(dir)
+}
+
+/// Pick the data root from the environment variable, the portable directory and the home
+/// directory, in that order
+fn pick_data_root(
+    env: Option<OsString>,
+    portable: Option<PathBuf>,
+    home: Option<PathBuf>,
+) -> Result<DataRoot, AppError> {
+    if let Some(value) = env.filter(|v| !v.is_empty()) {
+        let expanded = shellexpand::tilde(&value.to_string_lossy()).to_string();
+        return Ok(DataRoot {
+            path: std::path::absolute(Path::new(&expanded))?,
+            source: DataRootSource::Env,
+        });
+    }
+    if let Some(path) = portable {
+        return Ok(DataRoot {
+            path,
+            source: DataRootSource::Portable,
+        });
+    }
+    let path = home
+        .map(|h| h.join(".claude-skill-manager"))
+        .ok_or(AppError::ConfigDirNotFound)?;
+    Ok(DataRoot {
+        path,
+        source: DataRootSource::Default,
+    })
+}
+
+fn resolve_data_root() -> Result<DataRoot, AppError> {
+    pick_data_root(std::env::var_os(DATA_DIR_ENV), portable_dir(), dirs::home_dir())
+}
+
+/// The data root, resolved once per process: SKILLPILOT_DATA_DIR, else a portable
+/// skillpilot-data directory next to the executable, else ~/.claude-skill-manager
+fn data_root() -> Result<&'static DataRoot, AppError> {
+    if let Some(root) = DATA_ROOT.get() {
+        return Ok(root);
+    }
+    let root = resolve_data_root()?;
+    Ok(DATA_ROOT.get_or_init(|| root))
+}
+
+/// Point the data root at a temporary directory shared by every test of this run; fails
+/// if something already resolved the real one
+#[cfg(test)]
+pub(crate) fn use_temp_data_root() -> PathBuf {
+    let temp = || std::env::temp_dir().join(format!("skillpilot-data-{}", uuid::Uuid::new_v4()));
+    let root = DATA_ROOT.get_or_init(|| DataRoot {
+        path: temp(),
+        source: DataRootSource::Env,
+    });
+    assert!(
+        root.path.starts_with(std::env::temp_dir()),
+        "data root already resolved to {}",
+        root.path.display()
+    );
+    root.path.clone()
+}
+
+/// The data root directory, created if missing
+pub(crate) fn data_dir() -> Result<PathBuf, AppError> {
+    let dir = data_root()?.path.clone();
+    fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
+/// A subdirectory of the data root, created if missing
+pub(crate) fn data_subdir(name: &str) -> Result<PathBuf, AppError> {
+    let dir = data_dir()?.join(name);
+    fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
+/// Get the data root in use and where it comes from
+#[tauri::command]
+pub fn get_data_root() -> Result<DataRoot, AppError> {
+    data_root().cloned()
+}
+
+fn settings_path() -> Result<PathBuf, AppError> {
+    Ok(data_dir()?.join("config.json"))
+}
+
+/// Current settings (defaults when config.json does not exist)
+pub(crate) fn load_settings() -> Result<AppSettings, AppError> {
+    Ok(store::read_json(&settings_path()?, &SETTINGS_SCHEMA)?.unwrap_or_default())
+}
+
+/// Change the stored settings under the config lock (listeners are not notified)
+pub(crate) fn update_stored_settings<R>(
+    f: impl FnOnce(&mut AppSettings) -> Result<R, AppError>,
+) -> Result<R, AppError> {
+    store::update_json(&settings_path()?, &SETTINGS_SCHEMA, f)
+}
+
+/// The first configured repo root, if any
+pub(crate) fn default_repo_path() -> Option<String> {
+    load_settings().ok()?.repo_roots.into_iter().next()
+}
+
+/// The given repo path, or the first configured repo root
+pub(crate) fn repo_path(repo_path: Option<String>) -> Result<String, AppError> {
+    repo_path
+        .filter(|p| !p.trim().is_empty())
+        .or_else(default_repo_path)
+        .ok_or_else(|| AppError::Custom("No skills repository configured".to_string()))
+}
+
+/// The given link mode, or the configured default
+pub(crate) fn link_mode(mode: Option<LinkMode>) -> Result<LinkMode, AppError> {
+    match mode {
+        Some(mode) => Ok(mode),
+        None => Ok(load_settings()?.default_link_mode),
+    }
+}
+
+/// Compiled ignore patterns of the settings
+pub(crate) fn ignore_regexes(settings: &AppSettings) -> Vec<Regex> {
+    settings.ignore_patterns.iter().filter_map(|p| glob_to_regex(p)).collect()
+}
+
+/// Normalize settings before saving: trimmed repo roots without blanks or duplicates, a
+/// sync branch, and every ignore pattern and target ID checked
+fn normalize(mut settings: AppSettings) -> Result<AppSettings, AppError> {
+    let mut roots: Vec<String> = Vec::new();
+    for root in settings.repo_roots.iter().map(|r| r.trim()) {
+        if !root.is_empty() && !roots.iter().any(|r| r == root) {
+            roots.push(root.to_string());
+        }
+    }
+    settings.repo_roots = roots;
+
+    settings.sync.remote = settings.sync.remote.map(|r| r.trim().to_string()).filter(|r| !r.is_empty());
+    settings.sync.branch = settings.sync.branch.trim().to_string();
+    if settings.sync.branch.is_empty() {
+        settings.sync.branch = SyncSettings::default().branch;
+    }
+
+    if let Some(bad) = settings.ignore_patterns.iter().find(|p| glob_to_regex(p).is_none()) {
+        return Err(AppError::Custom(format!("Invalid ignore pattern: {}", bad)));
+    }
+    for id in &settings.target_ids {
+        find_target(id)?;
+    }
+    Ok(settings)
+}
+
+/// Get the application settings
+#[tauri::command]
+pub fn get_settings() -> Result<AppSettings, AppError> {
+    load_settings()
+}
+
+/// Replace the application settings and notify listeners
+#[tauri::command]
+pub fn update_settings(app: AppHandle, settings: AppSettings) -> Result<AppSettings, AppError> {
+    let settings = normalize(settings)?;
+    store::write_json(&settings_path()?, &SETTINGS_SCHEMA, &settings)?;
+    let _ = app.emit(SETTINGS_CHANGED_EVENT, settings.clone());
+    Ok(settings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::LinkTarget;
+
+    fn pick(env: Option<&str>, portable: Option<&str>) -> DataRoot {
+        pick_data_root(env.map(OsString::from), portable.map(PathBuf::from), Some(PathBuf::from("/home/me"))).unwrap()
+    }
+
+    #[test]
+    fn data_root_prefers_env_then_portable_then_home() {
+        let root = pick(Some("/srv/data"), Some("/opt/app/skillpilot-data"));
+        assert_eq!((root.path, root.source), (PathBuf::from("/srv/data"), DataRootSource::Env));
+
+        let root = pick(Some(""), Some("/opt/app/skillpilot-data"));
+        assert_eq!(
+            (root.path, root.source),
+            (PathBuf::from("/opt/app/skillpilot-data"), DataRootSource::Portable)
+        );
+
+        let root = pick(None, None);
+        assert_eq!(
+            (root.path, root.source),
+            (PathBuf::from("/home/me/.claude-skill-manager"), DataRootSource::Default)
+        );
+        assert!(matches!(pick_data_root(None, None, None), Err(AppError::ConfigDirNotFound)));
+    }
+
+    #[test]
+    fn normalize_cleans_roots_and_sync_fields() {
+        use_temp_data_root();
+        let mut settings = AppSettings {
+            repo_roots: ["  /repo ", "", "/repo", "/other"].map(String::from).to_vec(),
+            target_ids: vec![LinkTarget::builtins()[0].id.clone()],
+            ignore_patterns: vec!["drafts/**".to_string()],
+            ..Default::default()
+        };
+        settings.sync.remote = Some("  ".to_string());
+        settings.sync.branch = " ".to_string();
+
+        let settings = normalize(settings).unwrap();
+        assert_eq!(settings.repo_roots, vec!["/repo", "/other"]);
+        assert_eq!(settings.sync.remote, None);
+        assert_eq!(settings.sync.branch, SyncSettings::default().branch);
+    }
+
+    #[test]
+    fn normalize_rejects_unknown_targets() {
+        use_temp_data_root();
+        let unknown_target = AppSettings {
+            target_ids: vec!["no-such-target".to_string()],
+            ..Default::default()
+        };
+        assert!(normalize(unknown_target).is_err());
+    }
+
+    #[test]
+    fn missing_settings_fall_back_to_defaults() {
+        use_temp_data_root();
+        let settings: AppSettings = serde_json::from_str(r#"{"repo_roots": ["/repo"]}"#).unwrap();
+        assert_eq!(settings.default_link_mode, LinkMode::Absolute);
+        assert_eq!(settings.thresholds.max_profile_versions, 50);
+        assert_eq!(settings.sync.branch, SyncSettings::default().branch);
+
+        assert_eq!(repo_path(Some("/given".to_string())).unwrap(), "/given");
+        assert_eq!(link_mode(Some(LinkMode::Relative)).unwrap(), LinkMode::Relative);
+        // Without config.json there is no repo root to fall back to
+        assert!(repo_path(Some(" ".to_string())).is_err());
+        assert_eq!(link_mode(None).unwrap(), LinkMode::Absolute);
+    }
+
+    #[test]
+    fn env_data_root_is_expanded_and_made_absolute() {
+        let root = pick(Some("~/skillpilot"), None);
+        assert_eq!(root.path, dirs::home_dir().unwrap().join("skillpilot"));
+
+        let root = pick(Some("relative/data"), None);
+        assert_eq!(root.path, std::env::current_dir().unwrap().join("relative/data"));
+    }
+}
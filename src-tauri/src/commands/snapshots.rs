@@ -0,0 +1,374 @@
+use crate::commands::linker::{project_skills_dir, read_link_entries, user_skills_dir, EntryKind, LinkEntry};
+use crate::commands::settings::data_subdir;
+use crate::error::AppError;
+use crate::models::{Snapshot, SnapshotEntry, SnapshotEntryKind};
+use crate::store::{self, Schema};
+use serde::Serialize;
+use std::collections::HashSet;
+use std::fs;
+use std::os::unix::fs as unix_fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SnapshotChange {
+    pub name: String,
+    pub snapshot: SnapshotEntry,
+    pub current: SnapshotEntry,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SnapshotDiff {
+    pub snapshot_id: String,
+    /// Entries a restore would bring back
+    pub only_in_snapshot: Vec<SnapshotEntry>,
+    /// Entries that exist now but not in the snapshot
+    pub only_in_current: Vec<SnapshotEntry>,
+    /// Same name, different kind or target
+    pub changed: Vec<SnapshotChange>,
+    pub unchanged: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Default)]
+pub struct RestoreReport {
+    /// Symlinks recreated from the snapshot
+    pub restored: Vec<String>,
+    /// Real directories/files present now that were kept in place
+    pub kept: Vec<String>,
+    /// Directories recorded in the snapshot that no longer exist (cannot be recreated)
+    pub missing_directories: Vec<String>,
+}
+
+/// Schema of snapshot files, locally and on remote servers
+pub(crate) const SNAPSHOT_SCHEMA: Schema = Schema {
+    version: 1,
+    migrations: &[store::unversioned],
+};
+
+fn snapshots_dir() -> Result<PathBuf, AppError> {
+    data_subdir("snapshots")
+}
+
+fn snapshot_path(id: &str) -> Result<PathBuf, AppError> {
+    store::check_file_id("snapshot", id)?;
+    Ok(snapshots_dir()?.join(format!("{}.json", id)))
+}
+
+fn load_snapshot(id: &str) -> Result<Snapshot, AppError> {
+    store::read_json(&snapshot_path(id)?, &SNAPSHOT_SCHEMA)?
+        .ok_or_else(|| AppError::Custom(format!("Snapshot not found: {}", id)))
+}
+
+fn target_dir(target_path: &Option<String>) -> Result<PathBuf, AppError> {
+    match target_path {
+        Some(p) => Ok(project_skills_dir(p)),
+        None => user_skills_dir(),
+    }
+}
+
+/// Directory a snapshot belongs to: the one it was taken from, else resolved from its target
+fn snapshot_dir(snapshot: &Snapshot) -> Result<PathBuf, AppError> {
+    match &snapshot.skills_dir {
+        Some(dir) => Ok(PathBuf::from(dir)),
+        None => target_dir(&snapshot.target_path),
+    }
+}
+
+/// Convert directory entries into sorted snapshot entries
+pub(crate) fn snapshot_entries(entries: &[LinkEntry]) -> Vec<SnapshotEntry> {
+    let mut result: Vec<SnapshotEntry> = entries
+        .iter()
+        .map(|e| SnapshotEntry {
+            name: e.name.clone(),
+            kind: match e.kind {
+                EntryKind::Symlink => SnapshotEntryKind::Symlink,
+                EntryKind::Directory => SnapshotEntryKind::Directory,
+            },
+            target: e.target.clone(),
+        })
+        .collect();
+    result.sort_by_key(|e| e.name.to_lowercase());
+    result
+}
+
+/// Build a new snapshot record
+pub(crate) fn new_snapshot(
+    name: String,
+    target_path: Option<String>,
+    skills_dir: String,
+    entries: &[LinkEntry],
+) -> Snapshot {
+    Snapshot {
+        id: uuid::Uuid::new_v4().to_string(),
+        name,
+        created_at: chrono::Utc::now().to_rfc3339(),
+        target_path,
+        skills_dir: Some(skills_dir),
+        entries: snapshot_entries(entries),
+    }
+}
+
+/// Compare a snapshot with the current entries of its directory
+pub(crate) fn diff_entries(snapshot: &Snapshot, current: &[SnapshotEntry]) -> SnapshotDiff {
+    let mut diff = SnapshotDiff {
+        snapshot_id: snapshot.id.clone(),
+        only_in_snapshot: Vec::new(),
+        only_in_current: Vec::new(),
+        changed: Vec::new(),
+        unchanged: 0,
+    };
+
+    for saved in &snapshot.entries {
+        match current.iter().find(|c| c.name == saved.name) {
+            None => diff.only_in_snapshot.push(saved.clone()),
+            Some(now) if now == saved => diff.unchanged += 1,
+            Some(now) => diff.changed.push(SnapshotChange {
+                name: saved.name.clone(),
+                snapshot: saved.clone(),
+                current: now.clone(),
+            }),
+        }
+    }
+    for now in current {
+        if !snapshot.entries.iter().any(|s| s.name == now.name) {
+            diff.only_in_current.push(now.clone());
+        }
+    }
+
+    diff
+}
+
+/// Plan a restore: which symlinks to create and what to report.
+/// Real directories are never removed, so a snapshot symlink that collides with one is skipped.
+pub(crate) fn plan_restore(snapshot: &Snapshot, current: &[SnapshotEntry]) -> (Vec<SnapshotEntry>, RestoreReport) {
+    let real: HashSet<&str> = current
+        .iter()
+        .filter(|e| e.kind == SnapshotEntryKind::Directory)
+        .map(|e| e.name.as_str())
+        .collect();
+
+    let mut report = RestoreReport {
+        kept: real.iter().map(|n| n.to_string()).collect(),
+        ..Default::default()
+    };
+    report.kept.sort();
+
+    let mut links = Vec::new();
+    for entry in &snapshot.entries {
+        match entry.kind {
+            SnapshotEntryKind::Symlink if !real.contains(entry.name.as_str()) => {
+                report.restored.push(entry.name.clone());
+                links.push(entry.clone());
+            }
+            SnapshotEntryKind::Symlink => {}
+            SnapshotEntryKind::Directory => {
+                if !real.contains(entry.name.as_str()) {
+                    report.missing_directories.push(entry.name.clone());
+                }
+            }
+        }
+    }
+    (links, report)
+}
+
+/// Sibling path used while restoring, e.g. ~/.claude/.skills.sp-restore
+fn sibling(dir: &Path, suffix: &str) -> PathBuf {
+    let name = dir
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| "skills".to_string());
+    dir.with_file_name(format!(".{}.{}", name, suffix))
+}
+
+/// Move every non-symlink entry from one directory to another, returning what was moved
+fn move_real_entries(from: &Path, to: &Path) -> Result<Vec<String>, AppError> {
+    let mut moved = Vec::new();
+    if !from.is_dir() {
+        return Ok(moved);
+    }
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        if entry.file_type()?.is_symlink() {
+            continue;
+        }
+        fs::rename(entry.path(), to.join(entry.file_name()))?;
+        moved.push(entry.file_name().to_string_lossy().to_string());
+    }
+    Ok(moved)
+}
+
+/// Build the restored directory next to the live one, then swap it in with renames.
+/// A symlinked skills directory is restored where it points, keeping the link.
+fn restore_dir(dir: &Path, links: &[SnapshotEntry]) -> Result<(), AppError> {
+    let resolved;
+    let dir = if dir.is_symlink() {
+        resolved = fs::canonicalize(dir)?;
+        resolved.as_path()
+    } else {
+        dir
+    };
+    let staging = sibling(dir, "sp-restore");
+    let old = sibling(dir, "sp-old");
+    for stale in [&staging, &old] {
+        if stale.exists() {
+            fs::remove_dir_all(stale)?;
+        }
+    }
+    if let Some(parent) = dir.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::create_dir(&staging)?;
+
+    let build = || -> Result<(), AppError> {
+        for link in links {
+            let target = link.target.clone().unwrap_or_default();
+            unix_fs::symlink(&target, staging.join(&link.name))?;
+        }
+        Ok(())
+    };
+    if let Err(e) = build() {
+        let _ = fs::remove_dir_all(&staging);
+        return Err(e);
+    }
+
+    // Carry real directories/files over, then swap
+    let swap = || -> Result<(), AppError> {
+        move_real_entries(dir, &staging)?;
+        if dir.exists() {
+            fs::rename(dir, &old)?;
+        }
+        fs::rename(&staging, dir)?;
+        Ok(())
+    };
+    if let Err(e) = swap() {
+        // Roll back: put the old directory and its real entries back
+        if !dir.exists() && old.exists() {
+            let _ = fs::rename(&old, dir);
+        }
+        let _ = move_real_entries(&staging, dir);
+        let _ = fs::remove_dir_all(&staging);
+        return Err(e);
+    }
+
+    if old.exists() {
+        fs::remove_dir_all(&old)?;
+    }
+    Ok(())
+}
+
+/// Save the current state of a skills directory as a named snapshot
+#[tauri::command]
+pub fn create_snapshot(name: String, target_path: Option<String>) -> Result<Snapshot, AppError> {
+    let dir = target_dir(&target_path)?;
+    let entries = read_link_entries(&dir)?;
+    let snapshot = new_snapshot(name, target_path, dir.to_string_lossy().to_string(), &entries);
+
+    store::write_json(&snapshot_path(&snapshot.id)?, &SNAPSHOT_SCHEMA, &snapshot)?;
+    Ok(snapshot)
+}
+
+/// List all snapshots, newest first
+#[tauri::command]
+pub fn list_snapshots() -> Result<Vec<Snapshot>, AppError> {
+    let mut snapshots = Vec::new();
+    for entry in fs::read_dir(snapshots_dir()?)? {
+        let path = entry?.path();
+        if path.extension().is_some_and(|e| e == "json") {
+            if let Ok(Some(snapshot)) = store::read_json::<Snapshot>(&path, &SNAPSHOT_SCHEMA) {
+                snapshots.push(snapshot);
+            }
+        }
+    }
+    snapshots.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    Ok(snapshots)
+}
+
+/// Delete a snapshot
+#[tauri::command]
+pub fn delete_snapshot(id: String) -> Result<(), AppError> {
+    store::remove(&snapshot_path(&id)?, &SNAPSHOT_SCHEMA)
+}
+
+/// Compare a snapshot with the current state of its directory
+#[tauri::command]
+pub fn diff_snapshot(id: String) -> Result<SnapshotDiff, AppError> {
+    let snapshot = load_snapshot(&id)?;
+    let current = snapshot_entries(&read_link_entries(&snapshot_dir(&snapshot)?)?);
+    Ok(diff_entries(&snapshot, &current))
+}
+
+/// Restore a skills directory to a snapshot, swapping the directory in atomically
+#[tauri::command]
+pub fn restore_snapshot(id: String) -> Result<RestoreReport, AppError> {
+    let snapshot = load_snapshot(&id)?;
+    let dir = snapshot_dir(&snapshot)?;
+    let current = snapshot_entries(&read_link_entries(&dir)?);
+    let (links, report) = plan_restore(&snapshot, &current);
+
+    restore_dir(&dir, &links)?;
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(name: &str, kind: SnapshotEntryKind) -> SnapshotEntry {
+        let target = (kind == SnapshotEntryKind::Symlink).then(|| format!("/repo/{}", name));
+        SnapshotEntry {
+            name: name.to_string(),
+            kind,
+            target,
+        }
+    }
+
+    #[test]
+    fn plan_restore_keeps_real_directories() {
+        let snapshot = Snapshot {
+            id: "s".to_string(),
+            name: "before".to_string(),
+            created_at: String::new(),
+            target_path: None,
+            skills_dir: None,
+            entries: vec![
+                entry("a", SnapshotEntryKind::Symlink),
+                entry("clash", SnapshotEntryKind::Symlink),
+                entry("gone", SnapshotEntryKind::Directory),
+                entry("local", SnapshotEntryKind::Directory),
+            ],
+        };
+        let current = vec![
+            entry("clash", SnapshotEntryKind::Directory),
+            entry("local", SnapshotEntryKind::Directory),
+            entry("stale", SnapshotEntryKind::Symlink),
+        ];
+
+        let (links, report) = plan_restore(&snapshot, &current);
+        assert_eq!(links, vec![entry("a", SnapshotEntryKind::Symlink)]);
+        assert_eq!(report.restored, vec!["a"]);
+        assert_eq!(report.kept, vec!["clash", "local"]);
+        assert_eq!(report.missing_directories, vec!["gone"]);
+    }
+
+    #[test]
+    fn restore_through_a_symlinked_directory_keeps_the_link() {
+        let root = std::env::temp_dir().join(format!("skillpilot-snapshots-{}", uuid::Uuid::new_v4()));
+        let real = root.join("real-skills");
+        let link = root.join("skills");
+        fs::create_dir_all(real.join("local")).unwrap();
+        unix_fs::symlink(&real, &link).unwrap();
+
+        restore_dir(&link, &[entry("a", SnapshotEntryKind::Symlink)]).unwrap();
+
+        assert!(link.is_symlink());
+        assert_eq!(fs::read_link(real.join("a")).unwrap(), Path::new("/repo/a"));
+        assert!(real.join("local").is_dir());
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn snapshot_ids_must_be_plain_file_names() {
+        for id in ["", "../x", "a/b", ".hidden"] {
+            assert!(snapshot_path(id).is_err(), "{:?}", id);
+        }
+    }
+}
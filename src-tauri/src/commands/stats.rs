@@ -1,7 +1,9 @@
+use crate::commands::homes::{active_home_id, DEFAULT_HOME_ID};
+use crate::commands::settings::data_dir;
 use crate::error::AppError;
+use crate::store::{self, Schema};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::fs;
 use std::path::PathBuf;
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -20,32 +22,37 @@ pub struct Stats {
     pub total_broken_cleaned: u32,
 }
 
+/// Schema of the stats files
+const STATS_SCHEMA: Schema = Schema {
+    version: 1,
+    migrations: &[store::unversioned],
+};
+
+/// Stats of the selected Claude home: stats.json for the default home, stats-<id>.json otherwise
 fn stats_path() -> Result<PathBuf, AppError> {
-    let dir = dirs::home_dir()
-        .map(|h| h.join(".claude-skill-manager"))
-        .ok_or(AppError::ConfigDirNotFound)?;
-    fs::create_dir_all(&dir)?;
-    Ok(dir.join("stats.json"))
+    let dir = data_dir()?;
+    let home_id = active_home_id()?;
+    if home_id == DEFAULT_HOME_ID {
+        Ok(dir.join("stats.json"))
+    } else {
+        store::check_file_id("Claude home", &home_id)?;
+        Ok(dir.join(format!("stats-{}.json", home_id)))
+    }
 }
 
 fn load_stats() -> Result<Stats, AppError> {
-    let path = stats_path()?;
-    if !path.exists() {
-        return Ok(Stats::default());
-    }
-    let content = fs::read_to_string(&path)?;
-    let stats: Stats = serde_json::from_str(&content)?;
-    Ok(stats)
+    Ok(store::read_json(&stats_path()?, &STATS_SCHEMA)?.unwrap_or_default())
 }
 
-fn save_stats(stats: &Stats) -> Result<(), AppError> {
-    let path = stats_path()?;
-    let json = serde_json::to_string_pretty(stats)?;
-    fs::write(&path, json)?;
-    Ok(())
+/// Change the stats under the config lock, so concurrent events are all counted
+fn update_stats(f: impl FnOnce(&mut Stats)) -> Result<(), AppError> {
+    store::update_json(&stats_path()?, &STATS_SCHEMA, |stats| {
+        f(stats);
+        Ok(())
+    })
 }
 
-/// Get current stats
+/// Get current stats (for the selected Claude home)
 #[tauri::command]
 pub fn get_stats() -> Result<Stats, AppError> {
     load_stats()
@@ -54,36 +61,30 @@ pub fn get_stats() -> Result<Stats, AppError> {
 /// Record a skill toggle event
 #[tauri::command]
 pub fn record_toggle(skill_name: String, created: bool) -> Result<(), AppError> {
-    let mut stats = load_stats()?;
-    *stats.toggle_counts.entry(skill_name).or_insert(0) += 1;
-    if created {
-        stats.total_links_created += 1;
-    } else {
-        stats.total_links_removed += 1;
-    }
-    save_stats(&stats)
+    update_stats(|stats| {
+        *stats.toggle_counts.entry(skill_name).or_insert(0) += 1;
+        if created {
+            stats.total_links_created += 1;
+        } else {
+            stats.total_links_removed += 1;
+        }
+    })
 }
 
 /// Record a profile apply event
 #[tauri::command]
 pub fn record_profile_apply(profile_id: String) -> Result<(), AppError> {
-    let mut stats = load_stats()?;
-    *stats.profile_apply_counts.entry(profile_id).or_insert(0) += 1;
-    save_stats(&stats)
+    update_stats(|stats| *stats.profile_apply_counts.entry(profile_id).or_insert(0) += 1)
 }
 
 /// Record a scan event
 #[tauri::command]
 pub fn record_scan() -> Result<(), AppError> {
-    let mut stats = load_stats()?;
-    stats.total_scans += 1;
-    save_stats(&stats)
+    update_stats(|stats| stats.total_scans += 1)
 }
 
 /// Record broken links cleaned
 #[tauri::command]
 pub fn record_clean(count: u32) -> Result<(), AppError> {
-    let mut stats = load_stats()?;
-    stats.total_broken_cleaned += count;
-    save_stats(&stats)
+    update_stats(|stats| stats.total_broken_cleaned += count)
 }
@@ -0,0 +1,536 @@
+//! Git-backed config sync.
+//!
+//! When on, the data root is a git working copy committed to, pulled from and pushed to a
+//! configured remote. A managed `.gitignore` keeps machine-specific files out of it (settings
+//! with their repo roots, discovery roots, Claude homes, servers with their key paths, stats,
+//! snapshots, trash, adopted skills, backups) and project paths are stored home-relative, so
+//! what is committed is the same on every machine. Secrets live in the Keychain and never
+//! reach the data root.
+
+use crate::commands::profiles::PROFILE_SCHEMA;
+use crate::commands::projects::update_projects;
+use crate::commands::settings::{data_dir, load_settings, update_stored_settings, SETTINGS_CHANGED_EVENT};
+use crate::error::AppError;
+use crate::models::{
+    AppSettings, ConflictResolution, Profile, ProfileConflict, SyncCommitResult, SyncConflict, SyncMergeResult,
+    SyncSettings, SyncStatus,
+};
+use crate::store;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+use tauri::{AppHandle, Emitter};
+
+const REMOTE_NAME: &str = "origin";
+
+const GITIGNORE: &str = "\
+# Managed by SkillPilot: machine-specific files are not synced
+.lock
+*.bak
+*.tmp
+config.json
+discovery.json
+homes.json
+remotes.json
+stats.json
+stats-*.json
+trash.json
+snapshots/
+trash/
+adopted/
+backups/
+";
+
+fn git_output(dir: &Path, args: &[&str]) -> Result<Output, AppError> {
+    Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .map_err(|e| AppError::Custom(format!("Failed to run git: {}", e)))
+}
+
+fn git_error(args: &[&str], output: &Output) -> AppError {
+    let command = args.iter().find(|a| !a.starts_with('-') && !a.contains('=')).unwrap_or(&"");
+    AppError::Custom(format!(
+        "git {} failed: {}",
+        command,
+        String::from_utf8_lossy(&output.stderr).trim()
+    ))
+}
+
+/// Run git in `dir` and return its output, failing with its error output
+fn git(dir: &Path, args: &[&str]) -> Result<String, AppError> {
+    let output = git_output(dir, args)?;
+    if !output.status.success() {
+        return Err(git_error(args, &output));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim_end().to_string())
+}
+
+fn lines(output: String) -> Vec<String> {
+    output.lines().filter(|l| !l.is_empty()).map(str::to_string).collect()
+}
+
+/// `args` preceded by a fallback identity when git has none configured, for commands
+/// that create commits
+fn with_identity<'a>(dir: &Path, args: &[&'a str]) -> Vec<&'a str> {
+    let mut all = Vec::new();
+    if git(dir, &["config", "user.email"]).is_err() {
+        all.extend(["-c", "user.name=SkillPilot", "-c", "user.email=skillpilot@localhost"]);
+    }
+    all.extend_from_slice(args);
+    all
+}
+
+/// Refuse a branch name git would not accept, or one that would be read as an option
+fn check_branch(dir: &Path, branch: &str) -> Result<(), AppError> {
+    if branch.starts_with('-') || git(dir, &["check-ref-format", "--branch", branch]).is_err() {
+        return Err(AppError::Custom(format!("Invalid branch name: {:?}", branch)));
+    }
+    Ok(())
+}
+
+fn head(dir: &Path) -> Option<String> {
+    git(dir, &["rev-parse", "--verify", "-q", "HEAD"]).ok()
+}
+
+fn is_merging(dir: &Path) -> bool {
+    dir.join(".git").join("MERGE_HEAD").exists()
+}
+
+fn ensure_not_merging(dir: &Path) -> Result<(), AppError> {
+    if is_merging(dir) {
+        return Err(AppError::Custom(
+            "A pull is waiting for its conflicts to be resolved".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Files with uncommitted changes, relative to the data root
+fn changed_files(dir: &Path) -> Result<Vec<String>, AppError> {
+    let status = git(dir, &["-c", "core.quotePath=false", "status", "--porcelain", "--untracked-files=all"])?;
+    Ok(lines(status).into_iter().filter_map(|l| l.get(3..).map(str::to_string)).collect())
+}
+
+fn conflicted_paths(dir: &Path) -> Result<Vec<String>, AppError> {
+    Ok(lines(git(
+        dir,
+        &["-c", "core.quotePath=false", "diff", "--name-only", "--diff-filter=U"],
+    )?))
+}
+
+/// The data root and sync settings, when config sync is on
+fn sync_root() -> Result<(PathBuf, SyncSettings), AppError> {
+    let sync = load_settings()?.sync;
+    if !sync.enabled {
+        return Err(AppError::Custom("Config sync is not enabled".to_string()));
+    }
+    let dir = data_dir()?;
+    if !dir.join(".git").exists() {
+        return Err(AppError::Custom(
+            "The data root is not a git repository; enable config sync again".to_string(),
+        ));
+    }
+    Ok((dir, sync))
+}
+
+/// Point the sync remote at the configured URL
+fn configure_remote(dir: &Path, sync: &SyncSettings) -> Result<(), AppError> {
+    let url = sync
+        .remote
+        .as_deref()
+        .ok_or_else(|| AppError::Custom("No sync remote configured".to_string()))?;
+    if git(dir, &["remote", "get-url", REMOTE_NAME]).is_ok() {
+        git(dir, &["remote", "set-url", REMOTE_NAME, url])?;
+    } else {
+        git(dir, &["remote", "add", REMOTE_NAME, url])?;
+    }
+    Ok(())
+}
+
+fn write_gitignore(dir: &Path) -> Result<(), AppError> {
+    let path = dir.join(".gitignore");
+    if fs::read_to_string(&path).ok().as_deref() != Some(GITIGNORE) {
+        fs::write(&path, GITIGNORE)?;
+    }
+    Ok(())
+}
+
+fn sync_status(dir: &Path, sync: &SyncSettings) -> Result<SyncStatus, AppError> {
+    let mut status = SyncStatus {
+        enabled: sync.enabled,
+        initialized: dir.join(".git").exists(),
+        remote: sync.remote.clone(),
+        branch: sync.branch.clone(),
+        head: None,
+        changed_files: Vec::new(),
+        ahead: 0,
+        behind: 0,
+        merging: false,
+        conflicts: Vec::new(),
+    };
+    if !status.initialized {
+        return Ok(status);
+    }
+    status.head = head(dir);
+    status.changed_files = changed_files(dir)?;
+    let range = format!("HEAD...{}/{}", REMOTE_NAME, sync.branch);
+    if let Ok(counts) = git(dir, &["rev-list", "--left-right", "--count", &range]) {
+        let mut counts = counts.split_whitespace().map(|n| n.parse().unwrap_or(0));
+        status.ahead = counts.next().unwrap_or(0);
+        status.behind = counts.next().unwrap_or(0);
+    }
+    status.merging = is_merging(dir);
+    status.conflicts = conflicted_paths(dir)?;
+    Ok(status)
+}
+
+/// A file as it is in one stage of a conflicted merge (1 base, 2 ours, 3 theirs)
+fn show_stage(dir: &Path, stage: u8, path: &str) -> Option<String> {
+    let output = git_output(dir, &["show", &format!(":{}:{}", stage, path)]).ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// Top-level fields that differ between two versions of a profile
+fn changed_fields(ours: &Profile, theirs: &Profile) -> Vec<String> {
+    let (Ok(serde_json::Value::Object(ours)), Ok(serde_json::Value::Object(theirs))) =
+        (serde_json::to_value(ours), serde_json::to_value(theirs))
+    else {
+        return Vec::new();
+    };
+    ours.iter()
+        .filter(|(key, value)| theirs.get(*key) != Some(*value))
+        .map(|(key, _)| key.clone())
+        .chain(theirs.keys().filter(|key| !ours.contains_key(*key)).cloned())
+        .collect()
+}
+
+fn describe_conflict(dir: &Path, path: &str) -> SyncConflict {
+    let base = show_stage(dir, 1, path);
+    let ours = show_stage(dir, 2, path);
+    let theirs = show_stage(dir, 3, path);
+    let profile = path
+        .strip_prefix("profiles/")
+        .and_then(|name| name.strip_suffix(".json"))
+        .filter(|id| !id.contains('/'))
+        .map(|id| {
+            let parse = |content: &Option<String>| {
+                content
+                    .as_deref()
+                    .and_then(|c| store::parse_json::<Profile>(&dir.join(path), c, &PROFILE_SCHEMA).ok())
+            };
+            let (ours, theirs) = (parse(&ours), parse(&theirs));
+            let changed_fields = match (&ours, &theirs) {
+                (Some(o), Some(t)) => changed_fields(o, t),
+                _ => Vec::new(),
+            };
+            ProfileConflict {
+                profile_id: id.to_string(),
+                ours,
+                theirs,
+                changed_fields,
+            }
+        });
+    SyncConflict {
+        path: path.to_string(),
+        base,
+        ours,
+        theirs,
+        profile,
+    }
+}
+
+/// The merge state after a pull or a resolution: the remaining conflicts, or the commit
+/// once there are none
+fn merge_state(dir: &Path, updated_files: Vec<String>) -> Result<SyncMergeResult, AppError> {
+    let conflicts: Vec<SyncConflict> = conflicted_paths(dir)?
+        .iter()
+        .map(|path| describe_conflict(dir, path))
+        .collect();
+    let commit = if conflicts.is_empty() { head(dir) } else { None };
+    Ok(SyncMergeResult {
+        updated_files,
+        conflicts,
+        commit,
+    })
+}
+
+fn emit_settings(app: &AppHandle, settings: &AppSettings) {
+    let _ = app.emit(SETTINGS_CHANGED_EVENT, settings.clone());
+}
+
+/// Get the state of config sync and of the working copy
+#[tauri::command]
+pub fn get_sync_status() -> Result<SyncStatus, AppError> {
+    sync_status(&data_dir()?, &load_settings()?.sync)
+}
+
+/// Turn config sync on: make the data root a git repository (committing what is there),
+/// with the given remote and branch when set
+#[tauri::command]
+pub fn enable_sync(app: AppHandle, remote: Option<String>, branch: Option<String>) -> Result<SyncStatus, AppError> {
+    let dir = data_dir()?;
+    let remote = remote.map(|r| r.trim().to_string());
+    if let Some(remote) = remote.as_ref().filter(|r| r.starts_with('-')) {
+        return Err(AppError::Custom(format!("Invalid sync remote: {:?}", remote)));
+    }
+    let branch = branch.map(|b| b.trim().to_string()).filter(|b| !b.is_empty());
+    if let Some(branch) = &branch {
+        check_branch(&dir, branch)?;
+    }
+    let settings = update_stored_settings(|settings| {
+        settings.sync.enabled = true;
+        if let Some(remote) = remote {
+            settings.sync.remote = Some(remote).filter(|r| !r.is_empty());
+        }
+        if let Some(branch) = branch {
+            settings.sync.branch = branch;
+        }
+        Ok(settings.clone())
+    })?;
+    let sync = &settings.sync;
+
+    if !dir.join(".git").exists() {
+        git(&dir, &["init", "-q"])?;
+        git(&dir, &["symbolic-ref", "HEAD", &format!("refs/heads/{}", sync.branch)])?;
+    }
+    write_gitignore(&dir)?;
+    if sync.remote.is_some() {
+        configure_remote(&dir, sync)?;
+    }
+    // Store the project paths home-relative
+    if dir.join("projects.json").exists() {
+        update_projects(|_| Ok(()))?;
+    }
+    if head(&dir).is_none() {
+        git(&dir, &["add", "-A"])?;
+        git(
+            &dir,
+            &with_identity(&dir, &["commit", "-q", "--allow-empty", "-m", "Start SkillPilot config sync"]),
+        )?;
+    }
+
+    emit_settings(&app, &settings);
+    sync_status(&dir, sync)
+}
+
+/// Turn config sync off. The repository is kept so sync can be turned back on.
+#[tauri::command]
+pub fn disable_sync(app: AppHandle) -> Result<SyncStatus, AppError> {
+    let settings = update_stored_settings(|settings| {
+        settings.sync.enabled = false;
+        Ok(settings.clone())
+    })?;
+    let dir = data_dir()?;
+    // Store the project paths absolute again
+    if dir.join("projects.json").exists() {
+        update_projects(|_| Ok(()))?;
+    }
+    emit_settings(&app, &settings);
+    sync_status(&dir, &settings.sync)
+}
+
+/// Commit every change in the data root that is synced
+#[tauri::command]
+pub fn sync_commit(message: Option<String>) -> Result<SyncCommitResult, AppError> {
+    let (dir, _) = sync_root()?;
+    ensure_not_merging(&dir)?;
+    write_gitignore(&dir)?;
+    git(&dir, &["add", "-A"])?;
+    let files = lines(git(&dir, &["-c", "core.quotePath=false", "diff", "--cached", "--name-only"])?);
+    if files.is_empty() {
+        return Ok(SyncCommitResult { commit: None, files });
+    }
+    let message = message
+        .map(|m| m.trim().to_string())
+        .filter(|m| !m.is_empty())
+        .unwrap_or_else(|| format!("Update SkillPilot config ({} files)", files.len()));
+    git(&dir, &with_identity(&dir, &["commit", "-q", "-m", &message]))?;
+    Ok(SyncCommitResult {
+        commit: head(&dir),
+        files,
+    })
+}
+
+/// Fetch the remote branch and merge it. Conflicting files are left for
+/// resolve_sync_conflict and returned with both sides (parsed, for profiles).
+#[tauri::command]
+pub fn sync_pull() -> Result<SyncMergeResult, AppError> {
+    let (dir, sync) = sync_root()?;
+    ensure_not_merging(&dir)?;
+    if !changed_files(&dir)?.is_empty() {
+        return Err(AppError::Custom("Commit local changes before pulling".to_string()));
+    }
+    configure_remote(&dir, &sync)?;
+    if git(&dir, &["ls-remote", "--heads", REMOTE_NAME, &sync.branch])?.is_empty() {
+        // Nothing pushed to the remote yet
+        return merge_state(&dir, Vec::new());
+    }
+    git(&dir, &["fetch", "-q", REMOTE_NAME, &sync.branch])?;
+
+    let incoming = format!("{}/{}", REMOTE_NAME, sync.branch);
+    let local = head(&dir).unwrap_or_else(|| "HEAD".to_string());
+    let since = git(&dir, &["merge-base", &local, &incoming]).unwrap_or(local);
+    let updated_files = lines(git(
+        &dir,
+        &["-c", "core.quotePath=false", "diff", "--name-only", &since, &incoming],
+    )?);
+
+    let args = with_identity(&dir, &["merge", "-q", "--no-edit", "--allow-unrelated-histories", &incoming]);
+    let output = git_output(&dir, &args)?;
+    if !output.status.success() && conflicted_paths(&dir)?.is_empty() {
+        let _ = git(&dir, &["merge", "--abort"]);
+        return Err(git_error(&args, &output));
+    }
+    merge_state(&dir, updated_files)
+}
+
+/// Resolve a conflicted file of a pull to one side; the merge is committed once no
+/// conflicts remain
+#[tauri::command]
+pub fn resolve_sync_conflict(path: String, resolution: ConflictResolution) -> Result<SyncMergeResult, AppError> {
+    let (dir, _) = sync_root()?;
+    if !conflicted_paths(&dir)?.contains(&path) {
+        return Err(AppError::Custom(format!("No conflict on {}", path)));
+    }
+    let (stage, side) = match resolution {
+        ConflictResolution::Ours => (2, "--ours"),
+        ConflictResolution::Theirs => (3, "--theirs"),
+    };
+    if show_stage(&dir, stage, &path).is_some() {
+        git(&dir, &["checkout", side, "--", &path])?;
+        git(&dir, &["add", "--", &path])?;
+    } else {
+        // Deleted on the chosen side
+        git(&dir, &["rm", "-q", "--", &path])?;
+    }
+    if conflicted_paths(&dir)?.is_empty() {
+        git(&dir, &with_identity(&dir, &["commit", "-q", "--no-edit"]))?;
+    }
+    merge_state(&dir, Vec::new())
+}
+
+/// Abandon a pull that stopped on conflicts, restoring the state before it
+#[tauri::command]
+pub fn abort_sync_merge() -> Result<SyncStatus, AppError> {
+    let (dir, sync) = sync_root()?;
+    if is_merging(&dir) {
+        git(&dir, &["merge", "--abort"])?;
+    }
+    sync_status(&dir, &sync)
+}
+
+/// Push the committed config to the remote branch
+#[tauri::command]
+pub fn sync_push() -> Result<SyncStatus, AppError> {
+    let (dir, sync) = sync_root()?;
+    ensure_not_merging(&dir)?;
+    configure_remote(&dir, &sync)?;
+    let refspec = format!("HEAD:refs/heads/{}", sync.branch);
+    let args = ["push", "-q", REMOTE_NAME, refspec.as_str()];
+    let output = git_output(&dir, &args)?;
+    if !output.status.success() {
+        if String::from_utf8_lossy(&output.stderr).contains("[rejected]") {
+            return Err(AppError::Custom(
+                "The remote has changes that are not pulled yet; pull first".to_string(),
+            ));
+        }
+        return Err(git_error(&args, &output));
+    }
+    // Bring the remote-tracking branch up to date for ahead/behind
+    git(&dir, &["fetch", "-q", REMOTE_NAME, &sync.branch])?;
+    sync_status(&dir, &sync)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ProfileKind;
+    use std::collections::HashMap;
+
+    fn profile(name: &str, skill_ids: &[&str]) -> Profile {
+        Profile {
+            id: "web".to_string(),
+            name: name.to_string(),
+            description: String::new(),
+            color: "#3b82f6".to_string(),
+            skill_ids: skill_ids.iter().map(|s| s.to_string()).collect(),
+            is_preset: false,
+            aliases: HashMap::new(),
+            extends: Vec::new(),
+            excludes: Vec::new(),
+            kind: ProfileKind::Static,
+            query: None,
+            conditions: HashMap::new(),
+            resolved: None,
+        }
+    }
+
+    fn commit_profile(dir: &Path, profile: &Profile, message: &str) {
+        fs::write(
+            dir.join("profiles/web.json"),
+            store::envelope(&PROFILE_SCHEMA, profile).unwrap(),
+        )
+        .unwrap();
+        git(dir, &["add", "-A"]).unwrap();
+        git(dir, &with_identity(dir, &["commit", "-q", "-m", message])).unwrap();
+    }
+
+    #[test]
+    fn changed_fields_lists_fields_that_differ_on_either_side() {
+        let ours = profile("Web", &["a", "b"]);
+        assert!(changed_fields(&ours, &ours.clone()).is_empty());
+
+        let mut theirs = profile("Frontend", &["a", "b"]);
+        theirs.extends = vec!["base".to_string()];
+        let mut fields = changed_fields(&ours, &theirs);
+        fields.sort();
+        assert_eq!(fields, vec!["extends", "name"]);
+    }
+
+    #[test]
+    fn check_branch_refuses_invalid_names_and_options() {
+        let dir = std::env::temp_dir();
+        assert!(check_branch(&dir, "main").is_ok());
+        assert!(check_branch(&dir, "team/config").is_ok());
+        for bad in ["-f", "a..b", "with space", ""] {
+            assert!(check_branch(&dir, bad).is_err(), "{:?}", bad);
+        }
+    }
+
+    #[test]
+    fn profile_conflicts_are_described_field_by_field() {
+        let dir = std::env::temp_dir().join(format!("skillpilot-sync-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(dir.join("profiles")).unwrap();
+        git(&dir, &["init", "-q", "-b", "main"]).unwrap();
+        commit_profile(&dir, &profile("Web", &["a"]), "base");
+        git(&dir, &["checkout", "-q", "-b", "incoming"]).unwrap();
+        commit_profile(&dir, &profile("Frontend", &["a", "b"]), "theirs");
+        git(&dir, &["checkout", "-q", "main"]).unwrap();
+        commit_profile(&dir, &profile("Web app", &["a"]), "ours");
+
+        let merge = git_output(&dir, &with_identity(&dir, &["merge", "-q", "incoming"])).unwrap();
+        assert!(!merge.status.success());
+        let result = merge_state(&dir, Vec::new()).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(result.commit, None);
+        let [conflict] = result.conflicts.as_slice() else {
+            panic!("expected one conflict: {:?}", result.conflicts);
+        };
+        assert_eq!(conflict.path, "profiles/web.json");
+        assert!(conflict.base.as_deref().is_some_and(|b| b.contains("\"Web\"")));
+        let profile = conflict.profile.as_ref().unwrap();
+        assert_eq!(profile.profile_id, "web");
+        assert_eq!(profile.ours.as_ref().unwrap().name, "Web app");
+        assert_eq!(profile.theirs.as_ref().unwrap().skill_ids, vec!["a", "b"]);
+        let mut fields = profile.changed_fields.clone();
+        fields.sort();
+        assert_eq!(fields, vec!["name", "skill_ids"]);
+    }
+}
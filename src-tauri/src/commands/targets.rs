@@ -0,0 +1,475 @@
+use crate::commands::linker::{create_skill_link, remove_skill_link, user_skills_dir};
+use crate::commands::scanner::parse_frontmatter;
+use crate::commands::settings::{self, data_dir};
+use crate::error::AppError;
+use crate::models::{AgentKind, LinkMode, LinkTarget, SkillFrontmatter, TargetLayout, TargetScope};
+use crate::store::{self, Schema};
+use serde::Serialize;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// First line of the body of generated single-file entries; marks them as ours
+const MANAGED_MARKER: &str = "<!-- skillpilot-source:";
+
+/// Schema of targets.json
+const TARGETS_SCHEMA: Schema = Schema {
+    version: 1,
+    migrations: &[store::unversioned],
+};
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub enum TargetEntryStatus {
+    /// Link/file exists and is up to date
+    Active,
+    /// Symlink target or generated file's source is gone
+    Broken,
+    /// Generated file exists but differs from the current SKILL.md
+    Stale,
+    /// Nothing at the expected location
+    Inactive,
+    /// Something not managed by SkillPilot occupies the location
+    Unmanaged,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TargetEntryState {
+    pub name: String,
+    /// Path of the link or generated file
+    pub path: String,
+    pub status: TargetEntryStatus,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TargetStatusReport {
+    pub target_id: String,
+    /// Resolved directory
+    pub dir: String,
+    /// Status of each requested skill
+    pub entries: Vec<TargetEntryState>,
+    /// Managed entries present in the directory that were not requested
+    pub extra: Vec<TargetEntryState>,
+}
+
+fn targets_path() -> Result<PathBuf, AppError> {
+    let dir = data_dir()?;
+    Ok(dir.join("targets.json"))
+}
+
+fn load_user_targets() -> Result<Vec<LinkTarget>, AppError> {
+    Ok(store::read_json(&targets_path()?, &TARGETS_SCHEMA)?.unwrap_or_default())
+}
+
+/// Change the user targets under the config lock
+fn update_user_targets(f: impl FnOnce(&mut Vec<LinkTarget>)) -> Result<(), AppError> {
+    store::update_json(&targets_path()?, &TARGETS_SCHEMA, |targets| {
+        f(targets);
+        Ok(())
+    })
+}
+
+pub(crate) fn find_target(id: &str) -> Result<LinkTarget, AppError> {
+    list_link_targets()?
+        .into_iter()
+        .find(|t| t.id == id)
+        .ok_or_else(|| AppError::Custom(format!("Link target not found: {}", id)))
+}
+
+/// Resolve a target's directory for the given project (project scope) or user
+pub(crate) fn target_dir(target: &LinkTarget, project_path: &Option<String>) -> Result<PathBuf, AppError> {
+    match target.scope {
+        // The built-in Claude Code target follows the selected Claude home
+        TargetScope::User if target.is_builtin && target.agent == AgentKind::ClaudeCode => user_skills_dir(),
+        TargetScope::User => Ok(PathBuf::from(shellexpand::tilde(&target.path).to_string())),
+        TargetScope::Project => match project_path {
+            Some(p) => Ok(PathBuf::from(p).join(&target.path)),
+            None => Err(AppError::Custom(format!(
+                "Target '{}' is project-scoped; a project path is required",
+                target.id
+            ))),
+        },
+    }
+}
+
+/// Frontmatter of a generated single-file skill
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SingleFileFrontmatter {
+    description: String,
+    globs: Option<String>,
+    always_apply: bool,
+}
+
+/// Generate the single-file form of a skill: frontmatter for the agent, a source marker, then the body
+fn render_single_file(source_path: &Path) -> Result<String, AppError> {
+    let content = fs::read_to_string(source_path.join("SKILL.md"))?;
+    let frontmatter: SkillFrontmatter = parse_frontmatter(&content);
+    let body = if content.starts_with("---") {
+        content.splitn(3, "---").nth(2).unwrap_or("").trim_start()
+    } else {
+        content.as_str()
+    };
+    let header = serde_yaml::to_string(&SingleFileFrontmatter {
+        description: frontmatter.description.unwrap_or_default().replace('\n', " "),
+        globs: None,
+        always_apply: false,
+    })?;
+
+    Ok(format!(
+        "---\n{}---\n{} {} -->\n{}",
+        header,
+        MANAGED_MARKER,
+        source_path.display(),
+        body
+    ))
+}
+
+/// Source path recorded in a generated file, if it is one of ours
+fn managed_source(content: &str) -> Option<PathBuf> {
+    content
+        .lines()
+        .find_map(|l| l.strip_prefix(MANAGED_MARKER))
+        .map(|rest| PathBuf::from(rest.trim_end_matches("-->").trim()))
+}
+
+fn entry_path(dir: &Path, layout: &TargetLayout, name: &str) -> PathBuf {
+    match layout {
+        TargetLayout::DirectorySymlink => dir.join(name),
+        TargetLayout::SingleFile { extension } => dir.join(format!("{}.{}", name, extension)),
+    }
+}
+
+fn entry_status(path: &Path, layout: &TargetLayout, source: Option<&Path>) -> TargetEntryStatus {
+    let meta = match path.symlink_metadata() {
+        Ok(m) => m,
+        Err(_) => return TargetEntryStatus::Inactive,
+    };
+    match layout {
+        TargetLayout::DirectorySymlink => {
+            if !meta.file_type().is_symlink() {
+                TargetEntryStatus::Unmanaged
+            } else if path.exists() {
+                TargetEntryStatus::Active
+            } else {
+                TargetEntryStatus::Broken
+            }
+        }
+        TargetLayout::SingleFile { .. } => {
+            let content = match fs::read_to_string(path) {
+                Ok(c) => c,
+                Err(_) => return TargetEntryStatus::Unmanaged,
+            };
+            let recorded = match managed_source(&content) {
+                Some(s) => s,
+                None => return TargetEntryStatus::Unmanaged,
+            };
+            let source = source.map(Path::to_path_buf).unwrap_or(recorded);
+            match render_single_file(&source) {
+                Ok(expected) if expected == content => TargetEntryStatus::Active,
+                Ok(_) => TargetEntryStatus::Stale,
+                Err(_) => TargetEntryStatus::Broken,
+            }
+        }
+    }
+}
+
+/// Names of managed entries currently in a target directory
+fn managed_entries(dir: &Path, layout: &TargetLayout) -> Result<Vec<(String, PathBuf)>, AppError> {
+    let mut found = Vec::new();
+    if !dir.is_dir() {
+        return Ok(found);
+    }
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let file_name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        match layout {
+            TargetLayout::DirectorySymlink => {
+                if path.symlink_metadata().is_ok_and(|m| m.file_type().is_symlink()) {
+                    found.push((file_name, path));
+                }
+            }
+            TargetLayout::SingleFile { extension } => {
+                let name = match file_name.strip_suffix(&format!(".{}", extension)) {
+                    Some(n) => n.to_string(),
+                    None => continue,
+                };
+                let ours = fs::read_to_string(&path)
+                    .map(|c| managed_source(&c).is_some())
+                    .unwrap_or(false);
+                if ours {
+                    found.push((name, path));
+                }
+            }
+        }
+    }
+    Ok(found)
+}
+
+fn remove_entry(dir: &Path, layout: &TargetLayout, name: &str) -> Result<(), AppError> {
+    match layout {
+        TargetLayout::DirectorySymlink => remove_skill_link(name, dir),
+        TargetLayout::SingleFile { .. } => {
+            let path = entry_path(dir, layout, name);
+            match fs::read_to_string(&path) {
+                Ok(c) if managed_source(&c).is_some() => Ok(fs::remove_file(&path)?),
+                Ok(_) => Err(AppError::Custom(format!(
+                    "Not managed by SkillPilot, refusing to remove: {}",
+                    path.display()
+                ))),
+                Err(_) => Ok(()),
+            }
+        }
+    }
+}
+
+/// List link targets (built-ins + user targets, user entries take priority)
+#[tauri::command]
+pub fn list_link_targets() -> Result<Vec<LinkTarget>, AppError> {
+    let mut user_targets = load_user_targets()?;
+
+    let mut targets = Vec::new();
+    for builtin in LinkTarget::builtins() {
+        match user_targets.iter().position(|t| t.id == builtin.id) {
+            Some(idx) => targets.push(user_targets.remove(idx)),
+            None => targets.push(builtin),
+        }
+    }
+    targets.extend(user_targets);
+    Ok(targets)
+}
+
+/// Create or update a link target
+#[tauri::command]
+pub fn save_link_target(target: LinkTarget) -> Result<LinkTarget, AppError> {
+    update_user_targets(|targets| match targets.iter_mut().find(|t| t.id == target.id) {
+        Some(existing) => *existing = target.clone(),
+        None => targets.push(target.clone()),
+    })?;
+    Ok(target)
+}
+
+/// Delete a user target (for built-ins this only removes the override)
+#[tauri::command]
+pub fn delete_link_target(id: String) -> Result<(), AppError> {
+    update_user_targets(|targets| targets.retain(|t| t.id != id))
+}
+
+/// Materialize skills in one target directory using its layout
+fn apply_to_target(
+    target: &LinkTarget,
+    skill_entries: &[(String, String)],
+    project_path: &Option<String>,
+    mode: LinkMode,
+) -> Result<Vec<String>, AppError> {
+    let dir = target_dir(target, project_path)?;
+    fs::create_dir_all(&dir)?;
+
+    let mut created = Vec::new();
+    for (name, source) in skill_entries {
+        let source_path = PathBuf::from(source);
+        let result = match &target.layout {
+            TargetLayout::DirectorySymlink => create_skill_link(name, &source_path, &dir, mode),
+            TargetLayout::SingleFile { .. } => {
+                let path = entry_path(&dir, &target.layout, name);
+                if entry_status(&path, &target.layout, Some(&source_path)) == TargetEntryStatus::Unmanaged {
+                    Err(AppError::Custom(format!(
+                        "Cannot replace unmanaged file: {}",
+                        path.display()
+                    )))
+                } else {
+                    render_single_file(&source_path)
+                        .and_then(|content| Ok(fs::write(&path, content)?))
+                }
+            }
+        };
+        match result {
+            Ok(()) => created.push(name.clone()),
+            Err(e) => eprintln!("Failed to apply {} to {}: {}", name, target.id, e),
+        }
+    }
+
+    Ok(created)
+}
+
+/// Materialize skills in a target directory using its layout; without a target ID, in every
+/// default target of the settings
+#[tauri::command]
+pub fn apply_to_link_target(
+    target_id: Option<String>,
+    skill_entries: Vec<(String, String)>, // (name, source_path) pairs
+    project_path: Option<String>,
+) -> Result<Vec<String>, AppError> {
+    let settings = settings::load_settings()?;
+    let target_ids = match target_id {
+        Some(id) => vec![id],
+        None if settings.target_ids.is_empty() => {
+            return Err(AppError::Custom("No default link targets configured".to_string()))
+        }
+        None => settings.target_ids,
+    };
+
+    let mut created: Vec<String> = Vec::new();
+    for id in &target_ids {
+        let target = find_target(id)?;
+        for name in apply_to_target(&target, &skill_entries, &project_path, settings.default_link_mode)? {
+            if !created.contains(&name) {
+                created.push(name);
+            }
+        }
+    }
+    Ok(created)
+}
+
+/// Remove skills from a target directory
+#[tauri::command]
+pub fn remove_from_link_target(
+    target_id: String,
+    names: Vec<String>,
+    project_path: Option<String>,
+) -> Result<Vec<String>, AppError> {
+    let target = find_target(&target_id)?;
+    let dir = target_dir(&target, &project_path)?;
+
+    let mut removed = Vec::new();
+    for name in names {
+        remove_entry(&dir, &target.layout, &name)?;
+        removed.push(name);
+    }
+    Ok(removed)
+}
+
+/// Report the status of skills in a target directory
+#[tauri::command]
+pub fn get_link_target_status(
+    target_id: String,
+    skill_entries: Vec<(String, String)>,
+    project_path: Option<String>,
+) -> Result<TargetStatusReport, AppError> {
+    let target = find_target(&target_id)?;
+    let dir = target_dir(&target, &project_path)?;
+
+    let entries: Vec<TargetEntryState> = skill_entries
+        .iter()
+        .map(|(name, source)| {
+            let path = entry_path(&dir, &target.layout, name);
+            TargetEntryState {
+                name: name.clone(),
+                status: entry_status(&path, &target.layout, Some(Path::new(source))),
+                path: path.to_string_lossy().to_string(),
+            }
+        })
+        .collect();
+
+    let requested: HashSet<&String> = skill_entries.iter().map(|(n, _)| n).collect();
+    let mut extra: Vec<TargetEntryState> = managed_entries(&dir, &target.layout)?
+        .into_iter()
+        .filter(|(name, _)| !requested.contains(name))
+        .map(|(name, path)| TargetEntryState {
+            status: entry_status(&path, &target.layout, None),
+            name,
+            path: path.to_string_lossy().to_string(),
+        })
+        .collect();
+    extra.sort_by_key(|e| e.name.to_lowercase());
+
+    Ok(TargetStatusReport {
+        target_id,
+        dir: dir.to_string_lossy().to_string(),
+        entries,
+        extra,
+    })
+}
+
+/// Remove broken managed entries from a target directory (or every managed entry with `remove_all`)
+#[tauri::command]
+pub fn clean_link_target(
+    target_id: String,
+    project_path: Option<String>,
+    remove_all: bool,
+) -> Result<Vec<String>, AppError> {
+    let target = find_target(&target_id)?;
+    let dir = target_dir(&target, &project_path)?;
+
+    let mut cleaned = Vec::new();
+    for (name, path) in managed_entries(&dir, &target.layout)? {
+        if remove_all || entry_status(&path, &target.layout, None) == TargetEntryStatus::Broken {
+            remove_entry(&dir, &target.layout, &name)?;
+            cleaned.push(name);
+        }
+    }
+    cleaned.sort();
+    Ok(cleaned)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("skillpilot-targets-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn mdc() -> TargetLayout {
+        TargetLayout::SingleFile {
+            extension: "mdc".to_string(),
+        }
+    }
+
+    #[test]
+    fn single_files_record_their_source() {
+        let root = temp_dir();
+        let skill = root.join("review");
+        fs::create_dir_all(&skill).unwrap();
+        fs::write(skill.join("SKILL.md"), "---\nname: review\ndescription: Review code\n---\n# Review\n").unwrap();
+
+        let rendered = render_single_file(&skill).unwrap();
+        assert!(rendered.starts_with("---\ndescription: Review code\n"));
+        assert!(rendered.ends_with("-->\n# Review\n"));
+        assert_eq!(managed_source(&rendered), Some(skill.clone()));
+        assert_eq!(managed_source("# Hand-written rule"), None);
+
+        let out = root.join("rules");
+        fs::create_dir_all(&out).unwrap();
+        let path = entry_path(&out, &mdc(), "review");
+        assert_eq!(entry_status(&path, &mdc(), None), TargetEntryStatus::Inactive);
+        fs::write(&path, &rendered).unwrap();
+        assert_eq!(entry_status(&path, &mdc(), None), TargetEntryStatus::Active);
+        fs::write(skill.join("SKILL.md"), "# Review v2\n").unwrap();
+        assert_eq!(entry_status(&path, &mdc(), None), TargetEntryStatus::Stale);
+        fs::remove_dir_all(&skill).unwrap();
+        assert_eq!(entry_status(&path, &mdc(), None), TargetEntryStatus::Broken);
+    }
+
+    #[test]
+    fn unmanaged_files_are_neither_listed_nor_removed() {
+        let dir = temp_dir();
+        fs::write(dir.join("mine.mdc"), "# Hand-written rule").unwrap();
+        fs::write(dir.join("ours.mdc"), format!("{} /repo/ours -->\n", MANAGED_MARKER)).unwrap();
+
+        let names: Vec<String> = managed_entries(&dir, &mdc()).unwrap().into_iter().map(|(n, _)| n).collect();
+        assert_eq!(names, vec!["ours"]);
+        assert_eq!(entry_status(&dir.join("mine.mdc"), &mdc(), None), TargetEntryStatus::Unmanaged);
+        assert!(remove_entry(&dir, &mdc(), "mine").is_err());
+        remove_entry(&dir, &mdc(), "ours").unwrap();
+        assert!(!dir.join("ours.mdc").exists());
+    }
+
+    #[test]
+    fn symlink_entries_are_told_apart_from_real_directories() {
+        let dir = temp_dir();
+        fs::create_dir_all(dir.join("real")).unwrap();
+        std::os::unix::fs::symlink(&dir, dir.join("live")).unwrap();
+        std::os::unix::fs::symlink(dir.join("gone"), dir.join("dead")).unwrap();
+
+        let layout = TargetLayout::DirectorySymlink;
+        assert_eq!(entry_status(&dir.join("live"), &layout, None), TargetEntryStatus::Active);
+        assert_eq!(entry_status(&dir.join("dead"), &layout, None), TargetEntryStatus::Broken);
+        assert_eq!(entry_status(&dir.join("real"), &layout, None), TargetEntryStatus::Unmanaged);
+        assert_eq!(entry_status(&dir.join("none"), &layout, None), TargetEntryStatus::Inactive);
+    }
+}
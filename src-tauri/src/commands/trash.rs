@@ -0,0 +1,272 @@
+use crate::commands::remote::{load_remotes, update_remotes};
+use crate::commands::{profiles, projects};
+use crate::commands::settings::{data_dir, data_subdir};
+use crate::error::AppError;
+use crate::models::remote::RemoteServer;
+use crate::models::{Profile, ProjectConfig, TrashItem, TrashKind, TrashSettings};
+use crate::ssh::connection::delete_keychain_password;
+use crate::store::{self, Schema};
+use serde::Serialize;
+use std::fs;
+use std::path::PathBuf;
+
+/// Schema of trash items (trash/<id>.json)
+const ITEM_SCHEMA: Schema = Schema {
+    version: 1,
+    migrations: &[store::unversioned],
+};
+
+/// Schema of trash.json
+const TRASH_SETTINGS_SCHEMA: Schema = Schema {
+    version: 1,
+    migrations: &[store::unversioned],
+};
+
+fn trash_dir() -> Result<PathBuf, AppError> {
+    data_subdir("trash")
+}
+
+fn item_path(id: &str) -> Result<PathBuf, AppError> {
+    Ok(trash_dir()?.join(format!("{}.json", id)))
+}
+
+fn settings_path() -> Result<PathBuf, AppError> {
+    Ok(data_dir()?.join("trash.json"))
+}
+
+fn load_settings() -> Result<TrashSettings, AppError> {
+    Ok(store::read_json(&settings_path()?, &TRASH_SETTINGS_SCHEMA)?.unwrap_or_default())
+}
+
+/// All trash items, newest first; unreadable files are skipped
+fn load_items() -> Result<Vec<TrashItem>, AppError> {
+    let mut items = Vec::new();
+    for entry in fs::read_dir(trash_dir()?)? {
+        let path = entry?.path();
+        if path.extension().is_some_and(|e| e == "json") {
+            if let Ok(Some(item)) = store::read_json::<TrashItem>(&path, &ITEM_SCHEMA) {
+                items.push(item);
+            }
+        }
+    }
+    items.sort_by(|a, b| b.deleted_at.cmp(&a.deleted_at));
+    Ok(items)
+}
+
+fn find_item(id: &str) -> Result<TrashItem, AppError> {
+    load_items()?
+        .into_iter()
+        .find(|i| i.id == id)
+        .ok_or_else(|| AppError::Custom(format!("Trash item not found: {}", id)))
+}
+
+/// Delete a trash item for good. A server's keychain entry goes with it, unless a server
+/// with the same ID has been configured again.
+fn purge_item(item: &TrashItem) -> Result<(), AppError> {
+    if item.kind == TrashKind::RemoteServer && !load_remotes()?.iter().any(|s| s.id == item.record_id) {
+        delete_keychain_password(&item.record_id);
+    }
+    store::remove(&item_path(&item.id)?, &ITEM_SCHEMA)
+}
+
+/// Purge items older than the retention period. A period reaching past the earliest
+/// representable date purges nothing.
+fn purge_expired() -> Result<(), AppError> {
+    let days = load_settings()?.retention_days;
+    if days == 0 {
+        return Ok(());
+    }
+    let cutoff = chrono::TimeDelta::try_days(i64::from(days)).and_then(|d| chrono::Utc::now().checked_sub_signed(d));
+    let Some(cutoff) = cutoff else {
+        return Ok(());
+    };
+    for item in load_items()? {
+        let expired = chrono::DateTime::parse_from_rfc3339(&item.deleted_at).is_ok_and(|t| t < cutoff);
+        if expired {
+            purge_item(&item)?;
+        }
+    }
+    Ok(())
+}
+
+/// Keep a deleted record in the trash
+pub(crate) fn move_to_trash(kind: TrashKind, record_id: &str, name: &str, data: impl Serialize) -> Result<(), AppError> {
+    purge_expired()?;
+    let item = TrashItem {
+        id: uuid::Uuid::new_v4().to_string(),
+        kind,
+        record_id: record_id.to_string(),
+        name: name.to_string(),
+        deleted_at: chrono::Utc::now().to_rfc3339(),
+        data: serde_json::to_value(data)?,
+    };
+    store::write_json(&item_path(&item.id)?, &ITEM_SCHEMA, &item)
+}
+
+/// List deleted profiles, projects and servers, newest first (expired items are purged first)
+#[tauri::command]
+pub fn list_trash() -> Result<Vec<TrashItem>, AppError> {
+    purge_expired()?;
+    load_items()
+}
+
+/// Put a deleted record back; fails if a record with the same ID exists again
+#[tauri::command]
+pub fn restore_from_trash(id: String) -> Result<TrashItem, AppError> {
+    let item = find_item(&id)?;
+    let exists = || AppError::Custom(format!("{:?} {} already exists", item.kind, item.record_id));
+
+    match item.kind {
+        // A profile that did not parse when deleted was kept as its raw file contents
+        TrashKind::Profile => match &item.data {
+            serde_json::Value::String(raw) => {
+                if profiles::user_profile_exists(&item.record_id)? {
+                    return Err(exists());
+                }
+                profiles::restore_raw_profile(&item.record_id, raw)?;
+            }
+            data => {
+                let profile: Profile = serde_json::from_value(data.clone())?;
+                if profiles::user_profile_exists(&profile.id)? {
+                    return Err(exists());
+                }
+                profiles::save_profile(profile)?;
+            }
+        },
+        TrashKind::Project => {
+            let project: ProjectConfig = serde_json::from_value(item.data.clone())?;
+            projects::update_projects(|all| {
+                if all.iter().any(|p| p.id == project.id) {
+                    return Err(exists());
+                }
+                all.push(project);
+                Ok(())
+            })?;
+        }
+        TrashKind::RemoteServer => {
+            let server: RemoteServer = serde_json::from_value(item.data.clone())?;
+            update_remotes(|servers| {
+                if servers.iter().any(|s| s.id == server.id) {
+                    return Err(exists());
+                }
+                servers.push(server);
+                Ok(())
+            })?;
+        }
+    }
+
+    store::remove(&item_path(&item.id)?, &ITEM_SCHEMA)?;
+    Ok(item)
+}
+
+/// Permanently delete one trash item
+#[tauri::command]
+pub fn purge_trash_item(id: String) -> Result<(), AppError> {
+    purge_item(&find_item(&id)?)
+}
+
+/// Permanently delete everything in the trash
+#[tauri::command]
+pub fn empty_trash() -> Result<(), AppError> {
+    for item in load_items()? {
+        purge_item(&item)?;
+    }
+    Ok(())
+}
+
+/// Get trash settings (retention period)
+#[tauri::command]
+pub fn get_trash_settings() -> Result<TrashSettings, AppError> {
+    load_settings()
+}
+
+/// Save trash settings and purge what the new retention period expires
+#[tauri::command]
+pub fn save_trash_settings(settings: TrashSettings) -> Result<TrashSettings, AppError> {
+    store::write_json(&settings_path()?, &TRASH_SETTINGS_SCHEMA, &settings)?;
+    purge_expired()?;
+    Ok(settings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::settings::use_temp_data_root;
+
+    fn trashed(record_id: &str) -> TrashItem {
+        list_trash().unwrap().into_iter().find(|i| i.record_id == record_id).unwrap()
+    }
+
+    fn project(id: &str) -> ProjectConfig {
+        ProjectConfig {
+            id: id.to_string(),
+            name: "Web".to_string(),
+            path: PathBuf::from("/work/web"),
+            profile_ids: vec!["frontend".to_string()],
+            extra_skill_ids: Vec::new(),
+            aliases: Default::default(),
+            tags: Vec::new(),
+            link_winners: Default::default(),
+        }
+    }
+
+    #[test]
+    fn restore_puts_a_raw_profile_back_once() {
+        use_temp_data_root();
+        let id = format!("raw-{}", uuid::Uuid::new_v4());
+
+        move_to_trash(TrashKind::Profile, &id, "Raw", "{ not json").unwrap();
+        let restored = restore_from_trash(trashed(&id).id).unwrap();
+        assert_eq!(restored.record_id, id);
+        let file = data_dir().unwrap().join("profiles").join(format!("{}.json", id));
+        assert_eq!(fs::read_to_string(file).unwrap(), "{ not json");
+        assert!(list_trash().unwrap().iter().all(|i| i.record_id != id));
+
+        // The profile exists again, so a second copy stays in the trash
+        move_to_trash(TrashKind::Profile, &id, "Raw", "{ other").unwrap();
+        let item = trashed(&id);
+        let err = restore_from_trash(item.id.clone()).unwrap_err().to_string();
+        assert!(err.contains("already exists"), "{}", err);
+        assert_eq!(find_item(&item.id).unwrap().data, "{ other");
+    }
+
+    #[test]
+    fn restore_adds_a_project_back_to_the_list() {
+        use_temp_data_root();
+        let id = format!("project-{}", uuid::Uuid::new_v4());
+
+        move_to_trash(TrashKind::Project, &id, "Web", project(&id)).unwrap();
+        restore_from_trash(trashed(&id).id).unwrap();
+        let restored = projects::load_projects().unwrap().into_iter().find(|p| p.id == id).unwrap();
+        assert_eq!(restored.profile_ids, vec!["frontend"]);
+
+        move_to_trash(TrashKind::Project, &id, "Web", project(&id)).unwrap();
+        assert!(restore_from_trash(trashed(&id).id).is_err());
+    }
+
+    #[test]
+    fn listing_purges_items_past_the_retention_period() {
+        use_temp_data_root();
+        let old_id = format!("old-{}", uuid::Uuid::new_v4());
+        let new_id = format!("new-{}", uuid::Uuid::new_v4());
+
+        move_to_trash(TrashKind::Profile, &new_id, "New", "{}").unwrap();
+        let old = TrashItem {
+            id: uuid::Uuid::new_v4().to_string(),
+            kind: TrashKind::Profile,
+            record_id: old_id.clone(),
+            name: "Old".to_string(),
+            deleted_at: (chrono::Utc::now() - chrono::TimeDelta::days(31)).to_rfc3339(),
+            data: serde_json::Value::Null,
+        };
+        store::write_json(&item_path(&old.id).unwrap(), &ITEM_SCHEMA, &old).unwrap();
+
+        let items = list_trash().unwrap();
+        assert!(items.iter().any(|i| i.record_id == new_id));
+        assert!(items.iter().all(|i| i.record_id != old_id));
+        assert!(!item_path(&old.id).unwrap().exists());
+
+        purge_trash_item(trashed(&new_id).id).unwrap();
+        assert!(list_trash().unwrap().iter().all(|i| i.record_id != new_id));
+    }
+}
@@ -0,0 +1,336 @@
+use crate::commands::profiles::{self, resolve_profile};
+use crate::commands::resolver::{find_skill, profile_link_name};
+use crate::commands::scanner;
+use crate::commands::settings::load_settings;
+use crate::error::AppError;
+use crate::models::{Profile, Skill};
+use serde::Serialize;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type")]
+pub enum ProfileIssue {
+    /// A declared skill ID matches nothing in the catalog
+    UnknownSkill {
+        skill_id: String,
+        /// Closest catalog IDs, best first
+        suggestions: Vec<String>,
+    },
+    /// Several declared entries resolve to the same catalog skill
+    Duplicate { skill_id: String, entries: Vec<String> },
+    /// Different skills of the resolved profile share a link name; only `kept` gets linked
+    LinkNameCollision {
+        link_name: String,
+        skill_ids: Vec<String>,
+        kept: String,
+    },
+}
+
+/// What the automatic fix-up changes in a profile's declared skills
+#[derive(Debug, Clone, Serialize, Default)]
+pub struct ProfileFix {
+    /// (unknown ID, replacement) pairs
+    pub replaced: Vec<(String, String)>,
+    /// Duplicate or shadowed entries that are dropped
+    pub removed: Vec<String>,
+    /// Skill IDs after the fix-up
+    pub skill_ids: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ProfileValidation {
+    pub profile_id: String,
+    pub is_preset: bool,
+    pub issues: Vec<ProfileIssue>,
+    /// Automatic fix-up, when one would change anything
+    pub fix: Option<ProfileFix>,
+}
+
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut prev = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let cur = row[j + 1];
+            row[j + 1] = if ca == *cb {
+                prev
+            } else {
+                1 + prev.min(row[j]).min(row[j + 1])
+            };
+            prev = cur;
+        }
+    }
+    row[b.len()]
+}
+
+fn leaf(id: &str) -> &str {
+    id.rsplit(['/', ':']).next().unwrap_or(id)
+}
+
+/// Catalog IDs close to an unknown ID with their distance, best first. IDs are compared
+/// case-insensitively in full and by last path segment; an ID naming a directory above skills
+/// matches the skills inside it.
+fn closest_matches(sid: &str, skills: &[Skill]) -> Vec<(usize, String)> {
+    let needle = sid.to_lowercase();
+    let needle_leaf = leaf(&needle);
+    let threshold = (needle_leaf.chars().count() / 3).max(1);
+
+    let mut scored: Vec<(usize, String)> = skills
+        .iter()
+        .filter_map(|skill| {
+            let id = skill.id.to_lowercase();
+            let score = if id.starts_with(&format!("{}/", needle)) {
+                1
+            } else {
+                levenshtein(&needle, &id)
+                    .min(levenshtein(needle_leaf, leaf(&id)))
+                    .min(levenshtein(needle_leaf, &skill.name.to_lowercase()))
+            };
+            (score <= threshold).then(|| (score, skill.id.clone()))
+        })
+        .collect();
+    scored.sort();
+    scored
+}
+
+/// Up to `max` catalog IDs close to an unknown ID, best first
+fn suggestions(sid: &str, skills: &[Skill], max: usize) -> Vec<String> {
+    closest_matches(sid, skills)
+        .into_iter()
+        .take(max)
+        .map(|(_, id)| id)
+        .collect()
+}
+
+/// Replacement used by the fix-up: only an unambiguous best match
+fn replacement(sid: &str, skills: &[Skill]) -> Option<String> {
+    match closest_matches(sid, skills).as_slice() {
+        [(_, id)] => Some(id.clone()),
+        [(best, id), (next, _), ..] if best < next => Some(id.clone()),
+        _ => None,
+    }
+}
+
+/// Link-name collisions in a profile's resolved skills, in resolution order
+fn collisions(profile: &Profile, profiles: &[Profile], skills: &[Skill]) -> Vec<(String, Vec<String>)> {
+    let mut by_name: Vec<(String, Vec<String>)> = Vec::new();
+    for resolved in resolve_profile(profile, profiles, Some(skills)).skills {
+        let skill = match find_skill(skills, &resolved.skill_id) {
+            Some(s) => s,
+            None => continue,
+        };
+        let name = profile_link_name(skill, &resolved.skill_id, Some(profile));
+        match by_name.iter_mut().find(|(n, _)| *n == name) {
+            Some((_, ids)) => {
+                if !ids.iter().any(|id| find_skill(skills, id).is_some_and(|s| s.id == skill.id)) {
+                    ids.push(resolved.skill_id);
+                }
+            }
+            None => by_name.push((name, vec![resolved.skill_id])),
+        }
+    }
+    by_name.retain(|(_, ids)| ids.len() > 1);
+    by_name
+}
+
+/// The profile with unknown IDs replaced, duplicates dropped and own entries that lose a link name dropped
+fn fix_profile(profile: &Profile, profiles: &[Profile], skills: &[Skill]) -> (Profile, ProfileFix) {
+    let mut fix = ProfileFix::default();
+    let mut fixed = profile.clone();
+    fixed.skill_ids.clear();
+
+    let mut seen: Vec<String> = Vec::new();
+    for sid in &profile.skill_ids {
+        let (entry, catalog_id) = match find_skill(skills, sid) {
+            Some(skill) => (sid.clone(), Some(skill.id.clone())),
+            None => match replacement(sid, skills) {
+                Some(new_id) => {
+                    if let Some(alias) = fixed.aliases.remove(sid) {
+                        fixed.aliases.entry(new_id.clone()).or_insert(alias);
+                    }
+                    fix.replaced.push((sid.clone(), new_id.clone()));
+                    (new_id.clone(), Some(new_id))
+                }
+                None => (sid.clone(), None),
+            },
+        };
+        match catalog_id {
+            Some(id) if seen.contains(&id) => fix.removed.push(sid.clone()),
+            Some(id) => {
+                seen.push(id);
+                fixed.skill_ids.push(entry);
+            }
+            None => fixed.skill_ids.push(entry),
+        }
+    }
+
+    let mut others: Vec<Profile> = profiles.iter().filter(|p| p.id != profile.id).cloned().collect();
+    others.push(fixed.clone());
+    for (_, ids) in collisions(&fixed, &others, skills) {
+        for shadowed in ids.iter().skip(1) {
+            if let Some(pos) = fixed.skill_ids.iter().position(|s| s == shadowed) {
+                fix.removed.push(fixed.skill_ids.remove(pos));
+            }
+        }
+    }
+
+    fix.skill_ids = fixed.skill_ids.clone();
+    (fixed, fix)
+}
+
+fn validate_profile(profile: &Profile, profiles: &[Profile], skills: &[Skill], max_suggestions: usize) -> ProfileValidation {
+    let mut issues = Vec::new();
+
+    let mut entries_by_skill: Vec<(String, Vec<String>)> = Vec::new();
+    for sid in &profile.skill_ids {
+        match find_skill(skills, sid) {
+            Some(skill) => match entries_by_skill.iter_mut().find(|(id, _)| *id == skill.id) {
+                Some((_, entries)) => entries.push(sid.clone()),
+                None => entries_by_skill.push((skill.id.clone(), vec![sid.clone()])),
+            },
+            None => issues.push(ProfileIssue::UnknownSkill {
+                skill_id: sid.clone(),
+                suggestions: suggestions(sid, skills, max_suggestions),
+            }),
+        }
+    }
+    for (skill_id, entries) in entries_by_skill {
+        if entries.len() > 1 {
+            issues.push(ProfileIssue::Duplicate { skill_id, entries });
+        }
+    }
+    for (link_name, skill_ids) in collisions(profile, profiles, skills) {
+        issues.push(ProfileIssue::LinkNameCollision {
+            kept: skill_ids[0].clone(),
+            link_name,
+            skill_ids,
+        });
+    }
+
+    let (_, fix) = fix_profile(profile, profiles, skills);
+    let changed = !fix.replaced.is_empty() || !fix.removed.is_empty();
+    ProfileValidation {
+        profile_id: profile.id.clone(),
+        is_preset: profile.is_preset,
+        issues,
+        fix: changed.then_some(fix),
+    }
+}
+
+/// Check every profile's skills against a scan of `repo_path`; only profiles with issues are returned
+#[tauri::command]
+pub fn validate_profiles(repo_path: Option<String>) -> Result<Vec<ProfileValidation>, AppError> {
+    let skills = scanner::scan_skills_repo(repo_path)?;
+    let profiles = profiles::load_profiles()?;
+    let max_suggestions = load_settings()?.thresholds.max_suggestions;
+    Ok(profiles
+        .iter()
+        .map(|p| validate_profile(p, &profiles, &skills, max_suggestions))
+        .filter(|v| !v.issues.is_empty())
+        .collect())
+}
+
+/// Apply the automatic fix-up to the given profiles (presets are saved as user overrides)
+/// and return what is left to resolve by hand
+#[tauri::command]
+pub fn fix_profiles(repo_path: Option<String>, profile_ids: Vec<String>) -> Result<Vec<ProfileValidation>, AppError> {
+    let skills = scanner::scan_skills_repo(repo_path)?;
+    let mut all = profiles::load_profiles()?;
+    let max_suggestions = load_settings()?.thresholds.max_suggestions;
+
+    for id in &profile_ids {
+        let profile = all
+            .iter()
+            .find(|p| &p.id == id)
+            .ok_or_else(|| AppError::Custom(format!("Profile not found: {}", id)))?;
+        let (fixed, fix) = fix_profile(profile, &all, &skills);
+        if fix.replaced.is_empty() && fix.removed.is_empty() {
+            continue;
+        }
+        let saved = profiles::save_profile(fixed)?;
+        all.retain(|p| p.id != saved.id);
+        all.push(saved);
+    }
+
+    Ok(all
+        .iter()
+        .filter(|p| profile_ids.contains(&p.id))
+        .map(|p| validate_profile(p, &all, &skills, max_suggestions))
+        .filter(|v| !v.issues.is_empty())
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{LinkStatus, ProfileKind};
+    use std::collections::HashMap;
+    use std::path::PathBuf;
+
+    fn skill(id: &str) -> Skill {
+        Skill {
+            id: id.to_string(),
+            name: id.rsplit('/').next().unwrap_or(id).to_string(),
+            description: String::new(),
+            source_path: PathBuf::from(format!("/repo/{}", id)),
+            source_repo: "repo".to_string(),
+            category: None,
+            tags: Vec::new(),
+            has_scripts: false,
+            has_references: false,
+            link_status_user: LinkStatus::Inactive,
+            link_name_user: None,
+            dependencies: Vec::new(),
+            raw_content: String::new(),
+        }
+    }
+
+    fn profile(skill_ids: &[&str]) -> Profile {
+        Profile {
+            id: "web".to_string(),
+            name: "Web".to_string(),
+            description: String::new(),
+            color: String::new(),
+            skill_ids: skill_ids.iter().map(|s| s.to_string()).collect(),
+            is_preset: false,
+            aliases: HashMap::new(),
+            extends: Vec::new(),
+            excludes: Vec::new(),
+            kind: ProfileKind::Static,
+            query: None,
+            conditions: HashMap::new(),
+            resolved: None,
+        }
+    }
+
+    #[test]
+    fn only_unambiguous_typos_are_replaced() {
+        let skills = [skill("tools/deploy"), skill("tools/lint"), skill("tools/lints")];
+        assert_eq!(replacement("tools/deplyo", &skills).as_deref(), Some("tools/deploy"));
+        assert_eq!(replacement("lin", &skills).as_deref(), Some("tools/lint"));
+        assert_eq!(replacement("tools/lintx", &skills), None);
+        assert_eq!(replacement("something-else", &skills), None);
+        assert_eq!(suggestions("tools", &skills, 2), vec!["tools/deploy", "tools/lint"]);
+    }
+
+    #[test]
+    fn fix_up_replaces_typos_and_drops_duplicates_and_shadowed_entries() {
+        let skills = [skill("tools/deploy"), skill("a/review"), skill("b/review")];
+        let mut web = profile(&["tools/deplyo", "tools/deploy", "a/review", "b/review", "gone-entirely"]);
+        web.aliases.insert("tools/deplyo".to_string(), "ship".to_string());
+
+        let (fixed, fix) = fix_profile(&web, &[web.clone()], &skills);
+        assert_eq!(fix.replaced, vec![("tools/deplyo".to_string(), "tools/deploy".to_string())]);
+        assert_eq!(fix.removed, vec!["tools/deploy", "b/review"]);
+        assert_eq!(fix.skill_ids, vec!["tools/deploy", "a/review", "gone-entirely"]);
+        assert_eq!(fixed.aliases.get("tools/deploy").map(String::as_str), Some("ship"));
+
+        let validation = validate_profile(&web, &[web.clone()], &skills, 3);
+        assert!(validation.fix.is_some());
+        assert!(validation
+            .issues
+            .iter()
+            .any(|i| matches!(i, ProfileIssue::LinkNameCollision { kept, .. } if kept == "a/review")));
+    }
+}
@@ -31,6 +31,13 @@ pub enum AppError {
 
     #[error("Keychain error: {0}")]
     Keychain(String),
+
+    #[error("{path} was written by a newer version of SkillPilot (schema version {found}, this version supports up to {supported}); update SkillPilot to use it")]
+    UnsupportedSchemaVersion {
+        path: String,
+        found: u32,
+        supported: u32,
+    },
 }
 
 impl Serialize for AppError {
@@ -2,8 +2,9 @@ mod commands;
 mod error;
 mod models;
 mod ssh;
+mod store;
 
-use commands::{linker, profiles, projects, remote, scanner, shell, stats};
+use commands::{adopt, detect, discovery, drift, linker, lockfile, profiles, projects, remote, scanner, shell, snapshots, stats, targets, homes, query, presets, profile_share, validation, profile_history, conditions, trash};
 use ssh::connection::SshPool;
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
@@ -24,21 +25,103 @@ pub fn run() {
             linker::clean_broken_links,
             linker::get_user_skill_links,
             linker::get_project_skill_links,
+            // Claude homes
+            homes::list_claude_homes,
+            homes::save_claude_home,
+            homes::delete_claude_home,
+            homes::get_active_claude_home,
+            homes::set_active_claude_home,
+            // Link targets
+            targets::list_link_targets,
+            targets::save_link_target,
+            targets::delete_link_target,
+            targets::apply_to_link_target,
+            targets::remove_from_link_target,
+            targets::get_link_target_status,
+            targets::clean_link_target,
+            // Adopt
+            adopt::adopt_skill_directory,
+            // Lockfile
+            lockfile::write_project_lock,
+            lockfile::verify_project_lock,
+            // Drift
+            drift::detect_project_drift,
+            drift::reconcile_all_projects,
             // Profiles
             profiles::list_profiles,
             profiles::save_profile,
             profiles::delete_profile,
             profiles::get_profile,
+            query::preview_profile_query,
+            conditions::resolve_profile_for_target,
+            conditions::resolve_project_link_entries,
+            // Presets
+            presets::list_preset_packs,
+            presets::install_preset_pack,
+            presets::remove_preset_pack,
+            presets::get_preset_settings,
+            presets::save_preset_settings,
+            profiles::create_profile_from_links,
+            // Profile sharing
+            profile_share::export_profiles,
+            profile_share::import_profiles,
+            // Profile validation
+            validation::validate_profiles,
+            validation::fix_profiles,
+            // Profile history
+            profile_history::list_profile_versions,
+            profile_history::diff_profile_versions,
+            profile_history::diff_profile_with_links,
+            profile_history::restore_profile_version,
             // Projects
             projects::list_projects,
             projects::save_project,
+            projects::set_project_link_winner,
             projects::delete_project,
+            // Project detection
+            detect::detect_project_type,
+            detect::list_detection_rules,
+            detect::save_detection_rules,
+            // Project discovery
+            discovery::get_discovery_config,
+            discovery::save_discovery_config,
+            discovery::discover_projects,
+            discovery::import_discovered_projects,
+            // Snapshots
+            snapshots::create_snapshot,
+            snapshots::list_snapshots,
+            snapshots::delete_snapshot,
+            snapshots::diff_snapshot,
+            snapshots::restore_snapshot,
             // Stats
             stats::get_stats,
             stats::record_toggle,
             stats::record_profile_apply,
             stats::record_scan,
             stats::record_clean,
+            // Trash
+            trash::list_trash,
+            trash::restore_from_trash,
+            trash::purge_trash_item,
+            trash::empty_trash,
+            trash::get_trash_settings,
+            trash::save_trash_settings,
+            // Settings
+            settings::get_data_root,
+            settings::get_settings,
+            settings::update_settings,
+            // Backup
+            backup::export_backup,
+            backup::import_backup,
+            // Sync
+            sync::get_sync_status,
+            sync::enable_sync,
+            sync::disable_sync,
+            sync::sync_commit,
+            sync::sync_pull,
+            sync::resolve_sync_conflict,
+            sync::abort_sync_merge,
+            sync::sync_push,
             // Shell
             shell::reveal_in_finder,
             shell::list_skill_files,
@@ -60,17 +143,34 @@ pub fn run() {
             remote::remote_toggle_skill_project_level,
             remote::remote_sync_project_links,
             remote::remote_apply_profile_links,
+            remote::remote_resolve_profile_for_target,
+            remote::remote_resolve_project_link_entries,
             remote::remote_clean_broken_links,
             remote::remote_get_project_skill_links,
             remote::remote_get_user_skill_links,
+            // Remote: drift
+            remote::remote_detect_project_drift,
+            remote::remote_reconcile_all_projects,
             // Remote: profiles
             remote::remote_list_profiles,
             remote::remote_save_profile,
             remote::remote_delete_profile,
+            remote::remote_list_profile_versions,
+            remote::remote_diff_profile_versions,
+            remote::remote_diff_profile_with_links,
+            remote::remote_restore_profile_version,
+            remote::remote_create_profile_from_links,
             // Remote: projects
             remote::remote_list_projects,
             remote::remote_save_project,
+            remote::remote_set_project_link_winner,
             remote::remote_delete_project,
+            // Remote: snapshots
+            remote::remote_create_snapshot,
+            remote::remote_list_snapshots,
+            remote::remote_delete_snapshot,
+            remote::remote_diff_snapshot,
+            remote::remote_restore_snapshot,
             // Remote: shell
             remote::remote_list_skill_files,
             remote::remote_read_file_content,
@@ -0,0 +1,67 @@
+use serde::{Deserialize, Serialize};
+
+/// Describes a backup archive (manifest.json at the archive root)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupManifest {
+    /// Archive format version
+    pub format_version: u32,
+    /// When the archive was written (RFC 3339)
+    pub created_at: String,
+    /// Home directory of the exporting machine
+    pub home_dir: Option<String>,
+    /// Skills repositories configured on the exporting machine
+    #[serde(default)]
+    pub repo_roots: Vec<String>,
+    /// Files under data/, relative to the data root
+    #[serde(default)]
+    pub files: Vec<String>,
+    /// Servers whose Keychain passwords are in secrets.bin (encrypted with the passphrase)
+    #[serde(default)]
+    pub secret_server_ids: Vec<String>,
+}
+
+/// How an import treats the existing configuration
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum ImportMode {
+    /// Add what is missing; existing records and files win
+    Merge,
+    /// Clear the data root first (a backup of it is written to <data root>/backups)
+    Replace,
+}
+
+/// Rewrites paths starting with `from` to start with `to`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PathMapping {
+    pub from: String,
+    pub to: String,
+}
+
+/// Outcome of export_backup
+#[derive(Debug, Clone, Serialize)]
+pub struct BackupExportResult {
+    pub path: String,
+    pub manifest: BackupManifest,
+}
+
+/// Outcome of import_backup
+#[derive(Debug, Clone, Serialize, Default)]
+pub struct BackupImportResult {
+    /// Files written, relative to the data root
+    pub imported_files: Vec<String>,
+    /// Files left alone because a local copy exists (merge only)
+    pub skipped_files: Vec<String>,
+    /// Projects and servers added (merge) or restored (replace)
+    pub imported_project_ids: Vec<String>,
+    pub imported_server_ids: Vec<String>,
+    /// Projects and servers already present locally (merge only)
+    pub skipped_project_ids: Vec<String>,
+    pub skipped_server_ids: Vec<String>,
+    /// Paths rewritten by the path mappings
+    pub remapped_paths: Vec<PathMapping>,
+    /// Servers whose Keychain passwords were restored
+    pub restored_secret_ids: Vec<String>,
+    /// The archive holds secrets but no passphrase was given
+    pub secrets_skipped: bool,
+    /// Archive of the configuration as it was before a replacing import
+    pub previous_backup: Option<String>,
+}
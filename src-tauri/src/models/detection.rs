@@ -0,0 +1,36 @@
+use crate::models::LocalizedText;
+use serde::{Deserialize, Serialize};
+
+/// A file-system marker that identifies a kind of project
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum ProjectMarker {
+    /// A file or directory exists (path relative to the project root)
+    FileExists { path: String },
+    /// A file exists and its content matches a regex
+    FileContains { path: String, pattern: String },
+    /// The project only contains documentation files
+    DocsOnly,
+}
+
+/// Maps project markers to suggested profiles and skills (defaults bundled from
+/// detection/default.json, user rules persisted to <data root>/detection-rules.json)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DetectionRule {
+    /// Unique identifier (user rules override defaults with the same ID)
+    pub id: String,
+    /// Human-readable reason shown with each suggestion, plain or keyed by locale
+    pub reason: LocalizedText,
+    /// Rule matches when any marker matches
+    pub markers: Vec<ProjectMarker>,
+    /// Profiles to suggest
+    #[serde(default)]
+    pub profile_ids: Vec<String>,
+    /// Individual skill IDs or names to suggest
+    #[serde(default)]
+    pub skill_ids: Vec<String>,
+    /// Suggest every catalog skill carrying one of these tags
+    #[serde(default)]
+    pub skill_tags: Vec<String>,
+}
+
@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+
+/// A Claude configuration directory (what `CLAUDE_CONFIG_DIR` points to, ~/.claude by default)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClaudeHome {
+    /// Unique identifier ("default" for the environment's home)
+    pub id: String,
+    /// Display name, e.g. "Work" or "Personal"
+    pub name: String,
+    /// Config directory; `~` is expanded. User-level skills live in `<path>/skills`
+    pub path: String,
+}
+
+/// Configured Claude homes and the one user-level operations act on
+/// (persisted to <data root>/homes.json)
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct HomesConfig {
+    pub homes: Vec<ClaudeHome>,
+    /// Selected home ID; None means "default"
+    #[serde(default)]
+    pub active_id: Option<String>,
+}
@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+
+/// Project lockfile (persisted to <project>/.claude/skills.lock)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkillLock {
+    /// Lockfile format version
+    pub version: u32,
+    /// When the lock was written (RFC 3339)
+    pub generated_at: String,
+    /// Skill repository the lock was resolved against
+    pub repo_path: String,
+    /// Locked skills, sorted by link name
+    pub skills: Vec<LockedSkill>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LockedSkill {
+    /// Link name inside .claude/skills/
+    pub name: String,
+    /// Skill ID (relative path from repo root)
+    pub skill_id: String,
+    /// Which repo/submodule the skill comes from
+    pub source_repo: String,
+    /// Path of the skill directory relative to its source repo
+    pub path: String,
+    /// Git commit of the containing repo/submodule
+    pub revision: Option<String>,
+    /// SHA-256 over the skill directory's files
+    pub content_hash: String,
+}
@@ -2,7 +2,27 @@ pub mod skill;
 pub mod profile;
 pub mod project;
 pub mod remote;
+pub mod lock;
+pub mod detection;
+pub mod snapshot;
+pub mod target;
+pub mod home;
+pub mod preset;
+pub mod trash;
+pub mod settings;
+pub mod backup;
+pub mod sync;
 
 pub use skill::*;
 pub use profile::*;
 pub use project::*;
+pub use lock::*;
+pub use detection::*;
+pub use snapshot::*;
+pub use target::*;
+pub use home::*;
+pub use preset::*;
+pub use trash::*;
+pub use settings::*;
+pub use backup::*;
+pub use sync::*;
@@ -0,0 +1,74 @@
+use crate::models::{EntryCondition, ProfileKind, ProfileQuery};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Text that is either plain or keyed by locale (e.g. {"en": "...", "zh-CN": "..."})
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum LocalizedText {
+    Plain(String),
+    Localized(HashMap<String, String>),
+}
+
+impl LocalizedText {
+    /// Pick the best translation: exact locale, same language, English, then any
+    pub fn get(&self, locale: &str) -> String {
+        let map = match self {
+            LocalizedText::Plain(s) => return s.clone(),
+            LocalizedText::Localized(map) => map,
+        };
+        let language = locale.split(['-', '_']).next().unwrap_or(locale);
+        map.get(locale)
+            .or_else(|| {
+                map.iter()
+                    .find(|(k, _)| k.split(['-', '_']).next() == Some(language))
+                    .map(|(_, v)| v)
+            })
+            .or_else(|| map.get("en"))
+            .or_else(|| map.values().min())
+            .cloned()
+            .unwrap_or_default()
+    }
+}
+
+/// A preset profile as written in a pack file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PresetProfile {
+    pub id: String,
+    pub name: LocalizedText,
+    pub description: LocalizedText,
+    pub color: String,
+    #[serde(default)]
+    pub skill_ids: Vec<String>,
+    #[serde(default)]
+    pub aliases: HashMap<String, String>,
+    #[serde(default)]
+    pub extends: Vec<String>,
+    #[serde(default)]
+    pub excludes: Vec<String>,
+    #[serde(default)]
+    pub kind: ProfileKind,
+    #[serde(default)]
+    pub query: Option<ProfileQuery>,
+    #[serde(default)]
+    pub conditions: HashMap<String, Vec<EntryCondition>>,
+}
+
+/// A set of preset profiles (bundled, or installed to <data root>/presets/<id>.json)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PresetPack {
+    pub id: String,
+    pub name: LocalizedText,
+    pub profiles: Vec<PresetProfile>,
+}
+
+/// Preset display settings (persisted to <data root>/presets.json)
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct PresetSettings {
+    /// Preset IDs not shown in `list_profiles`
+    #[serde(default)]
+    pub hidden: Vec<String>,
+    /// Locale for preset names/descriptions (default zh-CN, the original preset language)
+    #[serde(default)]
+    pub locale: Option<String>,
+}
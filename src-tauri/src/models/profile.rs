@@ -1,4 +1,87 @@
+use crate::models::TargetScope;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// How a profile's membership is defined
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub enum ProfileKind {
+    /// Fixed list of skill IDs
+    #[default]
+    Static,
+    /// Skills matching `query`, re-evaluated against every scan
+    Dynamic,
+}
+
+/// Match on a SKILL.md frontmatter field
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FrontmatterMatch {
+    pub field: String,
+    /// Expected value (for lists: contained in the list); None matches any value
+    #[serde(default)]
+    pub value: Option<String>,
+}
+
+/// Saved query of a dynamic profile; a skill matching any criterion is a member
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct ProfileQuery {
+    #[serde(default)]
+    pub source_repos: Vec<String>,
+    #[serde(default)]
+    pub categories: Vec<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Globs over skill IDs (repo-relative paths), e.g. `marketingskills/skills/*`
+    #[serde(default)]
+    pub path_globs: Vec<String>,
+    #[serde(default)]
+    pub frontmatter: Vec<FrontmatterMatch>,
+}
+
+/// Condition on a profile entry, evaluated against the target the profile is applied to
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type")]
+pub enum EntryCondition {
+    /// A file or directory exists, relative to the project root
+    FileExists { path: String },
+    /// Some project file matches a glob relative to the project root (e.g. `**/*.tf`)
+    GlobMatch { pattern: String },
+    /// The project carries a tag
+    ProjectTag { tag: String },
+    /// The target is the user-level or a project skills directory
+    Scope { scope: TargetScope },
+    /// Host OS, as in Rust's `std::env::consts::OS` ("linux", "macos", "windows")
+    HostOs { os: String },
+    /// The target is on this machine
+    Local,
+    /// The target is on a remote server (any server when `server_ids` is empty)
+    Remote {
+        #[serde(default)]
+        server_ids: Vec<String>,
+    },
+    /// At least one of the nested conditions holds
+    AnyOf { conditions: Vec<EntryCondition> },
+}
+
+/// A skill in a profile's flattened list and the profile that contributed it
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ResolvedSkill {
+    pub skill_id: String,
+    /// ID of the profile that declares the skill (the profile itself or an ancestor)
+    pub from_profile_id: String,
+}
+
+/// Flattened form of a profile after applying `extends` and `excludes` (computed, never persisted)
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ResolvedProfile {
+    /// Parents first (in `extends` order), then the profile's own skills, minus excludes
+    pub skills: Vec<ResolvedSkill>,
+    /// Parent IDs that matched no profile
+    pub missing_parents: Vec<String>,
+    /// Inheritance cycle that was cut, as a path of profile IDs (e.g. a -> b -> a)
+    pub cycle: Option<Vec<String>>,
+    /// A dynamic profile in the chain could not be evaluated (no scan available)
+    pub query_pending: bool,
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Profile {
@@ -10,183 +93,75 @@ pub struct Profile {
     pub description: String,
     /// Color hex for UI display
     pub color: String,
-    /// Ordered list of skill IDs in this profile
+    /// Ordered list of skill IDs in this profile (static includes for dynamic profiles)
     pub skill_ids: Vec<String>,
     /// Whether this is a built-in preset
     pub is_preset: bool,
+    /// skill ID -> link name, for skills linked under a different name than `Skill.name`
+    #[serde(default)]
+    pub aliases: HashMap<String, String>,
+    /// Profile IDs whose skills this profile builds on
+    #[serde(default)]
+    pub extends: Vec<String>,
+    /// Skill IDs removed from the inherited skills
+    #[serde(default)]
+    pub excludes: Vec<String>,
+    #[serde(default)]
+    pub kind: ProfileKind,
+    /// Membership query (dynamic profiles)
+    #[serde(default)]
+    pub query: Option<ProfileQuery>,
+    /// skill ID -> conditions that must all hold for the skill to be linked to a target
+    #[serde(default)]
+    pub conditions: HashMap<String, Vec<EntryCondition>>,
+    /// Flattened skill list, filled in by the backend when listing
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resolved: Option<ResolvedProfile>,
 }
 
 impl Profile {
-    pub fn presets() -> Vec<Profile> {
-        vec![
-            Profile {
-                id: "preset-java".into(),
-                name: "Java 开发".into(),
-                description: "Java/Maven 项目开发全流程".into(),
-                color: "#d4a259".into(),
-                skill_ids: vec![
-                    "maven-operating".into(),
-                    "code-review".into(),
-                    "unit-test-gen".into(),
-                    "java-project-ai-prompt-init".into(),
-                    "designing-database-tables".into(),
-                    "troubleshooting-java-logs".into(),
-                    "git-committing".into(),
-                    "deploy-test".into(),
-                    "java-multimodule-keyword-replacement".into(),
-                    "flowchart-gen".into(),
-                    "api-doc-gen".into(),
-                    "java-requirement-development".into(),
-                    "developing-from-product-docs".into(),
-                    "fetching-prd-from-gitlab".into(),
-                ],
-                is_preset: true,
-            },
-            Profile {
-                id: "preset-workflow".into(),
-                name: "通用工作流".into(),
-                description: "代码审查、Git、计划、调试等通用流程".into(),
-                color: "#3B82F6".into(),
-                skill_ids: vec![
-                    "brainstorming".into(),
-                    "writing-plans".into(),
-                    "executing-plans".into(),
-                    "code-review-plus".into(),
-                    "git-committing".into(),
-                    "systematic-debugging".into(),
-                    "test-driven-development".into(),
-                    "verification-before-completion".into(),
-                    "dispatching-parallel-agents".into(),
-                    "subagent-driven-development".into(),
-                    "finishing-a-development-branch".into(),
-                    "requesting-code-review".into(),
-                    "using-git-worktrees".into(),
-                ],
-                is_preset: true,
-            },
-            Profile {
-                id: "preset-marketing".into(),
-                name: "营销增长".into(),
-                description: "营销内容、品牌、竞品分析、SEO".into(),
-                color: "#EC4899".into(),
-                skill_ids: vec![
-                    "example-skills:frontend-design".into(),
-                    "example-skills:brand-guidelines".into(),
-                    "example-skills:canvas-design".into(),
-                    "example-skills:web-artifacts-builder".into(),
-                    "quality-website-search".into(),
-                    "rss-daily-digest".into(),
-                    "marketingskills/skills/seo-audit".into(),
-                    "marketingskills/skills/copywriting".into(),
-                    "marketingskills/skills/content-strategy".into(),
-                    "marketingskills/skills/social-content".into(),
-                    "marketingskills/skills/competitor-alternatives".into(),
-                    "ui-reference-to-code".into(),
-                ],
-                is_preset: true,
-            },
-            Profile {
-                id: "preset-devops".into(),
-                name: "DevOps".into(),
-                description: "Docker、部署、服务器运维".into(),
-                color: "#10B981".into(),
-                skill_ids: vec![
-                    "docker-bluegreen-deploy".into(),
-                    "deploy-test".into(),
-                    "aliyun-init".into(),
-                    "maven-operating".into(),
-                    "git-committing".into(),
-                    "linux-server-bootstrap".into(),
-                    "example-skills:mcp-builder".into(),
-                ],
-                is_preset: true,
-            },
-            Profile {
-                id: "preset-frontend".into(),
-                name: "前端工程开发".into(),
-                description: "前端 UI 开发、H5、React、Next.js、部署".into(),
-                color: "#f2994a".into(),
-                skill_ids: vec![
-                    "example-skills:frontend-design".into(),
-                    "example-skills:web-artifacts-builder".into(),
-                    "example-skills:webapp-testing".into(),
-                    "ui-reference-to-code".into(),
-                    "linear-dark-ui-style".into(),
-                    "nextjs-migration".into(),
-                    "deploy-to-vercel".into(),
-                    "fenxiang-skills/frontend/skills/h5-development-standards".into(),
-                    "fenxiang-skills/frontend/skills/converting-ui-to-h5code".into(),
-                    "fenxiang-skills/frontend/skills/implementing-complex-ui-pages".into(),
-                    "fenxiang-skills/frontend/skills/react-mobile-h5-activity-page".into(),
-                    "fenxiang-skills/frontend/skills/ordering-tailwind-classes".into(),
-                    "brainstorming".into(),
-                    "code-review-plus".into(),
-                    "git-committing".into(),
-                ],
-                is_preset: true,
-            },
-            Profile {
-                id: "preset-media-content".into(),
-                name: "自媒内容创作".into(),
-                description: "AI 生图、文章配图、自媒体发布（微信/X/小红书）".into(),
-                color: "#a77bca".into(),
-                skill_ids: vec![
-                    "baoyu-skills/skills/baoyu-image-gen".into(),
-                    "baoyu-skills/skills/baoyu-article-illustrator".into(),
-                    "baoyu-skills/skills/baoyu-infographic".into(),
-                    "baoyu-skills/skills/baoyu-cover-image".into(),
-                    "baoyu-skills/skills/baoyu-comic".into(),
-                    "baoyu-skills/skills/baoyu-xhs-images".into(),
-                    "baoyu-skills/skills/baoyu-post-to-wechat".into(),
-                    "baoyu-skills/skills/baoyu-post-to-x".into(),
-                    "baoyu-skills/skills/baoyu-url-to-markdown".into(),
-                    "baoyu-skills/skills/baoyu-format-markdown".into(),
-                    "example-skills:canvas-design".into(),
-                    "example-skills:algorithmic-art".into(),
-                    "video-downloader".into(),
-                    "transcript-summarizer".into(),
-                ],
-                is_preset: true,
-            },
-            Profile {
-                id: "preset-office-content".into(),
-                name: "办公内容创作".into(),
-                description: "文档、PPT、表格、会议纪要、周报".into(),
-                color: "#7c8ea6".into(),
-                skill_ids: vec![
-                    "example-skills:pptx".into(),
-                    "example-skills:pdf".into(),
-                    "example-skills:docx".into(),
-                    "example-skills:xlsx".into(),
-                    "example-skills:doc-coauthoring".into(),
-                    "example-skills:internal-comms".into(),
-                    "example-skills:theme-factory".into(),
-                    "baoyu-skills/skills/baoyu-slide-deck".into(),
-                    "baoyu-skills/skills/baoyu-format-markdown".into(),
-                    "NanoBanana-PPT-Skills".into(),
-                    "meeting-minutes".into(),
-                    "weekly-report".into(),
-                    "transcript-summarizer".into(),
-                ],
-                is_preset: true,
-            },
-            Profile {
-                id: "preset-info-collect".into(),
-                name: "信息收集".into(),
-                description: "RSS 资讯、网站搜索、视频下载、GitHub 追踪".into(),
-                color: "#4da7c9".into(),
-                skill_ids: vec![
-                    "rss-daily-digest".into(),
-                    "quality-website-search".into(),
-                    "video-downloader".into(),
-                    "transcript-summarizer".into(),
-                    "notebooklm-skill".into(),
-                    "github-search".into(),
-                    "github-trending".into(),
-                    "baoyu-skills/skills/baoyu-url-to-markdown".into(),
-                ],
-                is_preset: true,
-            },
-        ]
+    /// Skill IDs after inheritance (declared `skill_ids` if the profile has not been resolved)
+    pub fn effective_skill_ids(&self) -> Vec<String> {
+        match &self.resolved {
+            Some(r) => r.skills.iter().map(|s| s.skill_id.clone()).collect(),
+            None => self.skill_ids.clone(),
+        }
     }
 }
+
+/// A skill referenced by an exported profile, with where it came from
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundledSkill {
+    pub skill_id: String,
+    pub name: String,
+    /// Which repo/submodule the skill comes from
+    pub source_repo: String,
+    /// Git commit of the source repo/submodule at export time
+    pub revision: Option<String>,
+}
+
+/// Shareable export of one or more profiles (JSON or YAML)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProfileBundle {
+    /// Bundle format version
+    pub version: u32,
+    /// When the bundle was written (RFC 3339)
+    pub exported_at: String,
+    /// Declared profiles (resolved forms are not exported)
+    pub profiles: Vec<Profile>,
+    /// Referenced skills, when exported with skill information
+    #[serde(default)]
+    pub skills: Vec<BundledSkill>,
+}
+
+/// A saved state of a profile
+/// (persisted to <data root>/profile-history/<profile id>/<version>.json, or the remote config dir)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProfileVersion {
+    /// Increasing version number, starting at 1
+    pub version: u32,
+    /// When the version was saved (RFC 3339)
+    pub saved_at: String,
+    /// Declared form of the profile
+    pub profile: Profile,
+}
@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::PathBuf;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -13,4 +14,13 @@ pub struct ProjectConfig {
     pub profile_ids: Vec<String>,
     /// Additional individual skill IDs beyond profiles
     pub extra_skill_ids: Vec<String>,
+    /// skill ID -> link name; overrides the aliases of the applied profiles
+    #[serde(default)]
+    pub aliases: HashMap<String, String>,
+    /// Free-form tags, matched by `ProjectTag` profile entry conditions
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// link name -> skill ID that gets the link when several skills resolve to that name
+    #[serde(default)]
+    pub link_winners: HashMap<String, String>,
 }
@@ -15,7 +15,7 @@ pub enum SshAuth {
     Password,
 }
 
-/// Remote server configuration (persisted to ~/.claude-skill-manager/remotes.json)
+/// Remote server configuration (persisted to <data root>/remotes.json)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RemoteServer {
     /// Unique identifier
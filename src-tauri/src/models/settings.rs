@@ -0,0 +1,55 @@
+use crate::models::SyncSettings;
+use serde::{Deserialize, Serialize};
+
+/// How symlinks point at skill directories
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Default)]
+pub enum LinkMode {
+    /// Absolute path to the skill directory
+    #[default]
+    Absolute,
+    /// Path relative to the skills directory (survives moving a tree that holds both)
+    Relative,
+}
+
+/// Tunable limits
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Thresholds {
+    /// Versions kept per profile before the oldest are pruned
+    pub max_profile_versions: usize,
+    /// Suggestions listed per unknown skill ID when validating profiles
+    pub max_suggestions: usize,
+    /// Depth of the project listing used for glob conditions
+    pub max_glob_depth: usize,
+}
+
+impl Default for Thresholds {
+    fn default() -> Self {
+        Thresholds {
+            max_profile_versions: 50,
+            max_suggestions: 3,
+            max_glob_depth: 6,
+        }
+    }
+}
+
+/// Application settings (persisted to <data root>/config.json)
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct AppSettings {
+    /// Skills repositories; the first one is used when a command gets no repo path
+    #[serde(default)]
+    pub repo_roots: Vec<String>,
+    /// Link mode used when a command does not name one
+    #[serde(default)]
+    pub default_link_mode: LinkMode,
+    /// Link target IDs skills are applied to by default
+    #[serde(default)]
+    pub target_ids: Vec<String>,
+    /// Glob patterns (relative to the repo root) of skill directories left out of scans
+    #[serde(default)]
+    pub ignore_patterns: Vec<String>,
+    #[serde(default)]
+    pub thresholds: Thresholds,
+    #[serde(default)]
+    pub sync: SyncSettings,
+}
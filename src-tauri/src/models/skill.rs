@@ -42,8 +42,11 @@ pub struct Skill {
     pub has_scripts: bool,
     /// Whether the skill has a references/ directory
     pub has_references: bool,
-    /// User-level link status (~/.claude/skills/)
+    /// User-level link status (<claude home>/skills/)
     pub link_status_user: LinkStatus,
+    /// Name of the user-level link when it differs from `name` (an alias)
+    #[serde(default)]
+    pub link_name_user: Option<String>,
     /// Referenced skill names (dependencies)
     pub dependencies: Vec<String>,
     /// Raw SKILL.md content for preview
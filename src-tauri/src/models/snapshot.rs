@@ -0,0 +1,37 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum SnapshotEntryKind {
+    /// Symlink, restored by recreating it
+    Symlink,
+    /// Real directory, recorded as a marker only (contents are not captured)
+    Directory,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SnapshotEntry {
+    pub name: String,
+    pub kind: SnapshotEntryKind,
+    /// Symlink target (None for directories)
+    pub target: Option<String>,
+}
+
+/// Saved state of a skills directory
+/// (persisted to <data root>/snapshots/<id>.json, or the remote config dir)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Snapshot {
+    /// Unique identifier
+    pub id: String,
+    /// Display name
+    pub name: String,
+    /// When the snapshot was taken (RFC 3339)
+    pub created_at: String,
+    /// Project path, or None for the user-level skills directory
+    pub target_path: Option<String>,
+    /// Skills directory the snapshot was taken from, restored to regardless of the Claude
+    /// home active later (None for snapshots from before it was recorded)
+    #[serde(default)]
+    pub skills_dir: Option<String>,
+    /// Entries sorted by name
+    pub entries: Vec<SnapshotEntry>,
+}
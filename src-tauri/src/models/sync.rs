@@ -0,0 +1,100 @@
+use crate::models::Profile;
+use serde::{Deserialize, Serialize};
+
+/// Git-backed config sync (part of the application settings)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncSettings {
+    /// Whether the data root is kept as a git working copy
+    #[serde(default)]
+    pub enabled: bool,
+    /// URL or path of the remote repository (a local bare repository works)
+    #[serde(default)]
+    pub remote: Option<String>,
+    /// Branch committed to, pulled and pushed
+    #[serde(default = "default_branch")]
+    pub branch: String,
+}
+
+fn default_branch() -> String {
+    "main".to_string()
+}
+
+impl Default for SyncSettings {
+    fn default() -> Self {
+        SyncSettings {
+            enabled: false,
+            remote: None,
+            branch: default_branch(),
+        }
+    }
+}
+
+/// State of the config working copy
+#[derive(Debug, Clone, Serialize)]
+pub struct SyncStatus {
+    pub enabled: bool,
+    /// Whether the data root is a git repository
+    pub initialized: bool,
+    pub remote: Option<String>,
+    pub branch: String,
+    /// Current commit
+    pub head: Option<String>,
+    /// Files with changes not committed yet, relative to the data root
+    pub changed_files: Vec<String>,
+    /// Commits not pushed yet / not pulled yet (as of the last pull)
+    pub ahead: u32,
+    pub behind: u32,
+    /// A pull stopped on conflicts that are not all resolved
+    pub merging: bool,
+    pub conflicts: Vec<String>,
+}
+
+/// Outcome of sync_commit
+#[derive(Debug, Clone, Serialize)]
+pub struct SyncCommitResult {
+    /// The new commit, None when there was nothing to commit
+    pub commit: Option<String>,
+    pub files: Vec<String>,
+}
+
+/// A profile changed on both sides of a pull
+#[derive(Debug, Clone, Serialize)]
+pub struct ProfileConflict {
+    pub profile_id: String,
+    /// The local and the incoming version; None where the profile was deleted or does not parse
+    pub ours: Option<Profile>,
+    pub theirs: Option<Profile>,
+    /// Fields that differ between the two versions
+    pub changed_fields: Vec<String>,
+}
+
+/// A file changed on both sides of a pull
+#[derive(Debug, Clone, Serialize)]
+pub struct SyncConflict {
+    /// Path relative to the data root
+    pub path: String,
+    /// Content at the common ancestor, locally and incoming (None where the file is absent)
+    pub base: Option<String>,
+    pub ours: Option<String>,
+    pub theirs: Option<String>,
+    /// Set for profile files
+    pub profile: Option<ProfileConflict>,
+}
+
+/// Which side a conflicted file is resolved to
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum ConflictResolution {
+    Ours,
+    Theirs,
+}
+
+/// Outcome of sync_pull and resolve_sync_conflict
+#[derive(Debug, Clone, Serialize, Default)]
+pub struct SyncMergeResult {
+    /// Files changed by the incoming commits
+    pub updated_files: Vec<String>,
+    /// Conflicts left to resolve; the merge is committed once there are none
+    pub conflicts: Vec<SyncConflict>,
+    /// Commit the working copy is at after the merge, None while conflicts remain
+    pub commit: Option<String>,
+}
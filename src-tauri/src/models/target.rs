@@ -0,0 +1,99 @@
+use serde::{Deserialize, Serialize};
+
+/// Which coding agent reads the target directory
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum AgentKind {
+    ClaudeCode,
+    Codex,
+    GeminiCli,
+    Cursor,
+    Custom,
+}
+
+/// Whether the target path is absolute (user-level) or relative to a project root
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum TargetScope {
+    User,
+    Project,
+}
+
+/// How a skill is materialized in the target directory
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type")]
+pub enum TargetLayout {
+    /// `<dir>/<name>` symlinked to the skill directory
+    DirectorySymlink,
+    /// `<dir>/<name>.<extension>` generated from SKILL.md
+    SingleFile { extension: String },
+}
+
+/// A directory an agent reads skills/rules from
+/// (user targets persisted to <data root>/targets.json)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LinkTarget {
+    /// Unique identifier (user entries override built-ins with the same ID)
+    pub id: String,
+    /// Display name
+    pub name: String,
+    pub agent: AgentKind,
+    pub scope: TargetScope,
+    /// Directory path; `~` is expanded for user scope, project scope is relative
+    pub path: String,
+    pub layout: TargetLayout,
+    /// Whether this is a built-in target
+    pub is_builtin: bool,
+}
+
+impl LinkTarget {
+    pub fn builtins() -> Vec<LinkTarget> {
+        vec![
+            LinkTarget {
+                id: "claude-user".into(),
+                name: "Claude Code (user)".into(),
+                agent: AgentKind::ClaudeCode,
+                scope: TargetScope::User,
+                path: "~/.claude/skills".into(),
+                layout: TargetLayout::DirectorySymlink,
+                is_builtin: true,
+            },
+            LinkTarget {
+                id: "claude-project".into(),
+                name: "Claude Code (project)".into(),
+                agent: AgentKind::ClaudeCode,
+                scope: TargetScope::Project,
+                path: ".claude/skills".into(),
+                layout: TargetLayout::DirectorySymlink,
+                is_builtin: true,
+            },
+            LinkTarget {
+                id: "codex-user".into(),
+                name: "Codex (user)".into(),
+                agent: AgentKind::Codex,
+                scope: TargetScope::User,
+                path: "~/.codex/skills".into(),
+                layout: TargetLayout::DirectorySymlink,
+                is_builtin: true,
+            },
+            LinkTarget {
+                id: "gemini-user".into(),
+                name: "Gemini CLI (user)".into(),
+                agent: AgentKind::GeminiCli,
+                scope: TargetScope::User,
+                path: "~/.gemini/skills".into(),
+                layout: TargetLayout::DirectorySymlink,
+                is_builtin: true,
+            },
+            LinkTarget {
+                id: "cursor-project".into(),
+                name: "Cursor rules (project)".into(),
+                agent: AgentKind::Cursor,
+                scope: TargetScope::Project,
+                path: ".cursor/rules".into(),
+                layout: TargetLayout::SingleFile {
+                    extension: "mdc".into(),
+                },
+                is_builtin: true,
+            },
+        ]
+    }
+}
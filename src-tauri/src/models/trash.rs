@@ -0,0 +1,45 @@
+use serde::{Deserialize, Serialize};
+
+/// What a trash item holds
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum TrashKind {
+    Profile,
+    Project,
+    RemoteServer,
+}
+
+/// A deleted record (persisted to <data root>/trash/<id>.json)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrashItem {
+    /// Unique identifier of the trash item
+    pub id: String,
+    pub kind: TrashKind,
+    /// ID of the deleted profile, project or server
+    pub record_id: String,
+    /// Display name of the deleted record
+    pub name: String,
+    /// When the record was deleted (RFC 3339)
+    pub deleted_at: String,
+    /// The record as it was stored
+    pub data: serde_json::Value,
+}
+
+/// Trash settings (persisted to <data root>/trash.json)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrashSettings {
+    /// Days a deleted record is kept before it is purged (0 keeps records until purged by hand)
+    #[serde(default = "default_retention_days")]
+    pub retention_days: u32,
+}
+
+fn default_retention_days() -> u32 {
+    30
+}
+
+impl Default for TrashSettings {
+    fn default() -> Self {
+        TrashSettings {
+            retention_days: default_retention_days(),
+        }
+    }
+}
@@ -59,6 +59,16 @@ pub fn exec_command_checked(session: &Session, cmd: &str) -> Result<String, AppE
 
 /// Shell-escape a string for safe use in SSH commands.
 /// Wraps the value in single quotes and escapes any embedded single quotes.
+/// For names and other literal arguments; paths go through `shell_path`.
 pub fn shell_escape(s: &str) -> String {
     format!("'{}'", s.replace('\'', "'\\''"))
 }
+
+/// Shell-escape a remote path while keeping a leading `~/` expandable.
+pub fn shell_path(path: &str) -> String {
+    match path.strip_prefix("~/") {
+        Some(rest) => format!("\"$HOME\"/{}", shell_escape(rest)),
+        None if path == "~" => "\"$HOME\"".to_string(),
+        None => shell_escape(path),
+    }
+}
@@ -0,0 +1,357 @@
+//! Config file storage shared by the commands.
+//!
+//! Writes go to a temp file that is renamed over the target, so a crash never leaves a
+//! truncated file. Writers take an advisory lock on the file's directory (`.lock`), which
+//! also covers the read in a read-modify-write. The lock is reentrant within a thread, so
+//! store calls nested in an `update_json` closure do not wait on it. The version being
+//! replaced is kept as `<file>.bak` and is read instead when the file itself no longer parses.
+//!
+//! Files are stored as `{"schema_version": N, "data": ...}`. A file without the envelope is
+//! version 0. Older files are migrated on load and rewritten, keeping the original as
+//! `<file>.v<N>.bak`; files from a newer version are refused and never overwritten.
+
+use crate::error::AppError;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+use std::cell::RefCell;
+use std::ffi::OsString;
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Upgrades the data of a file by one schema version
+pub(crate) type Migration = fn(Value) -> Result<Value, AppError>;
+
+/// Current schema version of a kind of config file and how to get there
+pub(crate) struct Schema {
+    pub version: u32,
+    /// `migrations[n]` upgrades version n to n + 1
+    pub migrations: &'static [Migration],
+}
+
+/// Migration from a file written before schema versions existed: the data is unchanged,
+/// fields added since then take their defaults
+pub(crate) fn unversioned(data: Value) -> Result<Value, AppError> {
+    Ok(data)
+}
+
+thread_local! {
+    /// Config directories whose lock this thread holds
+    static HELD: RefCell<Vec<PathBuf>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Held while a config directory is being written; dropping it releases the lock.
+/// Empty when the thread already held the lock of the directory.
+struct DirLock {
+    held: Option<(File, PathBuf)>,
+}
+
+impl Drop for DirLock {
+    fn drop(&mut self) {
+        if let Some((_, dir)) = &self.held {
+            HELD.with(|held| held.borrow_mut().retain(|d| d != dir));
+        }
+    }
+}
+
+fn lock_dir(path: &Path) -> Result<DirLock, AppError> {
+    let dir = path
+        .parent()
+        .ok_or_else(|| AppError::Custom(format!("Invalid config path: {}", path.display())))?;
+    fs::create_dir_all(dir)?;
+    let dir = dir.canonicalize()?;
+    if HELD.with(|held| held.borrow().contains(&dir)) {
+        return Ok(DirLock { held: None });
+    }
+    let file = OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(dir.join(".lock"))?;
+    file.lock()?;
+    HELD.with(|held| held.borrow_mut().push(dir.clone()));
+    Ok(DirLock {
+        held: Some((file, dir)),
+    })
+}
+
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut name: OsString = path.file_name().unwrap_or_default().to_os_string();
+    name.push(suffix);
+    path.with_file_name(name)
+}
+
+/// IDs become file names in the config directory; refuse any that could leave it
+pub(crate) fn check_file_id(kind: &str, id: &str) -> Result<(), AppError> {
+    if id.is_empty() || id.contains(['/', '\\']) || id.contains("..") || id.starts_with('.') {
+        return Err(AppError::Custom(format!("Invalid {} ID: {:?}", kind, id)));
+    }
+    Ok(())
+}
+
+/// Rolling backup of a config file
+pub(crate) fn backup_path(path: &Path) -> PathBuf {
+    with_suffix(path, ".bak")
+}
+
+/// Copy of a file as it was before being migrated from `version`
+fn original_path(path: &Path, version: u32) -> PathBuf {
+    with_suffix(path, &format!(".v{}.bak", version))
+}
+
+/// Schema version and data of a stored document
+fn split_envelope(value: Value) -> (u32, Value) {
+    match value {
+        Value::Object(mut map) if map.contains_key("data") => match map.get("schema_version").and_then(Value::as_u64) {
+            Some(version) => (version as u32, map.remove("data").unwrap_or(Value::Null)),
+            None => (0, Value::Object(map)),
+        },
+        other => (0, other),
+    }
+}
+
+#[derive(Serialize)]
+struct Envelope<'a, T: ?Sized> {
+    schema_version: u32,
+    data: &'a T,
+}
+
+/// `data` as the contents of a file of `schema`, for files written outside this module
+/// (e.g. on a remote server)
+pub(crate) fn envelope<T: Serialize + ?Sized>(schema: &Schema, data: &T) -> Result<String, AppError> {
+    let doc = Envelope {
+        schema_version: schema.version,
+        data,
+    };
+    Ok(serde_json::to_string_pretty(&doc)?)
+}
+
+fn parse(path: &Path) -> Result<Value, AppError> {
+    let content = fs::read_to_string(path)?;
+    Ok(serde_json::from_str(&content)?)
+}
+
+/// The stored document (version, data) and the file it came from; the backup stands in
+/// for a file that no longer parses
+fn read_document(path: &Path) -> Result<Option<(u32, Value, PathBuf)>, AppError> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let (value, source) = match parse(path) {
+        Ok(value) => (value, path.to_path_buf()),
+        Err(e) => match parse(&backup_path(path)) {
+            Ok(value) => {
+                eprintln!("Reading backup of {}: {}", path.display(), e);
+                (value, backup_path(path))
+            }
+            Err(_) => return Err(e),
+        },
+    };
+    let (version, data) = split_envelope(value);
+    Ok(Some((version, data, source)))
+}
+
+fn check_supported(path: &Path, version: u32, schema: &Schema) -> Result<(), AppError> {
+    if version > schema.version {
+        return Err(AppError::UnsupportedSchemaVersion {
+            path: path.display().to_string(),
+            found: version,
+            supported: schema.version,
+        });
+    }
+    Ok(())
+}
+
+fn migrate(path: &Path, mut data: Value, from: u32, schema: &Schema) -> Result<Value, AppError> {
+    for version in from..schema.version {
+        let step = schema.migrations.get(version as usize).ok_or_else(|| {
+            AppError::Custom(format!("No migration from schema version {} for {}", version, path.display()))
+        })?;
+        data = step(data)?;
+    }
+    Ok(data)
+}
+
+/// Read a file with the directory lock held, migrating it in place when it is older than the schema
+fn load_locked<T: DeserializeOwned>(path: &Path, schema: &Schema) -> Result<Option<T>, AppError> {
+    let (version, data, source) = match read_document(path)? {
+        Some(doc) => doc,
+        None => return Ok(None),
+    };
+    check_supported(path, version, schema)?;
+    if version == schema.version {
+        return Ok(Some(serde_json::from_value(data)?));
+    }
+
+    let migrated = migrate(path, data, version, schema)?;
+    let value: T = serde_json::from_value(migrated.clone())?;
+    let original = original_path(path, version);
+    if !original.exists() {
+        fs::copy(&source, &original)?;
+    }
+    replace(path, &envelope(schema, &migrated)?)?;
+    Ok(Some(value))
+}
+
+/// Read a JSON config file, or None if it does not exist. A file that no longer parses
+/// is replaced by its backup when that one does; an older file is migrated first.
+pub(crate) fn read_json<T: DeserializeOwned>(path: &Path, schema: &Schema) -> Result<Option<T>, AppError> {
+    let (version, data, _) = match read_document(path)? {
+        Some(doc) => doc,
+        None => return Ok(None),
+    };
+    check_supported(path, version, schema)?;
+    if version == schema.version {
+        return Ok(Some(serde_json::from_value(data)?));
+    }
+    let _lock = lock_dir(path)?;
+    load_locked(path, schema)
+}
+
+/// Parse `content` as a revision of the config file at `path` (e.g. from version control),
+/// migrating it in memory; the file itself is not touched
+pub(crate) fn parse_json<T: DeserializeOwned>(path: &Path, content: &str, schema: &Schema) -> Result<T, AppError> {
+    let (version, data) = split_envelope(serde_json::from_str(content)?);
+    check_supported(path, version, schema)?;
+    Ok(serde_json::from_value(migrate(path, data, version, schema)?)?)
+}
+
+/// Refuse to replace a file written by a newer version
+fn check_writable(path: &Path, schema: &Schema) -> Result<(), AppError> {
+    match fs::read_to_string(path) {
+        Ok(content) => check_replaceable(path, &content, schema),
+        Err(_) => Ok(()),
+    }
+}
+
+/// Refuse to replace `content`, the current revision of the file at `path` read from
+/// elsewhere, when a newer version wrote it
+pub(crate) fn check_replaceable(path: &Path, content: &str, schema: &Schema) -> Result<(), AppError> {
+    if let Ok(value) = serde_json::from_str(content) {
+        check_supported(path, split_envelope(value).0, schema)?;
+    }
+    Ok(())
+}
+
+/// Replace a file with `content` without ever exposing a partial write. Must be called
+/// with the directory lock held.
+fn replace(path: &Path, content: &str) -> Result<(), AppError> {
+    if path.exists() && parse(path).is_ok() {
+        fs::copy(path, backup_path(path))?;
+    }
+    let tmp = with_suffix(path, ".tmp");
+    let mut file = File::create(&tmp)?;
+    file.write_all(content.as_bytes())?;
+    file.sync_all()?;
+    fs::rename(&tmp, path)?;
+    Ok(())
+}
+
+/// Write a JSON config file atomically, keeping the previous version as backup
+pub(crate) fn write_json<T: Serialize + ?Sized>(path: &Path, schema: &Schema, value: &T) -> Result<(), AppError> {
+    let content = envelope(schema, value)?;
+    let _lock = lock_dir(path)?;
+    check_writable(path, schema)?;
+    replace(path, &content)
+}
+
+/// Read-modify-write a JSON config file under the directory lock, so concurrent updates are
+/// not lost. A missing file starts from the default value; nothing is written when `f` fails.
+/// `f` may read and write other config files of the same directory on this thread.
+pub(crate) fn update_json<T, R>(
+    path: &Path,
+    schema: &Schema,
+    f: impl FnOnce(&mut T) -> Result<R, AppError>,
+) -> Result<R, AppError>
+where
+    T: DeserializeOwned + Serialize + Default,
+{
+    let _lock = lock_dir(path)?;
+    let mut value: T = load_locked(path, schema)?.unwrap_or_default();
+    let result = f(&mut value)?;
+    replace(path, &envelope(schema, &value)?)?;
+    Ok(result)
+}
+
+/// Delete a config file and its backup
+pub(crate) fn remove(path: &Path, schema: &Schema) -> Result<(), AppError> {
+    let _lock = lock_dir(path)?;
+    check_writable(path, schema)?;
+    for p in [path.to_path_buf(), backup_path(path)] {
+        if p.exists() {
+            fs::remove_file(p)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    static TEST_SCHEMA: Schema = Schema {
+        version: 1,
+        migrations: &[unversioned],
+    };
+
+    fn temp_file() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("skillpilot-store-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        dir.join("config.json")
+    }
+
+    #[test]
+    fn unversioned_file_is_migrated_and_kept() {
+        let path = temp_file();
+        fs::write(&path, r#"{"name":"a"}"#).unwrap();
+
+        let value: Value = read_json(&path, &TEST_SCHEMA).unwrap().unwrap();
+        assert_eq!(value, json!({"name": "a"}));
+
+        let stored: Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(stored, json!({"schema_version": 1, "data": {"name": "a"}}));
+        assert_eq!(fs::read_to_string(original_path(&path, 0)).unwrap(), r#"{"name":"a"}"#);
+    }
+
+    #[test]
+    fn newer_file_is_refused_and_left_alone() {
+        let path = temp_file();
+        let content = r#"{"schema_version":2,"data":{"name":"a"}}"#;
+        fs::write(&path, content).unwrap();
+
+        let read = read_json::<Value>(&path, &TEST_SCHEMA);
+        assert!(matches!(read, Err(AppError::UnsupportedSchemaVersion { found: 2, supported: 1, .. })));
+        assert!(write_json(&path, &TEST_SCHEMA, &json!({"name": "b"})).is_err());
+        assert!(remove(&path, &TEST_SCHEMA).is_err());
+        assert_eq!(fs::read_to_string(&path).unwrap(), content);
+    }
+
+    #[test]
+    fn nested_calls_reuse_the_directory_lock() {
+        let path = temp_file();
+        let other = path.with_file_name("other.json");
+        fs::write(&other, r#"{"name":"old"}"#).unwrap();
+
+        update_json(&path, &TEST_SCHEMA, |value: &mut Value| {
+            *value = read_json::<Value>(&other, &TEST_SCHEMA)?.unwrap_or_default();
+            write_json(&other, &TEST_SCHEMA, &json!({"name": "new"}))
+        })
+        .unwrap();
+
+        assert_eq!(read_json::<Value>(&path, &TEST_SCHEMA).unwrap(), Some(json!({"name": "old"})));
+        assert_eq!(read_json::<Value>(&other, &TEST_SCHEMA).unwrap(), Some(json!({"name": "new"})));
+    }
+
+    #[test]
+    fn corrupt_file_falls_back_to_backup() {
+        let path = temp_file();
+        write_json(&path, &TEST_SCHEMA, &json!({"name": "a"})).unwrap();
+        write_json(&path, &TEST_SCHEMA, &json!({"name": "b"})).unwrap();
+        fs::write(&path, "{\"schema_version\": 1, \"da").unwrap();
+
+        let value: Value = read_json(&path, &TEST_SCHEMA).unwrap().unwrap();
+        assert_eq!(value, json!({"name": "a"}));
+    }
+}
@@ -17,10 +17,16 @@ import { useSkillStore } from "./store/useSkillStore";
 import { useProfileStore } from "./store/useProfileStore";
 import { useProjectStore } from "./store/useProjectStore";
 import { useRemoteStore } from "./store/useRemoteStore";
-import { applyProfileLinks, recordProfileApply } from "./utils/tauri";
-import { resolveProfileSkillEntries } from "./utils/resolveProfileSkills";
+import { listen } from "@tauri-apps/api/event";
+import {
+  applyProfileLinks,
+  recordProfileApply,
+  resolveProfileForTarget,
+  SETTINGS_CHANGED_EVENT,
+} from "./utils/tauri";
 import type { Profile } from "./types/profile";
 import type { ProjectConfig } from "./types/project";
+import type { AppSettings } from "./types/settings";
 
 type View = "skills" | "profiles" | "projects" | "graph" | "stats";
 
@@ -35,6 +41,8 @@ export default function App() {
   const [editingProject, setEditingProject] = useState<ProjectConfig | null>(null);
 
   const scan = useSkillStore((s) => s.scan);
+  const loadRepoPath = useSkillStore((s) => s.loadRepoPath);
+  const applyRepoRoots = useSkillStore((s) => s.applyRepoRoots);
   const selectedSkillId = useSkillStore((s) => s.selectedSkillId);
   const selectSkill = useSkillStore((s) => s.selectSkill);
   const loadProfiles = useProfileStore((s) => s.loadProfiles);
@@ -46,10 +54,20 @@ export default function App() {
 
   useEffect(() => {
     loadServers();
-    scan();
+    loadRepoPath().then(scan);
     loadProfiles();
     loadProjects();
-  }, [loadServers, scan, loadProfiles, loadProjects]);
+  }, [loadServers, loadRepoPath, scan, loadProfiles, loadProjects]);
+
+  // Settings can also change outside this window (another window, the CLI)
+  useEffect(() => {
+    const unlisten = listen<AppSettings>(SETTINGS_CHANGED_EVENT, (event) => {
+      applyRepoRoots(event.payload.repo_roots);
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, [applyRepoRoots]);
 
   const handleNewProfile = () => {
     setEditingProfile(null);
@@ -81,17 +99,27 @@ export default function App() {
 
   const applyProfileToTargets = useCallback(
     async (profileId: string, targetPaths: (string | null)[]) => {
-      const skills = useSkillStore.getState().skills;
+      const repoPath = useSkillStore.getState().repoPath;
       const profile = useProfileStore
         .getState()
         .profiles.find((p) => p.id === profileId);
       if (!profile) return;
 
-      const entries = resolveProfileSkillEntries(profile, skills);
-      if (entries.length === 0) return;
-
       for (const targetPath of targetPaths) {
-        await applyProfileLinks(entries, targetPath);
+        // 条件条目按目标求值，被跳过的条目附带原因
+        const resolution = await resolveProfileForTarget(
+          profileId,
+          targetPath,
+          repoPath || undefined
+        );
+        for (const skipped of resolution.skipped) {
+          console.info(
+            `Skipped ${skipped.skill_id} for ${targetPath ?? "user level"}: ${skipped.reasons.join("; ")}`
+          );
+        }
+        if (resolution.entries.length > 0) {
+          await applyProfileLinks(resolution.entries, targetPath);
+        }
 
         // 配置即链接: 同步更新项目的 profile_ids，确保 UI 能回显
         if (targetPath !== null) {
@@ -29,13 +29,10 @@ export function SettingsModal({ onClose, initialTab = "general" }: SettingsModal
   const handleSave = useCallback(async () => {
     if (!path.trim()) return;
     setSaving(true);
-    setRepoPath(path.trim());
-    // Small delay to let state update propagate, then rescan
-    setTimeout(async () => {
-      await scan();
-      setSaving(false);
-      onClose();
-    }, 50);
+    await setRepoPath(path.trim());
+    await scan();
+    setSaving(false);
+    onClose();
   }, [path, setRepoPath, scan, onClose]);
 
   const hasChanged = path.trim() !== repoPath;
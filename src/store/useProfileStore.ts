@@ -4,15 +4,16 @@ import {
   listProfiles,
   saveProfile as apiSaveProfile,
   deleteProfile as apiDeleteProfile,
+  resolveProjectLinkEntries,
   syncProjectLinks,
 } from "../utils/tauri";
 import {
   remoteListProfiles,
   remoteSaveProfile,
   remoteDeleteProfile,
+  remoteResolveProjectLinkEntries,
   remoteSyncProjectLinks,
 } from "../utils/tauri-remote";
-import { resolveProjectSkillEntries } from "../utils/resolveProfileSkills";
 import { useRemoteStore } from "./useRemoteStore";
 
 /**
@@ -27,22 +28,20 @@ async function syncProjectsForProfile(profileId: string) {
 
   const serverId = useRemoteStore.getState().activeServerId;
   const projects = useProjectStore.getState().projects;
-  const skills = useSkillStore.getState().skills;
-  const profiles = useProfileStore.getState().profiles;
+  const repoPath = useSkillStore.getState().repoPath || undefined;
 
   const affected = projects.filter((p) => p.profile_ids.includes(profileId));
   for (const project of affected) {
     if (!project.path) continue;
     try {
-      const entries = resolveProjectSkillEntries(
-        project.profile_ids,
-        project.extra_skill_ids,
-        profiles,
-        skills
-      );
       if (serverId) {
+        const entries = await remoteResolveProjectLinkEntries(
+          serverId,
+          project
+        );
         await remoteSyncProjectLinks(serverId, entries, project.path);
       } else {
+        const entries = await resolveProjectLinkEntries(project, repoPath);
         await syncProjectLinks(entries, project.path);
       }
     } catch (err) {
@@ -75,9 +74,10 @@ export const useProfileStore = create<ProfileState>((set, get) => ({
     const serverId = useRemoteStore.getState().activeServerId;
     set({ loading: true });
     try {
+      const { useSkillStore } = await import("./useSkillStore");
       const profiles = serverId
         ? await remoteListProfiles(serverId)
-        : await listProfiles();
+        : await listProfiles(useSkillStore.getState().repoPath || undefined);
       console.log("[ProfileStore] loaded", profiles.length, "profiles:", profiles.map(p => p.id));
       set({ profiles, loading: false });
     } catch (err) {
@@ -95,15 +95,8 @@ export const useProfileStore = create<ProfileState>((set, get) => ({
     } else {
       await apiSaveProfile(profile);
     }
-    const { profiles } = get();
-    const existing = profiles.findIndex((p) => p.id === profile.id);
-    if (existing >= 0) {
-      const updated = [...profiles];
-      updated[existing] = profile;
-      set({ profiles: updated });
-    } else {
-      set({ profiles: [...profiles, profile] });
-    }
+    // Reload so resolved skill lists (inheritance, dynamic queries) are recomputed
+    await get().loadProfiles();
 
     // Cascade: re-sync all projects that use this profile
     await syncProjectsForProfile(profile.id);
@@ -4,17 +4,17 @@ import {
   listProjects,
   saveProject as apiSaveProject,
   deleteProject as apiDeleteProject,
+  resolveProjectLinkEntries,
   syncProjectLinks,
 } from "../utils/tauri";
 import {
   remoteListProjects,
   remoteSaveProject,
   remoteDeleteProject,
+  remoteResolveProjectLinkEntries,
   remoteSyncProjectLinks,
 } from "../utils/tauri-remote";
-import { resolveProjectSkillEntries } from "../utils/resolveProfileSkills";
 import { useSkillStore } from "./useSkillStore";
-import { useProfileStore } from "./useProfileStore";
 import { useRemoteStore } from "./useRemoteStore";
 
 interface ProjectState {
@@ -70,17 +70,17 @@ export const useProjectStore = create<ProjectState>((set, get) => ({
     // Auto-sync symlinks for this project (creates missing + removes stale)
     if (project.path) {
       try {
-        const skills = useSkillStore.getState().skills;
-        const profiles = useProfileStore.getState().profiles;
-        const entries = resolveProjectSkillEntries(
-          project.profile_ids,
-          project.extra_skill_ids,
-          profiles,
-          skills
-        );
         if (serverId) {
+          const entries = await remoteResolveProjectLinkEntries(
+            serverId,
+            project
+          );
           await remoteSyncProjectLinks(serverId, entries, project.path);
         } else {
+          const entries = await resolveProjectLinkEntries(
+            project,
+            useSkillStore.getState().repoPath || undefined
+          );
           await syncProjectLinks(entries, project.path);
         }
       } catch (err) {
@@ -1,6 +1,12 @@
 import { create } from "zustand";
 import type { Skill, LinkStatus } from "../types/skill";
-import { scanSkillsRepo, recordScan, refreshLinkStatuses } from "../utils/tauri";
+import {
+  scanSkillsRepo,
+  recordScan,
+  refreshLinkStatuses,
+  getSettings,
+  updateSettings,
+} from "../utils/tauri";
 import { remoteScanSkillsRepo } from "../utils/tauri-remote";
 import { useRemoteStore } from "./useRemoteStore";
 
@@ -18,7 +24,9 @@ interface SkillState {
   loading: boolean;
   error: string | null;
 
-  setRepoPath: (path: string) => void;
+  setRepoPath: (path: string) => Promise<void>;
+  loadRepoPath: () => Promise<void>;
+  applyRepoRoots: (repoRoots: string[]) => void;
   scan: () => Promise<void>;
   setSearchQuery: (query: string) => void;
   setGroupBy: (groupBy: GroupBy) => void;
@@ -62,7 +70,8 @@ export const useSkillStore = create<SkillState>((set, get) => ({
   skills: [],
   filteredSkills: [],
   selectedSkillId: null,
-  repoPath: "/Users/eamanc/Documents/pe/skills",
+  // Empty until loaded from the settings; the backend then picks the configured root
+  repoPath: "",
   searchQuery: "",
   groupBy: "repo",
   filterRepo: null,
@@ -70,7 +79,32 @@ export const useSkillStore = create<SkillState>((set, get) => ({
   loading: false,
   error: null,
 
-  setRepoPath: (path) => set({ repoPath: path }),
+  // The repo path lives in the backend settings (first repo root)
+  setRepoPath: async (path) => {
+    set({ repoPath: path });
+    try {
+      const settings = await getSettings();
+      await updateSettings({
+        ...settings,
+        repo_roots: [path, ...settings.repo_roots.filter((r) => r !== path)],
+      });
+    } catch (err) {
+      console.error("Failed to save repo path:", err);
+    }
+  },
+
+  loadRepoPath: async () => {
+    try {
+      const settings = await getSettings();
+      get().applyRepoRoots(settings.repo_roots);
+    } catch (err) {
+      console.error("Failed to load settings:", err);
+    }
+  },
+
+  applyRepoRoots: (repoRoots) => {
+    if (repoRoots.length > 0) set({ repoPath: repoRoots[0] });
+  },
 
   scan: async () => {
     const { repoPath, searchQuery, filterRepo, filterStatus } = get();
@@ -79,7 +113,7 @@ export const useSkillStore = create<SkillState>((set, get) => ({
     try {
       const skills = serverId
         ? await remoteScanSkillsRepo(serverId)
-        : await scanSkillsRepo(repoPath);
+        : await scanSkillsRepo(repoPath || undefined);
       if (!serverId) await recordScan();
       const filteredSkills = applyFilters(
         skills,
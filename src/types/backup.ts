@@ -0,0 +1,38 @@
+export interface BackupManifest {
+  format_version: number;
+  created_at: string;
+  home_dir: string | null;
+  repo_roots: string[];
+  /** Files in the archive, relative to the data root */
+  files: string[];
+  /** Servers whose Keychain passwords are in the archive (encrypted) */
+  secret_server_ids: string[];
+}
+
+export type ImportMode = "Merge" | "Replace";
+
+export interface PathMapping {
+  from: string;
+  to: string;
+}
+
+export interface BackupExportResult {
+  path: string;
+  manifest: BackupManifest;
+}
+
+export interface BackupImportResult {
+  imported_files: string[];
+  /** Files left alone because a local copy exists (merge only) */
+  skipped_files: string[];
+  imported_project_ids: string[];
+  imported_server_ids: string[];
+  skipped_project_ids: string[];
+  skipped_server_ids: string[];
+  remapped_paths: PathMapping[];
+  restored_secret_ids: string[];
+  /** The archive holds secrets but no passphrase was given */
+  secrets_skipped: boolean;
+  /** Archive of the configuration as it was before a replacing import */
+  previous_backup: string | null;
+}
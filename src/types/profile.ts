@@ -1,3 +1,83 @@
+export type ProfileKind = "Static" | "Dynamic";
+
+export interface FrontmatterMatch {
+  field: string;
+  /** null matches any value */
+  value: string | null;
+}
+
+/** Saved query of a dynamic profile; a skill matching any criterion is a member */
+export interface ProfileQuery {
+  source_repos: string[];
+  categories: string[];
+  tags: string[];
+  /** Globs over skill IDs, e.g. "marketingskills/skills/*" */
+  path_globs: string[];
+  frontmatter: FrontmatterMatch[];
+}
+
+/** Condition on a profile entry, evaluated against the target it is applied to */
+export type EntryCondition =
+  /** Path relative to the project root */
+  | { type: "FileExists"; path: string }
+  /** Glob relative to the project root, e.g. "**\/*.tf" */
+  | { type: "GlobMatch"; pattern: string }
+  | { type: "ProjectTag"; tag: string }
+  | { type: "Scope"; scope: "User" | "Project" }
+  /** "linux" | "macos" | "windows" */
+  | { type: "HostOs"; os: string }
+  | { type: "Local" }
+  /** Any remote server when server_ids is empty */
+  | { type: "Remote"; server_ids?: string[] }
+  | { type: "AnyOf"; conditions: EntryCondition[] };
+
+export interface SkippedEntry {
+  skill_id: string;
+  profile_id: string;
+  reasons: string[];
+}
+
+export interface ConflictCandidate {
+  skill_id: string;
+  source_path: string;
+  /** null for a project's extra skills */
+  profile_id: string | null;
+}
+
+/** Different skills competing for one link name */
+export interface LinkConflict {
+  link_name: string;
+  candidates: ConflictCandidate[];
+  /** Skill ID that gets the link */
+  winner: string;
+  /** Whether the winner is recorded in the project config */
+  explicit: boolean;
+}
+
+/** A profile resolved for one target (user level or a project) */
+export interface TargetResolution {
+  profile_id: string;
+  target_path: string | null;
+  /** [link name, source path] pairs */
+  entries: [string, string][];
+  skipped: SkippedEntry[];
+  unresolved_skill_ids: string[];
+  conflicts: LinkConflict[];
+}
+
+export interface ResolvedSkill {
+  skill_id: string;
+  /** Profile that declares the skill (the profile itself or an ancestor) */
+  from_profile_id: string;
+}
+
+export interface ResolvedProfile {
+  skills: ResolvedSkill[];
+  missing_parents: string[];
+  cycle: string[] | null;
+  query_pending: boolean;
+}
+
 export interface Profile {
   id: string;
   name: string;
@@ -5,4 +85,16 @@ export interface Profile {
   color: string;
   skill_ids: string[];
   is_preset: boolean;
+  /** skill ID -> link name */
+  aliases?: Record<string, string>;
+  /** Parent profile IDs */
+  extends?: string[];
+  /** Skill IDs removed from the inherited skills */
+  excludes?: string[];
+  kind?: ProfileKind;
+  query?: ProfileQuery | null;
+  /** skill ID -> conditions that must all hold for the skill to be linked */
+  conditions?: Record<string, EntryCondition[]>;
+  /** Flattened skill list computed by the backend (not persisted) */
+  resolved?: ResolvedProfile;
 }
@@ -4,4 +4,10 @@ export interface ProjectConfig {
   path: string;
   profile_ids: string[];
   extra_skill_ids: string[];
+  /** skill ID -> link name, overrides profile aliases */
+  aliases?: Record<string, string>;
+  /** Matched by ProjectTag profile entry conditions */
+  tags?: string[];
+  /** link name -> skill ID that gets the link when several skills claim the name */
+  link_winners?: Record<string, string>;
 }
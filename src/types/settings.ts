@@ -0,0 +1,27 @@
+export type LinkMode = "Absolute" | "Relative";
+
+export interface Thresholds {
+  max_profile_versions: number;
+  max_suggestions: number;
+  max_glob_depth: number;
+}
+
+export interface SyncSettings {
+  /** Whether the data root is kept as a git working copy */
+  enabled: boolean;
+  /** URL or path of the remote repository */
+  remote: string | null;
+  branch: string;
+}
+
+export interface AppSettings {
+  /** Skills repositories; the first one is used when a command gets no repo path */
+  repo_roots: string[];
+  default_link_mode: LinkMode;
+  /** Link target IDs skills are applied to by default */
+  target_ids: string[];
+  /** Globs (relative to the repo root) of skill directories left out of scans */
+  ignore_patterns: string[];
+  thresholds: Thresholds;
+  sync: SyncSettings;
+}
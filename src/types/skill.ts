@@ -11,6 +11,7 @@ export interface Skill {
   has_scripts: boolean;
   has_references: boolean;
   link_status_user: LinkStatus;
+  link_name_user?: string | null;
   dependencies: string[];
   raw_content: string;
 }
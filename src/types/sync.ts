@@ -0,0 +1,49 @@
+import type { Profile } from "./profile";
+
+export interface SyncStatus {
+  enabled: boolean;
+  /** Whether the data root is a git repository */
+  initialized: boolean;
+  remote: string | null;
+  branch: string;
+  head: string | null;
+  /** Files with changes not committed yet, relative to the data root */
+  changed_files: string[];
+  ahead: number;
+  behind: number;
+  /** A pull stopped on conflicts that are not all resolved */
+  merging: boolean;
+  conflicts: string[];
+}
+
+export interface SyncCommitResult {
+  /** The new commit, null when there was nothing to commit */
+  commit: string | null;
+  files: string[];
+}
+
+export interface ProfileConflict {
+  profile_id: string;
+  /** null where the profile was deleted or does not parse */
+  ours: Profile | null;
+  theirs: Profile | null;
+  changed_fields: string[];
+}
+
+export interface SyncConflict {
+  path: string;
+  base: string | null;
+  ours: string | null;
+  theirs: string | null;
+  /** Set for profile files */
+  profile: ProfileConflict | null;
+}
+
+export type ConflictResolution = "Ours" | "Theirs";
+
+export interface SyncMergeResult {
+  updated_files: string[];
+  /** Conflicts left to resolve; the merge is committed once there are none */
+  conflicts: SyncConflict[];
+  commit: string | null;
+}
@@ -79,6 +79,15 @@ export const remoteToggleSkillProjectLevel = (
     currentlyActive,
   });
 
+export const remoteResolveProjectLinkEntries = (
+  serverId: string,
+  project: ProjectConfig
+) =>
+  invoke<[string, string][]>("remote_resolve_project_link_entries", {
+    serverId,
+    project,
+  });
+
 export const remoteSyncProjectLinks = (
   serverId: string,
   skillEntries: [string, string][],
@@ -1,10 +1,23 @@
 import { invoke } from "@tauri-apps/api/core";
 import type { Skill } from "../types/skill";
-import type { Profile } from "../types/profile";
+import type { Profile, TargetResolution } from "../types/profile";
 import type { ProjectConfig } from "../types/project";
+import type { AppSettings } from "../types/settings";
+import type {
+  BackupExportResult,
+  BackupImportResult,
+  ImportMode,
+  PathMapping,
+} from "../types/backup";
+import type {
+  ConflictResolution,
+  SyncCommitResult,
+  SyncMergeResult,
+  SyncStatus,
+} from "../types/sync";
 
 // Scanner
-export const scanSkillsRepo = (repoPath: string) =>
+export const scanSkillsRepo = (repoPath?: string) =>
   invoke<Skill[]>("scan_skills_repo", { repoPath });
 
 export const refreshLinkStatuses = (skills: Skill[]) =>
@@ -41,6 +54,26 @@ export const applyProfileLinks = (
 ) =>
   invoke<string[]>("apply_profile_links", { skillEntries, targetPath });
 
+export const resolveProfileForTarget = (
+  profileId: string,
+  targetPath: string | null,
+  repoPath?: string
+) =>
+  invoke<TargetResolution>("resolve_profile_for_target", {
+    profileId,
+    targetPath,
+    repoPath,
+  });
+
+export const resolveProjectLinkEntries = (
+  project: ProjectConfig,
+  repoPath?: string
+) =>
+  invoke<[string, string][]>("resolve_project_link_entries", {
+    project,
+    repoPath,
+  });
+
 export const syncProjectLinks = (
   skillEntries: [string, string][],
   projectPath: string
@@ -59,7 +92,8 @@ export const getUserSkillLinks = () =>
   invoke<[string, string, boolean][]>("get_user_skill_links");
 
 // Profiles
-export const listProfiles = () => invoke<Profile[]>("list_profiles");
+export const listProfiles = (repoPath?: string) =>
+  invoke<Profile[]>("list_profiles", { repoPath });
 export const saveProfile = (profile: Profile) =>
   invoke<Profile>("save_profile", { profile });
 export const deleteProfile = (id: string) =>
@@ -71,6 +105,16 @@ export const getProfile = (id: string) =>
 export const listProjects = () => invoke<ProjectConfig[]>("list_projects");
 export const saveProject = (project: ProjectConfig) =>
   invoke<ProjectConfig>("save_project", { project });
+export const setProjectLinkWinner = (
+  projectId: string,
+  linkName: string,
+  skillId: string | null
+) =>
+  invoke<ProjectConfig>("set_project_link_winner", {
+    projectId,
+    linkName,
+    skillId,
+  });
 export const deleteProject = (id: string) =>
   invoke<void>("delete_project", { id });
 
@@ -92,3 +136,50 @@ export const recordProfileApply = (profileId: string) =>
 export const recordScan = () => invoke<void>("record_scan");
 export const recordClean = (count: number) =>
   invoke<void>("record_clean", { count });
+
+// Settings
+export const SETTINGS_CHANGED_EVENT = "settings-changed";
+export const getSettings = () => invoke<AppSettings>("get_settings");
+export const updateSettings = (settings: AppSettings) =>
+  invoke<AppSettings>("update_settings", { settings });
+
+// Backup
+export const exportBackup = (
+  path: string,
+  includeSecrets: boolean,
+  passphrase: string | null
+) =>
+  invoke<BackupExportResult>("export_backup", {
+    path,
+    includeSecrets,
+    passphrase,
+  });
+export const importBackup = (
+  path: string,
+  mode: ImportMode,
+  passphrase: string | null,
+  pathMappings: PathMapping[],
+  repoPath: string | null
+) =>
+  invoke<BackupImportResult>("import_backup", {
+    path,
+    mode,
+    passphrase,
+    pathMappings,
+    repoPath,
+  });
+
+// Sync
+export const getSyncStatus = () => invoke<SyncStatus>("get_sync_status");
+export const enableSync = (remote: string | null, branch: string | null) =>
+  invoke<SyncStatus>("enable_sync", { remote, branch });
+export const disableSync = () => invoke<SyncStatus>("disable_sync");
+export const syncCommit = (message: string | null) =>
+  invoke<SyncCommitResult>("sync_commit", { message });
+export const syncPull = () => invoke<SyncMergeResult>("sync_pull");
+export const resolveSyncConflict = (
+  path: string,
+  resolution: ConflictResolution
+) => invoke<SyncMergeResult>("resolve_sync_conflict", { path, resolution });
+export const abortSyncMerge = () => invoke<SyncStatus>("abort_sync_merge");
+export const syncPush = () => invoke<SyncStatus>("sync_push");